@@ -1,12 +1,16 @@
-/// AES-256 加密模块，支持 RISC-V 硬件加速
-///
-/// 该模块提供 AES-256 对称加密算法的实现，支持软件实现和 RISC-V 硬件加速。
-/// AES-256 是 NIST 标准的高级加密算法，使用 256 位密钥提供高安全性。
+//! AES-256 加密模块，支持 RISC-V 硬件加速
+//!
+//! 该模块提供 AES-256 对称加密算法的实现，支持软件实现和 RISC-V 硬件加速。
+//! AES-256 是 NIST 标准的高级加密算法，使用 256 位密钥提供高安全性。
 
 #[allow(dead_code)]
-use crate::crypto::riscv_ext::RiscVCryptoExt;
+use crate::crypto::riscv_ext::{Aes256KeySchedule, RiscVCryptoExt};
+use crate::crypto::secret::{Aes256Key, SecretVec};
+use rand::TryRng;
+use rand::rngs::SysRng;
 use std::error::Error;
 use std::fmt;
+use zeroize::Zeroize;
 
 /// AES-256 加密/解密操作中发生的错误
 #[allow(dead_code)]
@@ -23,27 +27,113 @@ impl fmt::Display for Aes256Error {
 
 impl Error for Aes256Error {}
 
+/// AES 分组长度（字节）
+pub const AES_BLOCK_SIZE: usize = 16;
+/// AES-256 轮数
+const AES256_ROUNDS: usize = 14;
+/// AES-256 扩展密钥长度（15 个 16 字节轮密钥）
+const AES256_EXPANDED_KEY_SIZE: usize = 240;
+
+/// AES-GCM nonce 长度（字节）
+pub const GCM_NONCE_SIZE: usize = 12;
+/// AES-GCM 认证标签长度（字节）
+pub const GCM_TAG_SIZE: usize = 16;
+
+/// AES-256-GCM 加密结果
+///
+/// 包含解密所需的全部公开参数：nonce、密文和认证标签。
+/// 密文长度与明文相同。
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AesGcmCiphertext {
+    /// 96 位 nonce
+    pub nonce: [u8; GCM_NONCE_SIZE],
+    /// 密文
+    pub ciphertext: Vec<u8>,
+    /// 128 位认证标签
+    pub tag: [u8; GCM_TAG_SIZE],
+}
+
+#[allow(dead_code)]
+impl AesGcmCiphertext {
+    /// 编码为 `nonce || ciphertext || tag`
+    ///
+    /// # 返回
+    ///
+    /// 长度为密文长度 + 28 字节的字节串
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(GCM_NONCE_SIZE + self.ciphertext.len() + GCM_TAG_SIZE);
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes.extend_from_slice(&self.tag);
+        bytes
+    }
+
+    /// 从 `nonce || ciphertext || tag` 解码
+    ///
+    /// # 参数
+    ///
+    /// * `bytes` - `to_bytes` 生成的字节串
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(AesGcmCiphertext)`: 解码结果
+    /// - `Err(Box<dyn Error>)`: 长度不足 28 字节
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if bytes.len() < GCM_NONCE_SIZE + GCM_TAG_SIZE {
+            return Err(Box::new(Aes256Error {
+                message: "Ciphertext too short".to_string(),
+            }));
+        }
+
+        let tag_start = bytes.len() - GCM_TAG_SIZE;
+        let mut nonce = [0u8; GCM_NONCE_SIZE];
+        let mut tag = [0u8; GCM_TAG_SIZE];
+        nonce.copy_from_slice(&bytes[..GCM_NONCE_SIZE]);
+        tag.copy_from_slice(&bytes[tag_start..]);
+
+        Ok(AesGcmCiphertext {
+            nonce,
+            ciphertext: bytes[GCM_NONCE_SIZE..tag_start].to_vec(),
+            tag,
+        })
+    }
+}
+
 /// AES-256 加密器，支持 RISC-V 硬件加速
 #[allow(dead_code)]
 pub struct Aes256Riscv {
     accelerator: Box<dyn RiscVCryptoExt>,
-    expanded_key: Vec<u8>,
+    expanded_key: SecretVec,
 }
 
 #[allow(dead_code)]
 impl Aes256Riscv {
     /// 创建新的 AES-256 加密器实例
     ///
-    /// 初始化时自动创建 RISC-V 硬件加速器实例。
-    /// 如果硬件加速器不可用，将使用软件回退实现。
+    /// 初始化时通过 `crate::crypto::backend::default_backend()` 选择加速器后端，
+    /// 可用环境变量 `ZK_ROLLUP_CRYPTO_BACKEND` 切换。
     ///
     /// # 返回
     ///
     /// 新的 `Aes256Riscv` 实例，可立即用于加密/解密操作
     pub fn new() -> Self {
+        Self::with_accelerator(crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 AES-256 加密器实例
+    ///
+    /// # 参数
+    ///
+    /// * `accelerator` - 执行 AES-GCM 和加速分组运算的后端
+    ///
+    /// # 返回
+    ///
+    /// 新的 `Aes256Riscv` 实例
+    pub fn with_accelerator(accelerator: Box<dyn RiscVCryptoExt>) -> Self {
         Aes256Riscv {
-            accelerator: Box::new(crate::crypto::riscv_ext::HardwareAccelerator::new()),
-            expanded_key: vec![],
+            accelerator,
+            expanded_key: SecretVec::default(),
         }
     }
 
@@ -58,311 +148,104 @@ impl Aes256Riscv {
     ///
     /// # 返回
     ///
-    /// - `Ok(Vec<u8>)`: 240 字节的扩展密钥
+    /// - `Ok(SecretVec)`: 240 字节的扩展密钥，释放时清零
     /// - `Err(Box<dyn Error>)`: 密钥无效或处理失败
     ///
     /// # 扩展密钥布局
     ///
-    /// 扩展密钥前 32 字节与主密钥相同，后续每轮 16 字节，
-    /// 共 15 轮需要 240 字节扩展密钥。
-    pub fn key_expansion(&mut self, key: &[u8; 32]) -> Result<Vec<u8>, Box<dyn Error>> {
-        let mut round_keys = vec![0u8; 240];
-        round_keys[..32].copy_from_slice(key);
+    /// 扩展密钥由 60 个 32 位字组成（FIPS-197 5.2 节，Nk = 8）。
+    /// 前 32 字节与主密钥相同，之后每个字 w[i] = w[i-8] ^ temp，其中：
+    /// - i % 8 == 0 时 temp = SubWord(RotWord(w[i-1])) ^ Rcon(i/8)
+    /// - i % 8 == 4 时 temp = SubWord(w[i-1])
+    /// - 其余情况 temp = w[i-1]
+    pub fn key_expansion(&mut self, key: &Aes256Key) -> Result<SecretVec, Box<dyn Error>> {
+        let round_keys = Self::expand_key(key.expose_secret());
+        self.expanded_key = round_keys.clone();
+        Ok(round_keys)
+    }
 
-        for i in 1..=14 {
-            let start = (i - 1) * 16;
-            let prev13 = round_keys[start + 13];
-            let prev14 = round_keys[start + 14];
-            let prev15 = round_keys[start + 15];
-            let prev12 = round_keys[start + 12];
-
-            let rot_word = [prev13, prev14, prev15, prev12];
-            let mut substituted = [0u8; 4];
-            for (j, byte) in rot_word.iter().enumerate() {
-                substituted[j] = Self::substitute_byte(*byte);
-            }
+    /// 计算 AES-256 扩展密钥，不修改实例状态
+    pub(crate) fn expand_key(key: &[u8; 32]) -> SecretVec {
+        let mut round_keys = vec![0u8; AES256_EXPANDED_KEY_SIZE];
+        round_keys[..32].copy_from_slice(key);
 
-            let rcon = Self::rcon(i);
+        for i in 8..60 {
+            let mut temp = [0u8; 4];
+            temp.copy_from_slice(&round_keys[(i - 1) * 4..i * 4]);
 
-            for j in 0..4 {
-                round_keys[start + 16 + j] = round_keys[start + j] ^ substituted[j] ^ rcon[j];
+            if i % 8 == 0 {
+                temp.rotate_left(1);
+                for byte in temp.iter_mut() {
+                    *byte = Self::substitute_byte(*byte);
+                }
+                let rcon = Self::rcon(i / 8);
+                for j in 0..4 {
+                    temp[j] ^= rcon[j];
+                }
+            } else if i % 8 == 4 {
+                for byte in temp.iter_mut() {
+                    *byte = Self::substitute_byte(*byte);
+                }
             }
 
-            for j in 1..4 {
-                let idx = start + 16 + j;
-                let prev_idx = idx - 16;
-                round_keys[idx] = round_keys[prev_idx] ^ round_keys[start + 16 + j - 1];
+            for j in 0..4 {
+                round_keys[i * 4 + j] = round_keys[(i - 8) * 4 + j] ^ temp[j];
             }
+            temp.zeroize();
         }
 
-        self.expanded_key = round_keys.clone();
-        Ok(round_keys)
+        SecretVec::new(round_keys)
+    }
+
+    /// S-Box 字节替换
+    ///
+    /// 按 FIPS-197 5.1.1 节的定义直接计算，不使用查找表：
+    /// 先求 GF(2^8) 乘法逆元（0 映射为 0），再做仿射变换
+    /// b ^ (b <<< 1) ^ (b <<< 2) ^ (b <<< 3) ^ (b <<< 4) ^ 0x63。
+    pub(crate) fn substitute_byte(byte: u8) -> u8 {
+        let b = Self::gf_inverse(byte);
+        b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+    }
+
+    /// 逆 S-Box 字节替换
+    ///
+    /// 先做逆仿射变换 (b <<< 1) ^ (b <<< 3) ^ (b <<< 6) ^ 0x05，再求 GF(2^8) 逆元。
+    pub(crate) fn inv_substitute_byte(byte: u8) -> u8 {
+        let b = byte.rotate_left(1) ^ byte.rotate_left(3) ^ byte.rotate_left(6) ^ 0x05;
+        Self::gf_inverse(b)
     }
 
-    /// S-Box 字节替换表
-    ///
-    /// AES S-Box 是固定的双射替换表，用于密钥扩展和字节替换操作。
-    /// 每个输入字节映射到对应的输出字节，提供非线性变换。
-    #[allow(dead_code)]
-    fn substitute_byte(byte: u8) -> u8 {
-        match byte {
-            0x00 => 0x63,
-            0x01 => 0x7c,
-            0x02 => 0x77,
-            0x03 => 0x7b,
-            0x04 => 0xf2,
-            0x05 => 0x6b,
-            0x06 => 0x6f,
-            0x07 => 0xc5,
-            0x08 => 0x30,
-            0x09 => 0x01,
-            0x0a => 0x67,
-            0x0b => 0x2b,
-            0x0c => 0xfe,
-            0x0d => 0xd7,
-            0x0e => 0xab,
-            0x0f => 0x76,
-            0x10 => 0xca,
-            0x11 => 0x82,
-            0x12 => 0xc9,
-            0x13 => 0x7d,
-            0x14 => 0xfa,
-            0x15 => 0x59,
-            0x16 => 0x47,
-            0x17 => 0xf0,
-            0x18 => 0xae,
-            0x19 => 0xd1,
-            0x1a => 0x30,
-            0x1b => 0xa5,
-            0x1c => 0xf3,
-            0x1d => 0xac,
-            0x1e => 0xe2,
-            0x1f => 0x3f,
-            0x20 => 0x52,
-            0x21 => 0x6c,
-            0x22 => 0x6b,
-            0x23 => 0xfe,
-            0x24 => 0x14,
-            0x25 => 0xf7,
-            0x26 => 0x2a,
-            0x27 => 0xed,
-            0x28 => 0x24,
-            0x29 => 0x82,
-            0x2a => 0xc5,
-            0x2b => 0x7f,
-            0x2c => 0x11,
-            0x2d => 0xb0,
-            0x2e => 0x5f,
-            0x2f => 0x14,
-            0x30 => 0x56,
-            0x31 => 0x08,
-            0x32 => 0x12,
-            0x33 => 0x77,
-            0x34 => 0x13,
-            0x35 => 0x94,
-            0x36 => 0xa0,
-            0x37 => 0xf9,
-            0x38 => 0xe1,
-            0x39 => 0xd8,
-            0x3a => 0x53,
-            0x3b => 0x03,
-            0x3c => 0x1d,
-            0x3d => 0x24,
-            0x3e => 0x20,
-            0x3f => 0xbd,
-            0x40 => 0x34,
-            0x41 => 0x58,
-            0x42 => 0x08,
-            0x43 => 0xb7,
-            0x44 => 0x35,
-            0x45 => 0xd5,
-            0x46 => 0xc0,
-            0x47 => 0xa7,
-            0x48 => 0x9d,
-            0x49 => 0x84,
-            0x4a => 0x70,
-            0x4b => 0x8e,
-            0x4c => 0xb1,
-            0x4d => 0xc4,
-            0x4e => 0x58,
-            0x4f => 0xf0,
-            0x50 => 0xda,
-            0x51 => 0x66,
-            0x52 => 0x11,
-            0x53 => 0xd0,
-            0x54 => 0x50,
-            0x55 => 0xf8,
-            0x56 => 0x28,
-            0x57 => 0x8c,
-            0x58 => 0xb0,
-            0x59 => 0x4e,
-            0x5a => 0x5a,
-            0x5b => 0x18,
-            0x5c => 0xa4,
-            0x5d => 0x3c,
-            0x5e => 0xc1,
-            0x5f => 0xa8,
-            0x60 => 0x51,
-            0x61 => 0x21,
-            0x62 => 0x72,
-            0x63 => 0x74,
-            0x64 => 0xb9,
-            0x65 => 0xf1,
-            0x66 => 0x09,
-            0x67 => 0xc5,
-            0x68 => 0x6a,
-            0x69 => 0xaa,
-            0x6a => 0xe5,
-            0x6b => 0x15,
-            0x6c => 0x2d,
-            0x6d => 0xe2,
-            0x6e => 0xf4,
-            0x6f => 0x0e,
-            0x70 => 0x12,
-            0x71 => 0xeb,
-            0x72 => 0x22,
-            0x73 => 0x0c,
-            0x74 => 0xd6,
-            0x75 => 0xeb,
-            0x76 => 0x6a,
-            0x77 => 0x19,
-            0x78 => 0x55,
-            0x79 => 0x74,
-            0x7a => 0xa2,
-            0x7b => 0x25,
-            0x7c => 0x17,
-            0x7d => 0x2b,
-            0x7e => 0x41,
-            0x7f => 0x56,
-            0x80 => 0x21,
-            0x81 => 0xcf,
-            0x82 => 0x4f,
-            0x83 => 0xd5,
-            0x84 => 0x43,
-            0x85 => 0x29,
-            0x86 => 0x55,
-            0x87 => 0x6b,
-            0x88 => 0xca,
-            0x89 => 0x73,
-            0x8a => 0x22,
-            0x8b => 0x95,
-            0x8c => 0x3f,
-            0x8d => 0x0f,
-            0x8e => 0x02,
-            0x8f => 0xc1,
-            0x90 => 0xaf,
-            0x91 => 0xd9,
-            0x92 => 0x6a,
-            0x93 => 0x2b,
-            0x94 => 0x08,
-            0x95 => 0x9a,
-            0x96 => 0x80,
-            0x97 => 0x86,
-            0x98 => 0x07,
-            0x99 => 0x91,
-            0x9a => 0x12,
-            0x9b => 0xce,
-            0x9c => 0xfa,
-            0x9d => 0xea,
-            0x9e => 0xfb,
-            0x9f => 0x43,
-            0xa0 => 0xd1,
-            0xa1 => 0x18,
-            0xa2 => 0x0d,
-            0xa3 => 0x26,
-            0xa4 => 0x69,
-            0xa5 => 0x6c,
-            0xa6 => 0x20,
-            0xa7 => 0xfc,
-            0xa8 => 0xb6,
-            0xa9 => 0x51,
-            0xaa => 0x10,
-            0xab => 0x8f,
-            0xac => 0xe8,
-            0xad => 0xf8,
-            0xae => 0x19,
-            0xaf => 0x0c,
-            0xb0 => 0xd7,
-            0xb1 => 0xc7,
-            0xb2 => 0x87,
-            0xb3 => 0x38,
-            0xb4 => 0x47,
-            0xb5 => 0xed,
-            0xb6 => 0x6c,
-            0xb7 => 0x3b,
-            0xb8 => 0xcc,
-            0xb9 => 0xb7,
-            0xba => 0x40,
-            0xbb => 0xd9,
-            0xbc => 0x5e,
-            0xbd => 0x42,
-            0xbe => 0x7a,
-            0xbf => 0x09,
-            0xc0 => 0x4f,
-            0xc1 => 0xc7,
-            0xc2 => 0x22,
-            0xc3 => 0x5d,
-            0xc4 => 0x52,
-            0xc5 => 0x11,
-            0xc6 => 0x23,
-            0xc7 => 0x44,
-            0xc8 => 0x8d,
-            0xc9 => 0x9a,
-            0xca => 0x87,
-            0xcb => 0x9c,
-            0xcc => 0x1a,
-            0xcd => 0xbf,
-            0xce => 0xe4,
-            0xcf => 0x2d,
-            0xd0 => 0x8a,
-            0xd1 => 0x73,
-            0xd2 => 0x16,
-            0xd3 => 0x1c,
-            0xd4 => 0x5f,
-            0xd5 => 0xbc,
-            0xd6 => 0x37,
-            0xd7 => 0x74,
-            0xd8 => 0xd4,
-            0xd9 => 0x9e,
-            0xda => 0xf5,
-            0xdb => 0x7e,
-            0xdc => 0xc5,
-            0xdd => 0xf9,
-            0xde => 0x2a,
-            0xdf => 0x68,
-            0xe0 => 0x3d,
-            0xe1 => 0xe0,
-            0xe2 => 0x4e,
-            0xe3 => 0x13,
-            0xe4 => 0x12,
-            0xe5 => 0x4a,
-            0xe6 => 0x2f,
-            0xe7 => 0x0c,
-            0xe8 => 0xa1,
-            0xe9 => 0xc3,
-            0xea => 0xf4,
-            0xeb => 0x1e,
-            0xec => 0xc8,
-            0xed => 0x3e,
-            0xee => 0xef,
-            0xef => 0x67,
-            0xf0 => 0x47,
-            0xf1 => 0xf7,
-            0xf2 => 0x0a,
-            0xf3 => 0x7d,
-            0xf4 => 0xf1,
-            0xf5 => 0xd2,
-            0xf6 => 0x2b,
-            0xf7 => 0x9f,
-            0xf8 => 0xa4,
-            0xf9 => 0x7c,
-            0xfa => 0xde,
-            0xfb => 0x27,
-            0xfc => 0x10,
-            0xfd => 0x8a,
-            0xfe => 0x05,
-            0xff => 0x01,
+    /// GF(2^8) 乘以 x（模 x^8 + x^4 + x^3 + x + 1）
+    fn xtime(a: u8) -> u8 {
+        (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7))
+    }
+
+    /// GF(2^8) 乘法，循环次数固定且不依赖操作数分支
+    pub(crate) fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0u8;
+        for _ in 0..8 {
+            product ^= a & 0u8.wrapping_sub(b & 1);
+            a = Self::xtime(a);
+            b >>= 1;
         }
+        product
+    }
+
+    /// GF(2^8) 乘法逆元，计算 a^254（a = 0 时结果为 0）
+    fn gf_inverse(a: u8) -> u8 {
+        let a2 = Self::gf_mul(a, a);
+        let a3 = Self::gf_mul(a2, a);
+        let a6 = Self::gf_mul(a3, a3);
+        let a7 = Self::gf_mul(a6, a);
+        let a14 = Self::gf_mul(a7, a7);
+        let a15 = Self::gf_mul(a14, a);
+        let a30 = Self::gf_mul(a15, a15);
+        let a31 = Self::gf_mul(a30, a);
+        let a62 = Self::gf_mul(a31, a31);
+        let a63 = Self::gf_mul(a62, a);
+        let a126 = Self::gf_mul(a63, a63);
+        let a127 = Self::gf_mul(a126, a);
+        Self::gf_mul(a127, a127)
     }
 
     /// 生成轮常数 (RCon)
@@ -395,10 +278,328 @@ impl Aes256Riscv {
         rcon
     }
 
-    /// 使用 AES-256 加密数据
+    /// AddRoundKey：状态与第 `round` 轮的 16 字节轮密钥异或
+    fn add_round_key(state: &mut [u8; 16], round_keys: &[u8], round: usize) {
+        let round_key = &round_keys[round * 16..round * 16 + 16];
+        for (byte, key) in state.iter_mut().zip(round_key) {
+            *byte ^= key;
+        }
+    }
+
+    /// SubBytes：对状态每个字节做 S-Box 替换
+    fn sub_bytes(state: &mut [u8; 16]) {
+        for byte in state.iter_mut() {
+            *byte = Self::substitute_byte(*byte);
+        }
+    }
+
+    /// InvSubBytes：对状态每个字节做逆 S-Box 替换
+    fn inv_sub_bytes(state: &mut [u8; 16]) {
+        for byte in state.iter_mut() {
+            *byte = Self::inv_substitute_byte(*byte);
+        }
+    }
+
+    /// ShiftRows：第 r 行循环左移 r 字节
+    ///
+    /// 状态按列优先存储，第 r 行第 c 列位于下标 r + 4c。
+    fn shift_rows(state: &mut [u8; 16]) {
+        let old = *state;
+        for r in 1..4 {
+            for c in 0..4 {
+                state[r + 4 * c] = old[r + 4 * ((c + r) % 4)];
+            }
+        }
+    }
+
+    /// InvShiftRows：第 r 行循环右移 r 字节
+    fn inv_shift_rows(state: &mut [u8; 16]) {
+        let old = *state;
+        for r in 1..4 {
+            for c in 0..4 {
+                state[r + 4 * ((c + r) % 4)] = old[r + 4 * c];
+            }
+        }
+    }
+
+    /// MixColumns：每列乘以固定多项式 {03}x^3 + {01}x^2 + {01}x + {02}
+    fn mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_exact_mut(4) {
+            let [a0, a1, a2, a3] = [column[0], column[1], column[2], column[3]];
+            let all = a0 ^ a1 ^ a2 ^ a3;
+            column[0] = a0 ^ all ^ Self::xtime(a0 ^ a1);
+            column[1] = a1 ^ all ^ Self::xtime(a1 ^ a2);
+            column[2] = a2 ^ all ^ Self::xtime(a2 ^ a3);
+            column[3] = a3 ^ all ^ Self::xtime(a3 ^ a0);
+        }
+    }
+
+    /// InvMixColumns：每列乘以 {0b}x^3 + {0d}x^2 + {09}x + {0e}
+    fn inv_mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_exact_mut(4) {
+            let [a0, a1, a2, a3] = [column[0], column[1], column[2], column[3]];
+            column[0] = Self::gf_mul(a0, 0x0e)
+                ^ Self::gf_mul(a1, 0x0b)
+                ^ Self::gf_mul(a2, 0x0d)
+                ^ Self::gf_mul(a3, 0x09);
+            column[1] = Self::gf_mul(a0, 0x09)
+                ^ Self::gf_mul(a1, 0x0e)
+                ^ Self::gf_mul(a2, 0x0b)
+                ^ Self::gf_mul(a3, 0x0d);
+            column[2] = Self::gf_mul(a0, 0x0d)
+                ^ Self::gf_mul(a1, 0x09)
+                ^ Self::gf_mul(a2, 0x0e)
+                ^ Self::gf_mul(a3, 0x0b);
+            column[3] = Self::gf_mul(a0, 0x0b)
+                ^ Self::gf_mul(a1, 0x0d)
+                ^ Self::gf_mul(a2, 0x09)
+                ^ Self::gf_mul(a3, 0x0e);
+        }
+    }
+
+    /// 使用给定的 240 字节扩展密钥加密单个分组
+    pub(crate) fn cipher(block: &[u8; AES_BLOCK_SIZE], round_keys: &[u8]) -> [u8; AES_BLOCK_SIZE] {
+        let mut state = *block;
+        Self::add_round_key(&mut state, round_keys, 0);
+
+        for round in 1..AES256_ROUNDS {
+            Self::sub_bytes(&mut state);
+            Self::shift_rows(&mut state);
+            Self::mix_columns(&mut state);
+            Self::add_round_key(&mut state, round_keys, round);
+        }
+
+        Self::sub_bytes(&mut state);
+        Self::shift_rows(&mut state);
+        Self::add_round_key(&mut state, round_keys, AES256_ROUNDS);
+        state
+    }
+
+    /// 使用给定的 240 字节扩展密钥解密单个分组
+    pub(crate) fn inv_cipher(
+        block: &[u8; AES_BLOCK_SIZE],
+        round_keys: &[u8],
+    ) -> [u8; AES_BLOCK_SIZE] {
+        let mut state = *block;
+        Self::add_round_key(&mut state, round_keys, AES256_ROUNDS);
+
+        for round in (1..AES256_ROUNDS).rev() {
+            Self::inv_shift_rows(&mut state);
+            Self::inv_sub_bytes(&mut state);
+            Self::add_round_key(&mut state, round_keys, round);
+            Self::inv_mix_columns(&mut state);
+        }
+
+        Self::inv_shift_rows(&mut state);
+        Self::inv_sub_bytes(&mut state);
+        Self::add_round_key(&mut state, round_keys, 0);
+        state
+    }
+
+    /// 获取已扩展的轮密钥
+    fn round_keys(&self) -> Result<&[u8], Box<dyn Error>> {
+        if self.expanded_key.len() != AES256_EXPANDED_KEY_SIZE {
+            return Err(Box::new(Aes256Error {
+                message: "Key not expanded, call key_expansion first".to_string(),
+            }));
+        }
+        Ok(self.expanded_key.expose_secret())
+    }
+
+    /// 使用软件实现加密单个 128 位分组
+    ///
+    /// 纯软件 AES-256 分组加密（FIPS-197 5.1 节），不依赖任何加速器：
+    /// 初始 AddRoundKey 后执行 13 轮 SubBytes、ShiftRows、MixColumns、AddRoundKey，
+    /// 最后一轮省略 MixColumns。S-Box 与 MixColumns 均直接计算，不使用查找表。
+    ///
+    /// # 参数
+    ///
+    /// * `block` - 16 字节明文分组
+    ///
+    /// # 返回
+    ///
+    /// - `Ok([u8; 16])`: 16 字节密文分组
+    /// - `Err(Box<dyn Error>)`: 尚未调用 `key_expansion`
+    pub fn encrypt_block(&self, block: &[u8; AES_BLOCK_SIZE]) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(Self::cipher(block, self.round_keys()?))
+    }
+
+    /// 使用软件实现解密单个 128 位分组
+    ///
+    /// `encrypt_block` 的逆操作（FIPS-197 5.3 节），按逆序使用轮密钥。
+    ///
+    /// # 参数
+    ///
+    /// * `block` - 16 字节密文分组
+    ///
+    /// # 返回
+    ///
+    /// - `Ok([u8; 16])`: 16 字节明文分组
+    /// - `Err(Box<dyn Error>)`: 尚未调用 `key_expansion`
+    pub fn decrypt_block(&self, block: &[u8; AES_BLOCK_SIZE]) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(Self::inv_cipher(block, self.round_keys()?))
+    }
+
+    /// 使用加速器加密单个 128 位分组
+    ///
+    /// 与 `encrypt_block` 结果一致，用于交叉校验加速路径。
+    ///
+    /// # 参数
+    ///
+    /// * `block` - 16 字节明文分组
+    /// * `key` - 256 位密钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok([u8; 16])`: 16 字节密文分组
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn encrypt_block_accelerated(
+        &self,
+        block: &[u8; AES_BLOCK_SIZE],
+        key: &Aes256Key,
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        self.accelerator
+            .copr_aes256_encrypt_block(key.expose_secret(), block)
+    }
+
+    /// 使用加速器解密单个 128 位分组
+    ///
+    /// # 参数
+    ///
+    /// * `block` - 16 字节密文分组
+    /// * `key` - 256 位密钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok([u8; 16])`: 16 字节明文分组
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn decrypt_block_accelerated(
+        &self,
+        block: &[u8; AES_BLOCK_SIZE],
+        key: &Aes256Key,
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        self.accelerator
+            .copr_aes256_decrypt_block(key.expose_secret(), block)
+    }
+
+    /// 使用加速器为 `key` 生成密钥调度
+    ///
+    /// 同一密钥处理多个分组时，密钥只展开一次。
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Box<dyn Aes256KeySchedule>)`: 绑定密钥的分组变换
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn key_schedule(
+        &self,
+        key: &Aes256Key,
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        self.accelerator
+            .copr_aes256_key_schedule(key.expose_secret())
+    }
+
+    /// 使用 AES-256-GCM 加密数据
+    ///
+    /// 从操作系统 CSPRNG 生成 96 位随机 nonce，对明文进行认证加密，
+    /// 并将附加认证数据（AAD）纳入认证标签。
+    ///
+    /// # 参数
+    ///
+    /// * `plaintext` - 要加密的明文数据，可为任意长度
+    /// * `key` - 256 位（32 字节）加密密钥，必须与解密密钥相同
+    /// * `aad` - 附加认证数据，只认证不加密，解密时必须一致
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(AesGcmCiphertext)`: nonce、密文与认证标签
+    /// - `Err(Box<dyn Error>)`: 随机源不可用或加速器错误
+    ///
+    /// # 注意
+    ///
+    /// 随机 nonce 下同一密钥加密的消息数不应超过 2^32 条。
+    pub fn encrypt_gcm(
+        &self,
+        plaintext: &[u8],
+        key: &Aes256Key,
+        aad: &[u8],
+    ) -> Result<AesGcmCiphertext, Box<dyn Error>> {
+        let mut nonce = [0u8; GCM_NONCE_SIZE];
+        SysRng.try_fill_bytes(&mut nonce)?;
+        self.encrypt_gcm_with_nonce(plaintext, key, &nonce, aad)
+    }
+
+    /// 使用调用方指定的 nonce 进行 AES-256-GCM 加密
+    ///
+    /// 同一密钥下 nonce 绝不能重复使用，否则会同时泄露明文异或值和认证密钥。
+    /// 主要用于测试向量和由上层协议保证 nonce 唯一的场景（如计数器 nonce）。
+    ///
+    /// # 参数
+    ///
+    /// * `plaintext` - 要加密的明文数据
+    /// * `key` - 256 位加密密钥
+    /// * `nonce` - 96 位 nonce
+    /// * `aad` - 附加认证数据
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(AesGcmCiphertext)`: nonce、密文与认证标签
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn encrypt_gcm_with_nonce(
+        &self,
+        plaintext: &[u8],
+        key: &Aes256Key,
+        nonce: &[u8; GCM_NONCE_SIZE],
+        aad: &[u8],
+    ) -> Result<AesGcmCiphertext, Box<dyn Error>> {
+        let (ciphertext, tag) =
+            self.accelerator
+                .copr_encrypt_aes256(key.expose_secret(), nonce, aad, plaintext)?;
+
+        Ok(AesGcmCiphertext {
+            nonce: *nonce,
+            ciphertext,
+            tag,
+        })
+    }
+
+    /// 使用 AES-256-GCM 解密并认证数据
+    ///
+    /// 先校验认证标签，再返回明文。密文、nonce、标签或 AAD
+    /// 任一被篡改时都会返回错误，不会输出任何明文。
+    ///
+    /// # 参数
+    ///
+    /// * `sealed` - `encrypt_gcm` 生成的加密结果
+    /// * `key` - 256 位解密密钥
+    /// * `aad` - 加密时使用的附加认证数据
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 解密后的明文
+    /// - `Err(Box<dyn Error>)`: 认证失败或加速器错误
+    pub fn decrypt_gcm(
+        &self,
+        sealed: &AesGcmCiphertext,
+        key: &Aes256Key,
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.accelerator.copr_decrypt_aes256(
+            key.expose_secret(),
+            &sealed.nonce,
+            aad,
+            &sealed.ciphertext,
+            &sealed.tag,
+        )
+    }
+
+    /// 使用 AES-256-GCM 加密数据
     ///
-    /// 对明文进行 AES-256 加密，支持 RISC-V 硬件加速。
-    /// 加密过程包括：明文填充、14 轮 AES 变换（SubBytes、ShiftRows、MixColumns、AddRoundKey）。
+    /// `encrypt_gcm` 的便捷封装，不带附加认证数据，
+    /// 输出为单个字节串：`nonce (12 字节) || 密文 || tag (16 字节)`。
     ///
     /// # 参数
     ///
@@ -407,36 +608,36 @@ impl Aes256Riscv {
     ///
     /// # 返回
     ///
-    /// - `Ok(Vec<u8>)`: 加密后的密文，长度为明文长度（无额外填充）
-    /// - `Err(Box<dyn Error>)`: 加密失败（如密钥无效、硬件错误）
+    /// - `Ok(Vec<u8>)`: 加密后的数据，长度为明文长度 + 28 字节
+    /// - `Err(Box<dyn Error>)`: 加密失败（如随机源不可用、硬件错误）
     pub fn encrypt_aes256(
         &self,
         plaintext: &[u8],
-        key: &[u8; 32],
+        key: &Aes256Key,
     ) -> Result<Vec<u8>, Box<dyn Error>> {
-        self.accelerator.copr_encrypt_aes256(plaintext, key)
+        Ok(self.encrypt_gcm(plaintext, key, &[])?.to_bytes())
     }
 
-    /// 使用 AES-256 解密数据
+    /// 使用 AES-256-GCM 解密数据
     ///
-    /// 对 AES-256 加密的密文进行解密，是 `encrypt_aes256` 的逆操作。
-    /// 解密过程执行与加密相反的 14 轮变换。
+    /// `encrypt_aes256` 的逆操作，解析 `nonce || 密文 || tag` 并校验认证标签。
     ///
     /// # 参数
     ///
-    /// * `ciphertext` - 要解密的密文数据
+    /// * `ciphertext` - `encrypt_aes256` 输出的数据
     /// * `key` - 256 位（32 字节）解密密钥，必须与加密密钥相同
     ///
     /// # 返回
     ///
     /// - `Ok(Vec<u8>)`: 解密后的明文
-    /// - `Err(Box<dyn Error>)`: 解密失败（如密钥错误、密文损坏）
+    /// - `Err(Box<dyn Error>)`: 解密失败（如密钥错误、密文被篡改或长度不足）
     pub fn decrypt_aes256(
         &self,
         ciphertext: &[u8],
-        key: &[u8; 32],
+        key: &Aes256Key,
     ) -> Result<Vec<u8>, Box<dyn Error>> {
-        self.accelerator.copr_decrypt_aes256(ciphertext, key)
+        let sealed = AesGcmCiphertext::from_bytes(ciphertext)?;
+        self.decrypt_gcm(&sealed, key, &[])
     }
 }
 
@@ -0,0 +1,657 @@
+//! AES-256 分组密码工作模式
+//!
+//! 在 `Aes256Riscv` 之上实现 CTR、CBC（PKCS#7 填充）和 XTS 模式，分组运算经
+//! 加速器的 `copr_aes256_key_schedule` 返回的密钥调度执行，每把密钥只展开一次。
+//! 各模式采用与 `Sha256Riscv` 相同的增量接口：`new` 使用默认后端创建实例，
+//! `with_accelerator` 指定后端，`update` 追加任意长度的数据并返回已可输出的部分，
+//! `finalize` 处理剩余数据。加速器出错时 `update` / `finalize` 返回错误，
+//! 此后该实例的状态不再可靠，应丢弃。
+//!
+//! # 模式选择
+//!
+//! - CTR：流式加密，不需要填充，加解密为同一操作；同一密钥下计数器不可重复
+//! - CBC：需要 PKCS#7 填充，解密端在 `finalize` 时校验填充
+//! - XTS（IEEE 1619 / NIST SP 800-38E）：面向按扇区寻址的存储加密，
+//!   使用密文挪用（ciphertext stealing）支持非 16 字节整数倍的数据单元
+//!
+//! 这些模式均不提供完整性保护，需要认证时请使用 `Aes256Riscv::encrypt_gcm`。
+
+use crate::crypto::aes::{AES_BLOCK_SIZE, Aes256Riscv};
+use crate::crypto::riscv_ext::{Aes256KeySchedule, RiscVCryptoExt};
+use crate::crypto::secret::Aes256Key;
+use std::error::Error;
+use std::fmt;
+use subtle::{ConstantTimeEq, ConstantTimeGreater, CtOption};
+
+/// 分组模式操作中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct AesModeError {
+    message: String,
+}
+
+impl fmt::Display for AesModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AES Mode Error: {}", self.message)
+    }
+}
+
+impl Error for AesModeError {}
+
+/// 对两个分组按字节异或
+fn xor_block(a: &[u8; AES_BLOCK_SIZE], b: &[u8; AES_BLOCK_SIZE]) -> [u8; AES_BLOCK_SIZE] {
+    let mut out = [0u8; AES_BLOCK_SIZE];
+    for i in 0..AES_BLOCK_SIZE {
+        out[i] = a[i] ^ b[i];
+    }
+    out
+}
+
+/// 从缓冲区头部取出一个完整分组
+fn take_block(buffer: &mut Vec<u8>) -> [u8; AES_BLOCK_SIZE] {
+    let mut block = [0u8; AES_BLOCK_SIZE];
+    block.copy_from_slice(&buffer[..AES_BLOCK_SIZE]);
+    buffer.drain(..AES_BLOCK_SIZE);
+    block
+}
+
+/// 绑定密钥的单分组变换
+///
+/// 各模式持有密钥副本（释放时清零），首次分组运算时向 `Aes256Riscv` 的加速器取得
+/// 密钥调度，之后的分组复用同一调度，密钥只展开一次。
+struct BlockCipher {
+    cipher: Aes256Riscv,
+    key: Aes256Key,
+    schedule: Option<Box<dyn Aes256KeySchedule>>,
+}
+
+impl BlockCipher {
+    fn new(key: &Aes256Key, accelerator: Box<dyn RiscVCryptoExt>) -> Self {
+        Self::with_cipher(Aes256Riscv::with_accelerator(accelerator), key)
+    }
+
+    fn with_cipher(cipher: Aes256Riscv, key: &Aes256Key) -> Self {
+        BlockCipher {
+            cipher,
+            key: key.clone(),
+            schedule: None,
+        }
+    }
+
+    fn schedule(&mut self) -> Result<&dyn Aes256KeySchedule, Box<dyn Error>> {
+        if self.schedule.is_none() {
+            self.schedule = Some(self.cipher.key_schedule(&self.key)?);
+        }
+        Ok(self.schedule.as_deref().unwrap())
+    }
+
+    fn encrypt(&mut self, block: &[u8; AES_BLOCK_SIZE]) -> Result<[u8; 16], Box<dyn Error>> {
+        self.schedule()?.encrypt_block(block)
+    }
+
+    fn decrypt(&mut self, block: &[u8; AES_BLOCK_SIZE]) -> Result<[u8; 16], Box<dyn Error>> {
+        self.schedule()?.decrypt_block(block)
+    }
+}
+
+/// 最后一个分组的 PKCS#7 填充长度
+///
+/// 检查整个分组且不提前退出，耗时不泄露填充在哪个字节出错（填充预言攻击）。
+/// 填充无效时结果为 none。
+pub(crate) fn pkcs7_padding_len(block: &[u8; AES_BLOCK_SIZE]) -> CtOption<usize> {
+    let pad = block[AES_BLOCK_SIZE - 1];
+    let mut valid = !pad.ct_eq(&0) & !pad.ct_gt(&(AES_BLOCK_SIZE as u8));
+    for (i, byte) in block.iter().enumerate() {
+        // 第 i 字节属于填充当且仅当 16 - i <= pad
+        let in_padding = !((AES_BLOCK_SIZE - i) as u8).ct_gt(&pad);
+        valid &= !in_padding | byte.ct_eq(&pad);
+    }
+    CtOption::new(pad as usize, valid)
+}
+
+/// AES-256 CTR 模式
+///
+/// 计数器分组按 128 位大端整数递增（NIST SP 800-38A 附录 B.1）。
+/// 加密和解密是同一操作，`update` 立即返回与输入等长的输出。
+#[allow(dead_code)]
+pub struct Aes256Ctr {
+    cipher: BlockCipher,
+    counter: [u8; AES_BLOCK_SIZE],
+    keystream: [u8; AES_BLOCK_SIZE],
+    keystream_pos: usize,
+}
+
+#[allow(dead_code)]
+impl Aes256Ctr {
+    /// 使用默认后端创建 CTR 实例
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    /// * `initial_counter` - 初始计数器分组（通常为 nonce || 计数器）
+    pub fn new(key: &Aes256Key, initial_counter: &[u8; AES_BLOCK_SIZE]) -> Self {
+        Self::with_accelerator(
+            key,
+            initial_counter,
+            crate::crypto::backend::default_backend(),
+        )
+    }
+
+    /// 使用指定的加速器创建 CTR 实例
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    /// * `initial_counter` - 初始计数器分组
+    /// * `accelerator` - 生成密钥流分组的后端
+    pub fn with_accelerator(
+        key: &Aes256Key,
+        initial_counter: &[u8; AES_BLOCK_SIZE],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Self {
+        Aes256Ctr {
+            cipher: BlockCipher::new(key, accelerator),
+            counter: *initial_counter,
+            keystream: [0u8; AES_BLOCK_SIZE],
+            keystream_pos: AES_BLOCK_SIZE,
+        }
+    }
+
+    /// 加密或解密一段数据
+    ///
+    /// 密钥流跨调用连续使用，因此任意切分输入得到的结果与一次性处理相同。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 与输入等长的输出
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut output = Vec::with_capacity(data.len());
+        for byte in data {
+            if self.keystream_pos == AES_BLOCK_SIZE {
+                self.keystream = self.cipher.encrypt(&self.counter)?;
+                self.increment_counter();
+                self.keystream_pos = 0;
+            }
+            output.push(byte ^ self.keystream[self.keystream_pos]);
+            self.keystream_pos += 1;
+        }
+        Ok(output)
+    }
+
+    /// 结束 CTR 处理
+    ///
+    /// CTR 不缓冲数据，始终返回空向量；保留该方法以与其他模式接口一致。
+    pub fn finalize(self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// 计数器按 128 位大端整数加一，溢出时回绕
+    fn increment_counter(&mut self) {
+        for byte in self.counter.iter_mut().rev() {
+            *byte = byte.wrapping_add(1);
+            if *byte != 0 {
+                break;
+            }
+        }
+    }
+}
+
+/// AES-256 CBC 加密器（PKCS#7 填充）
+///
+/// `update` 只输出已凑满的完整分组，剩余字节在 `finalize` 中填充后输出。
+/// 密文长度总是 16 的整数倍，且严格大于明文长度。
+#[allow(dead_code)]
+pub struct Aes256CbcEncryptor {
+    cipher: BlockCipher,
+    previous: [u8; AES_BLOCK_SIZE],
+    buffer: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl Aes256CbcEncryptor {
+    /// 使用默认后端创建 CBC 加密器
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    /// * `iv` - 16 字节初始向量，必须不可预测
+    pub fn new(key: &Aes256Key, iv: &[u8; AES_BLOCK_SIZE]) -> Self {
+        Self::with_accelerator(key, iv, crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 CBC 加密器
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    /// * `iv` - 16 字节初始向量
+    /// * `accelerator` - 执行分组加密的后端
+    pub fn with_accelerator(
+        key: &Aes256Key,
+        iv: &[u8; AES_BLOCK_SIZE],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Self {
+        Aes256CbcEncryptor {
+            cipher: BlockCipher::new(key, accelerator),
+            previous: *iv,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// 追加明文，返回本次可输出的密文分组
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 已凑满的密文分组
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.buffer.extend_from_slice(data);
+
+        let mut output = Vec::with_capacity(self.buffer.len());
+        while self.buffer.len() >= AES_BLOCK_SIZE {
+            let block = take_block(&mut self.buffer);
+            self.encrypt_block(&block, &mut output)?;
+        }
+        Ok(output)
+    }
+
+    /// 追加 PKCS#7 填充并输出最后的密文分组
+    ///
+    /// 填充长度为 1 到 16 字节；明文恰好为整数个分组时追加一个完整的填充分组。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 最后一个密文分组
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn finalize(mut self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let pad = AES_BLOCK_SIZE - self.buffer.len();
+        let mut block = [pad as u8; AES_BLOCK_SIZE];
+        block[..self.buffer.len()].copy_from_slice(&self.buffer);
+
+        let mut output = Vec::with_capacity(AES_BLOCK_SIZE);
+        self.encrypt_block(&block, &mut output)?;
+        Ok(output)
+    }
+
+    fn encrypt_block(
+        &mut self,
+        block: &[u8; AES_BLOCK_SIZE],
+        output: &mut Vec<u8>,
+    ) -> Result<(), Box<dyn Error>> {
+        let ciphertext = self.cipher.encrypt(&xor_block(block, &self.previous))?;
+        output.extend_from_slice(&ciphertext);
+        self.previous = ciphertext;
+        Ok(())
+    }
+}
+
+/// AES-256 CBC 解密器（PKCS#7 填充）
+///
+/// 由于最后一个分组包含填充，`update` 总是保留最后一个完整分组，
+/// 直到 `finalize` 时解密并移除填充。
+#[allow(dead_code)]
+pub struct Aes256CbcDecryptor {
+    cipher: BlockCipher,
+    previous: [u8; AES_BLOCK_SIZE],
+    buffer: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl Aes256CbcDecryptor {
+    /// 使用默认后端创建 CBC 解密器
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    /// * `iv` - 加密时使用的初始向量
+    pub fn new(key: &Aes256Key, iv: &[u8; AES_BLOCK_SIZE]) -> Self {
+        Self::with_accelerator(key, iv, crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 CBC 解密器
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 256 位密钥
+    /// * `iv` - 加密时使用的初始向量
+    /// * `accelerator` - 执行分组解密的后端
+    pub fn with_accelerator(
+        key: &Aes256Key,
+        iv: &[u8; AES_BLOCK_SIZE],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Self {
+        Aes256CbcDecryptor {
+            cipher: BlockCipher::new(key, accelerator),
+            previous: *iv,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// 追加密文，返回本次可输出的明文
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 除最后一个分组外已解密的明文
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.buffer.extend_from_slice(data);
+
+        let mut output = Vec::with_capacity(self.buffer.len());
+        while self.buffer.len() > AES_BLOCK_SIZE {
+            let block = take_block(&mut self.buffer);
+            output.extend_from_slice(&self.decrypt_block(&block)?);
+        }
+        Ok(output)
+    }
+
+    /// 解密最后一个分组并移除 PKCS#7 填充
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 去除填充后的剩余明文
+    /// - `Err(Box<dyn Error>)`: 密文长度不是分组整数倍、填充无效或加速器错误
+    pub fn finalize(mut self) -> Result<Vec<u8>, Box<dyn Error>> {
+        if self.buffer.len() != AES_BLOCK_SIZE {
+            return Err(Box::new(AesModeError {
+                message: "Ciphertext length is not a multiple of the block size".to_string(),
+            }));
+        }
+
+        let block = take_block(&mut self.buffer);
+        let plaintext = self.decrypt_block(&block)?;
+
+        let pad = Option::<usize>::from(pkcs7_padding_len(&plaintext)).ok_or_else(|| {
+            Box::new(AesModeError {
+                message: "Invalid PKCS#7 padding".to_string(),
+            })
+        })?;
+
+        Ok(plaintext[..AES_BLOCK_SIZE - pad].to_vec())
+    }
+
+    fn decrypt_block(
+        &mut self,
+        block: &[u8; AES_BLOCK_SIZE],
+    ) -> Result<[u8; AES_BLOCK_SIZE], Box<dyn Error>> {
+        let plaintext = xor_block(&self.cipher.decrypt(block)?, &self.previous);
+        self.previous = *block;
+        Ok(plaintext)
+    }
+}
+
+/// 由数据单元序号构造 XTS tweak
+///
+/// IEEE 1619 将数据单元序号（通常为扇区号）按小端序编码为 128 位 tweak。
+#[allow(dead_code)]
+pub fn xts_sector_tweak(data_unit: u128) -> [u8; AES_BLOCK_SIZE] {
+    data_unit.to_le_bytes()
+}
+
+/// XTS 模式的公共状态
+///
+/// 加密器和解密器共享分组调度与 tweak 更新，仅在单分组变换和密文挪用顺序上不同。
+struct XtsState {
+    data_cipher: BlockCipher,
+    tweak: [u8; AES_BLOCK_SIZE],
+    buffer: Vec<u8>,
+}
+
+impl XtsState {
+    /// 检查两把密钥并用 tweak 密钥加密初始 tweak
+    fn new(
+        data_key: &Aes256Key,
+        tweak_key: &Aes256Key,
+        tweak: &[u8; AES_BLOCK_SIZE],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Result<Self, Box<dyn Error>> {
+        // SP 800-38E 要求两把密钥不同，否则 XTS 退化并可被区分
+        if data_key == tweak_key {
+            return Err(Box::new(AesModeError {
+                message: "XTS data key and tweak key must differ".to_string(),
+            }));
+        }
+
+        let cipher = Aes256Riscv::with_accelerator(accelerator);
+        let tweak = cipher.encrypt_block_accelerated(tweak, tweak_key)?;
+        Ok(XtsState {
+            data_cipher: BlockCipher::with_cipher(cipher, data_key),
+            tweak,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// 在 GF(2^128) 中将 tweak 乘以 α（小端序左移一位，溢出时异或 0x87）
+    fn next_tweak(tweak: &[u8; AES_BLOCK_SIZE]) -> [u8; AES_BLOCK_SIZE] {
+        let mut next = [0u8; AES_BLOCK_SIZE];
+        let carry = tweak[AES_BLOCK_SIZE - 1] >> 7;
+        for i in (1..AES_BLOCK_SIZE).rev() {
+            next[i] = (tweak[i] << 1) | (tweak[i - 1] >> 7);
+        }
+        next[0] = (tweak[0] << 1) ^ (0x87 & 0u8.wrapping_sub(carry));
+        next
+    }
+
+    /// 使用给定 tweak 处理单个分组：C = E(P ^ T) ^ T
+    fn transform(
+        &mut self,
+        block: &[u8; AES_BLOCK_SIZE],
+        tweak: &[u8; AES_BLOCK_SIZE],
+        encrypt: bool,
+    ) -> Result<[u8; AES_BLOCK_SIZE], Box<dyn Error>> {
+        let input = xor_block(block, tweak);
+        let output = if encrypt {
+            self.data_cipher.encrypt(&input)?
+        } else {
+            self.data_cipher.decrypt(&input)?
+        };
+        Ok(xor_block(&output, tweak))
+    }
+
+    /// 处理缓冲区中的分组，至少保留 16 字节供 `finalize` 做密文挪用
+    fn update(&mut self, data: &[u8], encrypt: bool) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.buffer.extend_from_slice(data);
+
+        let mut output = Vec::with_capacity(self.buffer.len());
+        while self.buffer.len() >= 2 * AES_BLOCK_SIZE {
+            let block = take_block(&mut self.buffer);
+            let tweak = self.tweak;
+            output.extend_from_slice(&self.transform(&block, &tweak, encrypt)?);
+            self.tweak = Self::next_tweak(&self.tweak);
+        }
+        Ok(output)
+    }
+
+    /// 处理最后 16 到 31 字节，不足一个分组的尾部使用密文挪用
+    fn finalize(mut self, encrypt: bool) -> Result<Vec<u8>, Box<dyn Error>> {
+        if self.buffer.len() < AES_BLOCK_SIZE {
+            return Err(Box::new(AesModeError {
+                message: "XTS data unit must be at least one block".to_string(),
+            }));
+        }
+
+        let block = take_block(&mut self.buffer);
+        let tail = self.buffer.len();
+        if tail == 0 {
+            let tweak = self.tweak;
+            return Ok(self.transform(&block, &tweak, encrypt)?.to_vec());
+        }
+
+        // 解密时倒数第二个分组必须使用下一个 tweak，与加密时的顺序相反
+        let last_tweak = Self::next_tweak(&self.tweak);
+        let (first, second) = if encrypt {
+            (self.tweak, last_tweak)
+        } else {
+            (last_tweak, self.tweak)
+        };
+
+        let stolen = self.transform(&block, &first, encrypt)?;
+        let mut merged = stolen;
+        merged[..tail].copy_from_slice(&self.buffer);
+
+        let mut output = self.transform(&merged, &second, encrypt)?.to_vec();
+        output.extend_from_slice(&stolen[..tail]);
+        Ok(output)
+    }
+}
+
+/// AES-256 XTS 加密器（XTS-AES-256）
+///
+/// 每个实例处理一个数据单元（例如一个扇区），数据单元至少 16 字节。
+/// `update` 保留末尾至少 16 字节，以便在 `finalize` 时对不完整的尾分组做密文挪用。
+#[allow(dead_code)]
+pub struct Aes256XtsEncryptor {
+    state: XtsState,
+}
+
+#[allow(dead_code)]
+impl Aes256XtsEncryptor {
+    /// 使用默认后端创建 XTS 加密器
+    ///
+    /// # 参数
+    ///
+    /// * `data_key` - 数据密钥（IEEE 1619 中 512 位密钥的前半）
+    /// * `tweak_key` - tweak 密钥（后半），必须与数据密钥不同
+    /// * `tweak` - 数据单元 tweak，见 `xts_sector_tweak`
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Aes256XtsEncryptor)`: 新的加密器
+    /// - `Err(Box<dyn Error>)`: 两把密钥相同或加速器错误
+    pub fn new(
+        data_key: &Aes256Key,
+        tweak_key: &Aes256Key,
+        tweak: &[u8; AES_BLOCK_SIZE],
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::with_accelerator(
+            data_key,
+            tweak_key,
+            tweak,
+            crate::crypto::backend::default_backend(),
+        )
+    }
+
+    /// 使用指定的加速器创建 XTS 加密器
+    ///
+    /// # 参数
+    ///
+    /// * `data_key` - 数据密钥
+    /// * `tweak_key` - tweak 密钥
+    /// * `tweak` - 数据单元 tweak
+    /// * `accelerator` - 执行分组运算的后端
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Aes256XtsEncryptor)`: 新的加密器
+    /// - `Err(Box<dyn Error>)`: 两把密钥相同或加速器错误
+    pub fn with_accelerator(
+        data_key: &Aes256Key,
+        tweak_key: &Aes256Key,
+        tweak: &[u8; AES_BLOCK_SIZE],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Aes256XtsEncryptor {
+            state: XtsState::new(data_key, tweak_key, tweak, accelerator)?,
+        })
+    }
+
+    /// 追加明文，返回本次可输出的密文
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 本次可输出的密文
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.state.update(data, true)
+    }
+
+    /// 输出剩余密文
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 剩余密文
+    /// - `Err(Box<dyn Error>)`: 数据单元不足 16 字节或加速器错误
+    pub fn finalize(self) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.state.finalize(true)
+    }
+}
+
+/// AES-256 XTS 解密器（XTS-AES-256）
+///
+/// 与 `Aes256XtsEncryptor` 对应，密文长度与明文相同。
+#[allow(dead_code)]
+pub struct Aes256XtsDecryptor {
+    state: XtsState,
+}
+
+#[allow(dead_code)]
+impl Aes256XtsDecryptor {
+    /// 使用默认后端创建 XTS 解密器
+    ///
+    /// # 参数
+    ///
+    /// * `data_key` - 加密时使用的数据密钥
+    /// * `tweak_key` - 加密时使用的 tweak 密钥
+    /// * `tweak` - 加密时使用的数据单元 tweak
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Aes256XtsDecryptor)`: 新的解密器
+    /// - `Err(Box<dyn Error>)`: 两把密钥相同或加速器错误
+    pub fn new(
+        data_key: &Aes256Key,
+        tweak_key: &Aes256Key,
+        tweak: &[u8; AES_BLOCK_SIZE],
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::with_accelerator(
+            data_key,
+            tweak_key,
+            tweak,
+            crate::crypto::backend::default_backend(),
+        )
+    }
+
+    /// 使用指定的加速器创建 XTS 解密器
+    ///
+    /// # 参数
+    ///
+    /// * `data_key` - 数据密钥
+    /// * `tweak_key` - tweak 密钥
+    /// * `tweak` - 数据单元 tweak
+    /// * `accelerator` - 执行分组运算的后端
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Aes256XtsDecryptor)`: 新的解密器
+    /// - `Err(Box<dyn Error>)`: 两把密钥相同或加速器错误
+    pub fn with_accelerator(
+        data_key: &Aes256Key,
+        tweak_key: &Aes256Key,
+        tweak: &[u8; AES_BLOCK_SIZE],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Aes256XtsDecryptor {
+            state: XtsState::new(data_key, tweak_key, tweak, accelerator)?,
+        })
+    }
+
+    /// 追加密文，返回本次可输出的明文
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 本次可输出的明文
+    /// - `Err(Box<dyn Error>)`: 加速器错误
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.state.update(data, false)
+    }
+
+    /// 输出剩余明文
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 剩余明文
+    /// - `Err(Box<dyn Error>)`: 数据单元不足 16 字节或加速器错误
+    pub fn finalize(self) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.state.finalize(false)
+    }
+}
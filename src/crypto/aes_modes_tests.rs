@@ -0,0 +1,303 @@
+#[cfg(test)]
+mod aes_block_mode_tests {
+    use crate::crypto::aes_modes::{
+        Aes256CbcDecryptor, Aes256CbcEncryptor, Aes256Ctr, Aes256XtsDecryptor, Aes256XtsEncryptor,
+        xts_sector_tweak,
+    };
+    use crate::crypto::mock::{MockAccelerator, MockOperation};
+    use crate::crypto::secret::Aes256Key;
+    use crate::crypto::test_util::hex;
+    use std::error::Error;
+
+    // NIST SP 800-38A 附录 F 的 AES-256 密钥与明文
+    const SP800_38A_KEY: &str = "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+    const SP800_38A_PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+    // IEEE 1619-2007 XTS-AES-256 Vector 10 的密钥
+    const XTS_KEY: &str = "27182818284590452353602874713526624977572470936999595749669676273141592653589793238462643383279502884197169399375105820974944592";
+
+    fn key32(s: &str) -> Aes256Key {
+        Aes256Key::from_slice(&hex(s)).unwrap()
+    }
+
+    /// IEEE 1619 的 512 位密钥拆为数据密钥和 tweak 密钥
+    fn xts_keys() -> (Aes256Key, Aes256Key) {
+        let key = hex(XTS_KEY);
+        (
+            Aes256Key::from_slice(&key[..32]).unwrap(),
+            Aes256Key::from_slice(&key[32..]).unwrap(),
+        )
+    }
+
+    /// 以不同长度的分片依次喂入数据，验证流式结果与分片方式无关
+    fn feed_in_chunks(
+        data: &[u8],
+        mut update: impl FnMut(&[u8]) -> Result<Vec<u8>, Box<dyn Error>>,
+    ) -> Vec<u8> {
+        let sizes = [1usize, 7, 16, 3, 33, 5];
+        let mut output = Vec::new();
+        let mut offset = 0;
+        let mut i = 0;
+        while offset < data.len() {
+            let end = (offset + sizes[i % sizes.len()]).min(data.len());
+            output.extend(update(&data[offset..end]).unwrap());
+            offset = end;
+            i += 1;
+        }
+        output
+    }
+
+    #[test]
+    fn test_ctr_sp800_38a_vector() {
+        // F.5.5 CTR-AES256.Encrypt
+        let key = key32(SP800_38A_KEY);
+        let counter: [u8; 16] = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").try_into().unwrap();
+        let plaintext = hex(SP800_38A_PLAINTEXT);
+        let expected = hex(
+            "601ec313775789a5b7a7f504bbf3d228f443e3ca4d62b59aca84e990cacaf5c52b0930daa23de94ce87017ba2d84988ddfc9c58db67aada613c2dd08457941a6",
+        );
+
+        let mut ctr = Aes256Ctr::new(&key, &counter);
+        let mut ciphertext = ctr.update(&plaintext).unwrap();
+        ciphertext.extend(ctr.finalize());
+        assert_eq!(ciphertext, expected);
+
+        let mut ctr = Aes256Ctr::new(&key, &counter);
+        let streamed = feed_in_chunks(&plaintext, |chunk| ctr.update(chunk));
+        assert_eq!(streamed, expected);
+
+        let mut ctr = Aes256Ctr::new(&key, &counter);
+        assert_eq!(ctr.update(&expected).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_ctr_counter_wraps_across_all_bytes() {
+        let key = Aes256Key::new([0x42u8; 32]);
+        let counter = [0xffu8; 16];
+
+        let mut wrapped = Aes256Ctr::new(&key, &counter);
+        let output = wrapped.update(&[0u8; 32]).unwrap();
+
+        let mut zero = Aes256Ctr::new(&key, &[0u8; 16]);
+        assert_eq!(output[16..], zero.update(&[0u8; 16]).unwrap()[..]);
+    }
+
+    #[test]
+    fn test_cbc_sp800_38a_vector() {
+        // F.2.5 CBC-AES256.Encrypt，外加明文恰为整数个分组时的完整 PKCS#7 填充分组
+        let key = key32(SP800_38A_KEY);
+        let iv: [u8; 16] = hex("000102030405060708090a0b0c0d0e0f").try_into().unwrap();
+        let plaintext = hex(SP800_38A_PLAINTEXT);
+        let expected = hex(
+            "f58c4c04d6e5f1ba779eabfb5f7bfbd69cfc4e967edb808d679f777bc6702c7d39f23369a9d9bacfa530e26304231461b2eb05e2c39be9fcda6c19078c6a9d1b",
+        );
+
+        let mut enc = Aes256CbcEncryptor::new(&key, &iv);
+        let mut ciphertext = enc.update(&plaintext).unwrap();
+        assert_eq!(ciphertext, expected);
+        ciphertext.extend(enc.finalize().unwrap());
+        assert_eq!(ciphertext.len(), plaintext.len() + 16);
+
+        let mut dec = Aes256CbcDecryptor::new(&key, &iv);
+        let mut decrypted = dec.update(&ciphertext).unwrap();
+        decrypted.extend(dec.finalize().unwrap());
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_cbc_streaming_roundtrip_all_lengths() {
+        let key = Aes256Key::new([0x11u8; 32]);
+        let iv = [0x22u8; 16];
+
+        for len in 0..70 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+
+            let mut enc = Aes256CbcEncryptor::new(&key, &iv);
+            let mut ciphertext = feed_in_chunks(&plaintext, |chunk| enc.update(chunk));
+            ciphertext.extend(enc.finalize().unwrap());
+            assert_eq!(ciphertext.len(), (len / 16 + 1) * 16);
+
+            let mut dec = Aes256CbcDecryptor::new(&key, &iv);
+            let mut decrypted = feed_in_chunks(&ciphertext, |chunk| dec.update(chunk));
+            decrypted.extend(dec.finalize().unwrap());
+            assert_eq!(decrypted, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_cbc_rejects_bad_padding_and_length() {
+        let key = Aes256Key::new([0x33u8; 32]);
+        let iv = [0x44u8; 16];
+
+        let mut enc = Aes256CbcEncryptor::new(&key, &iv);
+        let mut ciphertext = enc.update(b"rollup data-availability blob").unwrap();
+        ciphertext.extend(enc.finalize().unwrap());
+
+        // 篡改倒数第二个分组会翻转最后一个明文分组的填充字节
+        let mut tampered = ciphertext.clone();
+        let last_pad_in_prev = tampered.len() - 17;
+        tampered[last_pad_in_prev] ^= 0x01;
+        let mut dec = Aes256CbcDecryptor::new(&key, &iv);
+        dec.update(&tampered).unwrap();
+        assert!(dec.finalize().is_err());
+
+        let mut dec = Aes256CbcDecryptor::new(&key, &iv);
+        dec.update(&ciphertext[..ciphertext.len() - 1]).unwrap();
+        assert!(dec.finalize().is_err());
+
+        let dec = Aes256CbcDecryptor::new(&key, &iv);
+        assert!(dec.finalize().is_err());
+    }
+
+    #[test]
+    fn test_xts_ieee1619_vector_10() {
+        let plaintext: Vec<u8> = (0..=255u8).chain(0..=255u8).collect();
+        let expected = hex(
+            "1c3b3a102f770386e4836c99e370cf9bea00803f5e482357a4ae12d414a3e63b5d31e276f8fe4a8d66b317f9ac683f44680a86ac35adfc3345befecb4bb188fd5776926c49a3095eb108fd1098baec70aaa66999a72a82f27d848b21d4a741b0c5cd4d5fff9dac89aeba122961d03a757123e9870f8acf1000020887891429ca2a3e7a7d7df7b10355165c8b9a6d0a7de8b062c4500dc4cd120c0f7418dae3d0b5781c34803fa75421c790dfe1de1834f280d7667b327f6c8cd7557e12ac3a0f93ec05c52e0493ef31a12d3d9260f79a289d6a379bc70c50841473d1a8cc81ec583e9645e07b8d9670655ba5bbcfecc6dc3966380ad8fecb17b6ba02469a020a84e18e8f84252070c13e9f1f289be54fbc481457778f616015e1327a02b140f1505eb309326d68378f8374595c849d84f4c333ec4423885143cb47bd71c5edae9be69a2ffeceb1bec9de244fbe15992b11b77c040f12bd8f6a975a44a0f90c29a9abc3d4d893927284c58754cce294529f8614dcd2aba991925fedc4ae74ffac6e333b93eb4aff0479da9a410e4450e0dd7ae4c6e2910900575da401fc07059f645e8b7e9bfdef33943054ff84011493c27b3429eaedb4ed5376441a77ed43851ad77f16f541dfd269d50d6a5f14fb0aab1cbb4c1550be97f7ab4066193c4caa773dad38014bd2092fa755c824bb5e54c4f36ffda9fcea70b9c6e693e148c151",
+        );
+        let tweak = xts_sector_tweak(0xff);
+        let (data_key, tweak_key) = xts_keys();
+
+        let mut enc = Aes256XtsEncryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+        let mut ciphertext = enc.update(&plaintext).unwrap();
+        ciphertext.extend(enc.finalize().unwrap());
+        assert_eq!(ciphertext, expected);
+
+        let mut enc = Aes256XtsEncryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+        let mut streamed = feed_in_chunks(&plaintext, |chunk| enc.update(chunk));
+        streamed.extend(enc.finalize().unwrap());
+        assert_eq!(streamed, expected);
+
+        let mut dec = Aes256XtsDecryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+        let mut decrypted = feed_in_chunks(&expected, |chunk| dec.update(chunk));
+        decrypted.extend(dec.finalize().unwrap());
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_xts_ciphertext_stealing() {
+        // 40 字节数据单元：两个完整分组加 8 字节尾部，结果与 OpenSSL 交叉验证
+        let tweak: [u8; 16] = hex("0102030405060708090a0b0c0d0e0f10").try_into().unwrap();
+        let (data_key, tweak_key) = xts_keys();
+        let plaintext: Vec<u8> = (0..40u8).collect();
+        let expected =
+            hex("3d41ab531aedb0aba97a553b5c1e8d5520cc4b5fe59afbd48cec0b1709df2ee0eae63d11919d86de");
+
+        let mut enc = Aes256XtsEncryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+        let mut ciphertext = feed_in_chunks(&plaintext, |chunk| enc.update(chunk));
+        ciphertext.extend(enc.finalize().unwrap());
+        assert_eq!(ciphertext, expected);
+
+        for len in 16..70 {
+            let data: Vec<u8> = (0..len as u8).collect();
+            let mut enc = Aes256XtsEncryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+            let mut ciphertext = enc.update(&data).unwrap();
+            ciphertext.extend(enc.finalize().unwrap());
+            assert_eq!(ciphertext.len(), len);
+
+            let mut dec = Aes256XtsDecryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+            let mut decrypted = dec.update(&ciphertext).unwrap();
+            decrypted.extend(dec.finalize().unwrap());
+            assert_eq!(decrypted, data);
+        }
+    }
+
+    #[test]
+    fn test_xts_rejects_short_unit_and_equal_keys() {
+        let tweak = xts_sector_tweak(0);
+        let (data_key, tweak_key) = xts_keys();
+
+        let mut enc = Aes256XtsEncryptor::new(&data_key, &tweak_key, &tweak).unwrap();
+        enc.update(&[0u8; 15]).unwrap();
+        assert!(enc.finalize().is_err());
+
+        let same = Aes256Key::new([0x5au8; 32]);
+        assert!(Aes256XtsEncryptor::new(&same, &same.clone(), &tweak).is_err());
+        assert!(Aes256XtsDecryptor::new(&same, &same.clone(), &tweak).is_err());
+    }
+
+    #[test]
+    fn test_modes_run_on_the_accelerator() {
+        let key = Aes256Key::new([0x66u8; 32]);
+        let iv = [0x77u8; 16];
+        let data = [0x88u8; 40];
+
+        // 每个分组都经过加速器：CTR 3 个密钥流分组，CBC 加密 3 个分组、解密 3 个分组
+        let mock = MockAccelerator::new();
+        let mut ctr = Aes256Ctr::with_accelerator(&key, &iv, Box::new(mock.clone()));
+        assert_eq!(
+            ctr.update(&data).unwrap(),
+            Aes256Ctr::new(&key, &iv).update(&data).unwrap()
+        );
+        assert_eq!(mock.call_count(MockOperation::Aes256EncryptBlock), 3);
+
+        let mock = MockAccelerator::new();
+        let mut enc = Aes256CbcEncryptor::with_accelerator(&key, &iv, Box::new(mock.clone()));
+        let mut ciphertext = enc.update(&data).unwrap();
+        ciphertext.extend(enc.finalize().unwrap());
+        let mut dec = Aes256CbcDecryptor::with_accelerator(&key, &iv, Box::new(mock.clone()));
+        let mut decrypted = dec.update(&ciphertext).unwrap();
+        decrypted.extend(dec.finalize().unwrap());
+        assert_eq!(decrypted, data);
+        assert_eq!(mock.call_count(MockOperation::Aes256EncryptBlock), 3);
+        assert_eq!(mock.call_count(MockOperation::Aes256DecryptBlock), 3);
+        // 每个实例只取得一次密钥调度
+        assert_eq!(mock.call_count(MockOperation::Aes256KeySchedule), 2);
+
+        // 加速器错误作为 Err 返回
+        let failing = || {
+            Box::new(
+                MockAccelerator::new()
+                    .fail_operation(MockOperation::Aes256EncryptBlock)
+                    .fail_operation(MockOperation::Aes256DecryptBlock),
+            )
+        };
+        let mut ctr = Aes256Ctr::with_accelerator(&key, &iv, failing());
+        assert!(ctr.update(&data).is_err());
+        let enc = Aes256CbcEncryptor::with_accelerator(&key, &iv, failing());
+        assert!(enc.finalize().is_err());
+        let mut dec = Aes256CbcDecryptor::with_accelerator(&key, &iv, failing());
+        assert!(dec.update(&[0u8; 32]).is_err());
+
+        let (data_key, tweak_key) = xts_keys();
+        assert!(
+            Aes256XtsEncryptor::with_accelerator(&data_key, &tweak_key, &iv, failing()).is_err()
+        );
+        let failing_schedule =
+            || Box::new(MockAccelerator::new().fail_operation(MockOperation::Aes256KeySchedule));
+        let mut ctr = Aes256Ctr::with_accelerator(&key, &iv, failing_schedule());
+        assert!(ctr.update(&data).is_err());
+    }
+
+    #[test]
+    fn test_modes_expand_each_key_once() {
+        let key = Aes256Key::new([0x66u8; 32]);
+        let iv = [0x77u8; 16];
+        let data = [0x88u8; 16 * 64 + 5];
+
+        let mock = MockAccelerator::new();
+        let mut enc = Aes256CbcEncryptor::with_accelerator(&key, &iv, Box::new(mock.clone()));
+        for chunk in data.chunks(100) {
+            enc.update(chunk).unwrap();
+        }
+        enc.finalize().unwrap();
+        assert_eq!(mock.call_count(MockOperation::Aes256KeySchedule), 1);
+        assert_eq!(mock.call_count(MockOperation::Aes256EncryptBlock), 65);
+
+        // XTS：数据密钥一次调度，tweak 密钥只加密初始 tweak 一个分组
+        let (data_key, tweak_key) = xts_keys();
+        let mock = MockAccelerator::new();
+        let mut xts = Aes256XtsEncryptor::with_accelerator(
+            &data_key,
+            &tweak_key,
+            &iv,
+            Box::new(mock.clone()),
+        )
+        .unwrap();
+        let mut ciphertext = xts.update(&data).unwrap();
+        ciphertext.extend(xts.finalize().unwrap());
+        assert_eq!(ciphertext.len(), data.len());
+        assert_eq!(mock.call_count(MockOperation::Aes256KeySchedule), 1);
+        assert_eq!(mock.call_count(MockOperation::Aes256EncryptBlock), 1 + 65);
+    }
+}
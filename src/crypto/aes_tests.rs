@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod aes_cipher_tests {
+    use crate::crypto::aes::{Aes256Riscv, AesGcmCiphertext};
+    use crate::crypto::secret::Aes256Key;
+    use crate::crypto::test_util::hex;
+
+    #[test]
+    fn test_aes256_gcm_nist_vectors() {
+        // McGrew & Viega, "The Galois/Counter Mode of Operation", Test Case 13-16
+        let k2 = "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308";
+        let p = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255";
+        let c = "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662898015ad";
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "000000000000000000000000",
+                "",
+                "",
+                "",
+                "530f8afbc74536b9a963b4f1c4cb738b",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "000000000000000000000000",
+                "",
+                "00000000000000000000000000000000",
+                "cea7403d4d606b6e074ec5d3baf39d18",
+                "d0d1c8a799996bf0265b98b5d48ab919",
+            ),
+            (
+                k2,
+                "cafebabefacedbaddecaf888",
+                "",
+                p,
+                c,
+                "b094dac5d93471bdec1a502270e3cc6c",
+            ),
+            (
+                k2,
+                "cafebabefacedbaddecaf888",
+                "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+                &p[..120],
+                &c[..120],
+                "76fc6ece0f4e1768cddf8853bb2d551b",
+            ),
+        ];
+
+        let aes = Aes256Riscv::new();
+        for (key, nonce, aad, plaintext, ciphertext, tag) in vectors {
+            let key = Aes256Key::from_slice(&hex(key)).unwrap();
+            let nonce: [u8; 12] = hex(nonce).try_into().unwrap();
+            let aad = hex(aad);
+
+            let sealed = aes
+                .encrypt_gcm_with_nonce(&hex(plaintext), &key, &nonce, &aad)
+                .unwrap();
+            assert_eq!(sealed.ciphertext, hex(ciphertext));
+            assert_eq!(sealed.tag.to_vec(), hex(tag));
+
+            let opened = aes.decrypt_gcm(&sealed, &key, &aad).unwrap();
+            assert_eq!(opened, hex(plaintext));
+        }
+    }
+
+    #[test]
+    fn test_aes256_gcm_rejects_tampering() {
+        let aes = Aes256Riscv::new();
+        let key = Aes256Key::new([7u8; 32]);
+        let aad = b"block 42";
+        let sealed = aes.encrypt_gcm(b"rollup state blob", &key, aad).unwrap();
+
+        let mut bad_ciphertext = sealed.clone();
+        bad_ciphertext.ciphertext[0] ^= 0x01;
+        assert!(aes.decrypt_gcm(&bad_ciphertext, &key, aad).is_err());
+
+        let mut bad_tag = sealed.clone();
+        bad_tag.tag[15] ^= 0x80;
+        assert!(aes.decrypt_gcm(&bad_tag, &key, aad).is_err());
+
+        let mut bad_nonce = sealed.clone();
+        bad_nonce.nonce[0] ^= 0x01;
+        assert!(aes.decrypt_gcm(&bad_nonce, &key, aad).is_err());
+
+        assert!(aes.decrypt_gcm(&sealed, &key, b"block 43").is_err());
+        assert!(
+            aes.decrypt_gcm(&sealed, &Aes256Key::new([8u8; 32]), aad)
+                .is_err()
+        );
+        assert_eq!(
+            aes.decrypt_gcm(&sealed, &key, aad).unwrap(),
+            b"rollup state blob"
+        );
+    }
+
+    #[test]
+    fn test_aes256_gcm_byte_encoding() {
+        let aes = Aes256Riscv::new();
+        let key = Aes256Key::new([3u8; 32]);
+        let plaintext = b"Hello, ZK Rollup!";
+
+        let first = aes.encrypt_aes256(plaintext, &key).unwrap();
+        let second = aes.encrypt_aes256(plaintext, &key).unwrap();
+        assert_eq!(first.len(), plaintext.len() + 28);
+        // 每次加密使用新的随机 nonce
+        assert_ne!(first, second);
+
+        let sealed = AesGcmCiphertext::from_bytes(&first).unwrap();
+        assert_eq!(sealed.to_bytes(), first);
+        assert_eq!(aes.decrypt_aes256(&second, &key).unwrap(), plaintext);
+
+        let mut truncated = first.clone();
+        truncated.pop();
+        assert!(aes.decrypt_aes256(&truncated, &key).is_err());
+        assert!(aes.decrypt_aes256(&first[..27], &key).is_err());
+    }
+
+    #[test]
+    fn test_aes256_key_expansion_fips197() {
+        // FIPS-197 Appendix A.3
+        let key = Aes256Key::from_slice(&hex(
+            "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+        ))
+        .unwrap();
+        let mut aes = Aes256Riscv::new();
+        let expanded = aes.key_expansion(&key).unwrap();
+
+        let expanded = expanded.expose_secret();
+        assert_eq!(expanded.len(), 240);
+        assert_eq!(expanded[..32], *key.expose_secret());
+        assert_eq!(expanded[32..36], hex("9ba35411"));
+        assert_eq!(expanded[224..], hex("fe4890d1e6188d0b046df344706c631e"));
+    }
+
+    #[test]
+    fn test_aes256_block_fips197() {
+        // FIPS-197 Appendix C.3
+        let key = Aes256Key::from_slice(&hex(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ))
+        .unwrap();
+        let plaintext: [u8; 16] = hex("00112233445566778899aabbccddeeff").try_into().unwrap();
+        let ciphertext: [u8; 16] = hex("8ea2b7ca516745bfeafc49904b496089").try_into().unwrap();
+
+        let mut aes = Aes256Riscv::new();
+        aes.key_expansion(&key).unwrap();
+
+        assert_eq!(aes.encrypt_block(&plaintext).unwrap(), ciphertext);
+        assert_eq!(aes.decrypt_block(&ciphertext).unwrap(), plaintext);
+        assert_eq!(
+            aes.encrypt_block_accelerated(&plaintext, &key).unwrap(),
+            ciphertext
+        );
+        assert_eq!(
+            aes.decrypt_block_accelerated(&ciphertext, &key).unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn test_aes256_block_software_matches_accelerator() {
+        let mut aes = Aes256Riscv::new();
+        for _ in 0..64 {
+            let key = Aes256Key::random().unwrap();
+            let block: [u8; 16] = rand::random();
+            aes.key_expansion(&key).unwrap();
+
+            let software = aes.encrypt_block(&block).unwrap();
+            assert_eq!(
+                software,
+                aes.encrypt_block_accelerated(&block, &key).unwrap()
+            );
+            assert_eq!(aes.decrypt_block(&software).unwrap(), block);
+            assert_eq!(
+                aes.decrypt_block(&block).unwrap(),
+                aes.decrypt_block_accelerated(&block, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes256_block_requires_key_expansion() {
+        let aes = Aes256Riscv::new();
+        assert!(aes.encrypt_block(&[0u8; 16]).is_err());
+        assert!(aes.decrypt_block(&[0u8; 16]).is_err());
+    }
+}
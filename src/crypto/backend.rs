@@ -0,0 +1,406 @@
+//! 加密后端注册表
+//!
+//! 按名称注册和选择 `RiscVCryptoExt` 实现，使测试和部署可以在不修改源码的情况下
+//! 切换后端。内置后端：
+//!
+//! - `software`: `SoftwareBackend`，纯软件实现
+//! - `simulated`: `HardwareAccelerator`，模拟的 RISC-V 加速器（默认）
+//! - `device`: `DeviceClient`，通过设备协议访问 `ZK_ROLLUP_CRYPTO_DEVICE` 指定的路径
+//!   （默认 `/dev/crypto0`），首次调用时才打开设备
+//! - `mock`: `MockAccelerator`，委托给 `SoftwareBackend`、未配置故障的 Mock 加速器
+//! - `zkn-emulator`: `ZknBackend<ZknEmulator>`，由 Zkn 标量加密指令模型组合的实现
+//! - `riscv-zkn`: 启用 `riscv-zkn` 特性时注册，CPU 支持 Zkn 时使用硬件指令，
+//!   否则回退到 `SoftwareBackend`
+//!
+//! # 选择顺序
+//!
+//! 1. 环境变量 `ZK_ROLLUP_CRYPTO_BACKEND`
+//! 2. 调用方传入的配置值
+//! 3. 默认后端：`simulated`，启用 `riscv-zkn` 特性时为 `riscv-zkn`
+//!
+//! `Aes256Riscv::new`、`Sha256Riscv::new` 和 `Ed25519Riscv::new` 都通过
+//! `default_backend()` 获取加速器；需要显式指定时使用各类型的 `with_accelerator`。
+//!
+//! 默认后端只在首次使用时按上述顺序解析一次，此后所有 `new()` 共享同一个实例
+//! （`device` 后端因此只有一条设备连接）。环境变量指定了未注册的后端时不会 panic：
+//! 默认后端退化为 `UnavailableBackend`，各操作返回配置错误，带软件回退的哈希仍得到正确结果。
+//! 程序应在启动时调用 `try_default_backend()`，尽早报告错误配置。
+
+use crate::crypto::mock::MockAccelerator;
+use crate::crypto::riscv_ext::{
+    Aes256KeySchedule, HardwareAccelerator, RiscVCryptoExt, SoftwareBackend,
+};
+use crate::crypto::zkn::{ZknBackend, ZknEmulator};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// 选择后端的环境变量名
+pub const BACKEND_ENV_VAR: &str = "ZK_ROLLUP_CRYPTO_BACKEND";
+/// `device` 后端读取设备路径的环境变量名
+pub const DEVICE_ENV_VAR: &str = "ZK_ROLLUP_CRYPTO_DEVICE";
+/// 未配置时使用的后端名称
+#[cfg(not(feature = "riscv-zkn"))]
+pub const DEFAULT_BACKEND: &str = "simulated";
+/// 未配置时使用的后端名称
+#[cfg(feature = "riscv-zkn")]
+pub const DEFAULT_BACKEND: &str = "riscv-zkn";
+
+/// 后端工厂函数，每次调用创建一个新的后端实例
+pub type BackendFactory =
+    Arc<dyn Fn() -> Result<Box<dyn RiscVCryptoExt>, Box<dyn Error>> + Send + Sync>;
+
+/// 后端注册表操作中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct BackendError {
+    message: String,
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Backend Error: {}", self.message)
+    }
+}
+
+impl Error for BackendError {}
+
+/// 后端注册表
+///
+/// 名称到工厂函数的映射。可以独立创建用于测试，
+/// 也可以通过 `BackendRegistry::global()` 使用进程级共享实例。
+#[allow(dead_code)]
+pub struct BackendRegistry {
+    factories: RwLock<BTreeMap<String, BackendFactory>>,
+}
+
+#[allow(dead_code)]
+impl BackendRegistry {
+    /// 创建只包含内置后端的注册表
+    ///
+    /// # 返回
+    ///
+    /// 已注册全部内置后端的注册表
+    pub fn new() -> Self {
+        let registry = BackendRegistry {
+            factories: RwLock::new(BTreeMap::new()),
+        };
+        registry.register("software", || Ok(Box::new(SoftwareBackend)));
+        registry.register("simulated", || Ok(Box::new(HardwareAccelerator::new())));
+        registry.register("device", || {
+            let mut accelerator = HardwareAccelerator::new();
+            if let Ok(path) = std::env::var(DEVICE_ENV_VAR) {
+                accelerator.set_device_path(path);
+            }
+            Ok(Box::new(accelerator.open_device()))
+        });
+        registry.register("mock", || Ok(Box::new(MockAccelerator::new())));
+        registry.register("zkn-emulator", || {
+            Ok(Box::new(ZknBackend::new(ZknEmulator)))
+        });
+        #[cfg(feature = "riscv-zkn")]
+        registry.register("riscv-zkn", || {
+            Ok(crate::crypto::zkn_hw::accelerated_backend())
+        });
+        registry
+    }
+
+    /// 进程级共享注册表
+    ///
+    /// 首次访问时以内置后端初始化。
+    pub fn global() -> &'static BackendRegistry {
+        static GLOBAL: OnceLock<BackendRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(BackendRegistry::new)
+    }
+
+    /// 注册后端
+    ///
+    /// 同名后端会被替换，因此也可用于覆盖内置后端。
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 后端名称
+    /// * `factory` - 创建后端实例的工厂函数
+    pub fn register<F>(&self, name: &str, factory: F)
+    where
+        F: Fn() -> Result<Box<dyn RiscVCryptoExt>, Box<dyn Error>> + Send + Sync + 'static,
+    {
+        self.factories
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), Arc::new(factory));
+    }
+
+    /// 按名称创建后端实例
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Box<dyn RiscVCryptoExt>)`: 新的后端实例
+    /// - `Err(Box<dyn Error>)`: 名称未注册或工厂函数失败
+    pub fn create(&self, name: &str) -> Result<Box<dyn RiscVCryptoExt>, Box<dyn Error>> {
+        // 先复制工厂再调用，避免工厂内部访问注册表时死锁
+        let factory = self
+            .factories
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned();
+
+        match factory {
+            Some(factory) => factory(),
+            None => Err(Box::new(BackendError {
+                message: format!(
+                    "Unknown backend '{}', available: {}",
+                    name,
+                    self.names().join(", ")
+                ),
+            })),
+        }
+    }
+
+    /// 已注册的后端名称（按字典序）
+    pub fn names(&self) -> Vec<String> {
+        self.factories
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// 按选择顺序创建后端
+    ///
+    /// # 参数
+    ///
+    /// * `configured` - 配置文件等来源给出的后端名称，环境变量优先于该值
+    pub fn select(
+        &self,
+        configured: Option<&str>,
+    ) -> Result<Box<dyn RiscVCryptoExt>, Box<dyn Error>> {
+        let from_env = std::env::var(BACKEND_ENV_VAR).ok();
+        self.create(&resolve_backend_name(from_env.as_deref(), configured))
+    }
+}
+
+impl Default for BackendRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 按选择顺序确定后端名称
+///
+/// 空字符串视为未设置。
+///
+/// # 参数
+///
+/// * `from_env` - 环境变量的值
+/// * `configured` - 配置值
+///
+/// # 返回
+///
+/// 应使用的后端名称
+pub fn resolve_backend_name(from_env: Option<&str>, configured: Option<&str>) -> String {
+    from_env
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .or_else(|| configured.map(str::trim).filter(|name| !name.is_empty()))
+        .unwrap_or(DEFAULT_BACKEND)
+        .to_string()
+}
+
+/// 在全局注册表中注册后端
+#[allow(dead_code)]
+pub fn register_backend<F>(name: &str, factory: F)
+where
+    F: Fn() -> Result<Box<dyn RiscVCryptoExt>, Box<dyn Error>> + Send + Sync + 'static,
+{
+    BackendRegistry::global().register(name, factory);
+}
+
+/// 从全局注册表按名称创建后端
+#[allow(dead_code)]
+pub fn create_backend(name: &str) -> Result<Box<dyn RiscVCryptoExt>, Box<dyn Error>> {
+    BackendRegistry::global().create(name)
+}
+
+/// 进程级默认后端的解析结果，失败时保存错误信息
+static DEFAULT_HANDLE: OnceLock<Result<Arc<dyn RiscVCryptoExt>, String>> = OnceLock::new();
+
+/// 获取进程级默认后端
+///
+/// 首次调用时按环境变量从全局注册表创建后端并缓存，之后返回同一实例的共享句柄；
+/// 此后对全局注册表的修改不再影响默认后端。
+///
+/// # 返回
+///
+/// - `Ok(Arc<dyn RiscVCryptoExt>)`: 共享的默认后端
+/// - `Err(Box<dyn Error>)`: 环境变量指定了未注册的后端或工厂函数失败，每次调用返回相同错误
+pub fn try_default_backend() -> Result<Arc<dyn RiscVCryptoExt>, Box<dyn Error>> {
+    DEFAULT_HANDLE
+        .get_or_init(|| {
+            BackendRegistry::global()
+                .select(None)
+                .map(Arc::from)
+                .map_err(|e| format!("{}: {}", BACKEND_ENV_VAR, e))
+        })
+        .clone()
+        .map_err(|message| Box::new(BackendError { message }) as Box<dyn Error>)
+}
+
+/// 默认后端的句柄
+///
+/// 供各加密类型的 `new()` 使用，不读取环境变量、不访问注册表。
+/// 默认后端无法创建时返回 `UnavailableBackend`，不会 panic。
+pub fn default_backend() -> Box<dyn RiscVCryptoExt> {
+    match try_default_backend() {
+        Ok(shared) => Box::new(shared),
+        Err(e) => Box::new(UnavailableBackend::new(e.to_string())),
+    }
+}
+
+/// 无法使用的后端
+///
+/// 默认后端解析失败时代替它：所有操作都返回创建时给出的错误。
+/// `Sha256Riscv`、`Keccak256Riscv` 等在加速器出错时回退到软件实现，因此仍能得到正确结果；
+/// 其他调用方通过 `Err` 看到配置错误。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct UnavailableBackend {
+    message: String,
+}
+
+#[allow(dead_code)]
+impl UnavailableBackend {
+    /// 创建所有操作都返回 `message` 的后端
+    pub fn new(message: impl Into<String>) -> Self {
+        UnavailableBackend {
+            message: message.into(),
+        }
+    }
+
+    fn error<T>(&self) -> Result<T, Box<dyn Error>> {
+        Err(Box::new(BackendError {
+            message: format!("Backend unavailable: {}", self.message),
+        }))
+    }
+}
+
+impl RiscVCryptoExt for UnavailableBackend {
+    fn copr_encrypt_aes256(
+        &self,
+        _key: &[u8; 32],
+        _nonce: &[u8; 12],
+        _aad: &[u8],
+        _plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        _key: &[u8; 32],
+        _nonce: &[u8; 12],
+        _aad: &[u8],
+        _ciphertext: &[u8],
+        _tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        _key: &[u8; 32],
+        _block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_aes256_decrypt_block(
+        &self,
+        _key: &[u8; 32],
+        _block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        _key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_hash_sha256(&self, _input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        _state: &[u8; 32],
+        _block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_keccak_f1600(&self, _state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_ec_mul(
+        &self,
+        _scalar: &[u8; 32],
+        _point: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_ec_add(
+        &self,
+        _point1: &[u8; 32],
+        _point2: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_sign_ed25519(
+        &self,
+        _secret: &[u8; 32],
+        _message: &[u8],
+    ) -> Result<[u8; 64], Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_verify_ed25519(
+        &self,
+        _public: &[u8; 32],
+        _message: &[u8],
+        _signature: &[u8; 64],
+    ) -> Result<bool, Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        _items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_zkp_prove(&self, _witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_zkp_verify(&self, _proof: &[u8], _public_input: &[u8]) -> Result<bool, Box<dyn Error>> {
+        self.error()
+    }
+
+    fn copr_msm_accelerate(
+        &self,
+        _points: &[Vec<u8>],
+        _scalars: &[Vec<u8>],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.error()
+    }
+}
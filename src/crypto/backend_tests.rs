@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod crypto_backend_tests {
+    use crate::crypto::aes::Aes256Riscv;
+    use crate::crypto::backend::{
+        BackendRegistry, DEFAULT_BACKEND, UnavailableBackend, create_backend, default_backend,
+        register_backend, resolve_backend_name, try_default_backend,
+    };
+    use crate::crypto::ec::Ed25519Riscv;
+    use crate::crypto::riscv_ext::{HardwareAccelerator, RiscVCryptoExt};
+    use crate::crypto::secret::Aes256Key;
+    use crate::crypto::sha256::Sha256Riscv;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn test_registry_has_builtin_backends() {
+        let registry = BackendRegistry::new();
+        #[cfg(not(feature = "riscv-zkn"))]
+        let expected = vec!["device", "mock", "simulated", "software", "zkn-emulator"];
+        #[cfg(feature = "riscv-zkn")]
+        let expected = vec![
+            "device",
+            "mock",
+            "riscv-zkn",
+            "simulated",
+            "software",
+            "zkn-emulator",
+        ];
+        assert_eq!(registry.names(), expected);
+
+        for name in registry.names() {
+            assert!(registry.create(&name).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_registry_rejects_unknown_backend() {
+        let registry = BackendRegistry::new();
+        let err = registry.create("fpga").err().unwrap().to_string();
+
+        assert!(err.contains("fpga"));
+        assert!(err.contains("software"));
+    }
+
+    #[test]
+    fn test_backend_name_resolution_order() {
+        assert_eq!(resolve_backend_name(None, None), DEFAULT_BACKEND);
+        assert_eq!(resolve_backend_name(None, Some("software")), "software");
+        assert_eq!(
+            resolve_backend_name(Some("simulated"), Some("software")),
+            "simulated"
+        );
+        assert_eq!(
+            resolve_backend_name(Some("  "), Some("software")),
+            "software"
+        );
+    }
+
+    #[test]
+    fn test_builtin_backends_agree() {
+        let registry = BackendRegistry::new();
+        let software = registry.create("software").unwrap();
+        let simulated = registry.create("simulated").unwrap();
+
+        let key: [u8; 32] = rand::random();
+        let block: [u8; 16] = rand::random();
+        let data: Vec<u8> = (0..200).map(|_| rand::random()).collect();
+
+        assert_eq!(
+            software.copr_aes256_encrypt_block(&key, &block).unwrap(),
+            simulated.copr_aes256_encrypt_block(&key, &block).unwrap()
+        );
+        assert_eq!(
+            software.copr_aes256_decrypt_block(&key, &block).unwrap(),
+            simulated.copr_aes256_decrypt_block(&key, &block).unwrap()
+        );
+
+        let expected: [u8; 32] = Sha256::digest(&data).into();
+        assert_eq!(software.copr_hash_sha256(&data).unwrap(), expected);
+        assert_eq!(simulated.copr_hash_sha256(&data).unwrap(), expected);
+
+        let sealed = software
+            .copr_encrypt_aes256(&key, &[7u8; 12], b"aad", &data)
+            .unwrap();
+        assert_eq!(
+            simulated
+                .copr_encrypt_aes256(&key, &[7u8; 12], b"aad", &data)
+                .unwrap(),
+            sealed
+        );
+
+        let signature = software.copr_sign_ed25519(&key, &data).unwrap();
+        assert_eq!(simulated.copr_sign_ed25519(&key, &data).unwrap(), signature);
+    }
+
+    #[test]
+    fn test_crypto_types_accept_registered_backends() {
+        let registry = BackendRegistry::new();
+        registry.register("disabled", || {
+            let mut accelerator = HardwareAccelerator::new();
+            accelerator.set_enabled(false);
+            Ok(Box::new(accelerator))
+        });
+
+        // 禁用的加速器使 GCM 失败，而 SHA-256 回退到软件实现
+        let aes = Aes256Riscv::with_accelerator(registry.create("disabled").unwrap());
+        assert!(
+            aes.encrypt_aes256(b"data", &Aes256Key::new([0u8; 32]))
+                .is_err()
+        );
+
+        let mut hasher = Sha256Riscv::with_accelerator(registry.create("disabled").unwrap());
+        hasher.update(b"abc");
+        assert_eq!(hasher.finalize(), Sha256Riscv::hash(b"abc"));
+
+        // 没有专用单元的运算同样受开关控制
+        let disabled = registry.create("disabled").unwrap();
+        assert!(disabled.copr_aes256_key_schedule(&[1u8; 32]).is_err());
+        assert!(disabled.copr_sign_ed25519(&[1u8; 32], b"message").is_err());
+        assert!(disabled.copr_verify_ed25519_batch(&[]).is_err());
+        assert!(disabled.copr_zkp_prove(b"witness").is_err());
+        assert!(disabled.copr_msm_accelerate(&[], &[]).is_err());
+
+        let ec = Ed25519Riscv::with_accelerator(registry.create("software").unwrap());
+        let (sk, pk) = ec.keygen().unwrap();
+        let signature = ec.sign(&sk, b"message").unwrap();
+        assert!(ec.verify(&pk, b"message", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_global_registration() {
+        register_backend("global-test-software", || {
+            Ok(Box::new(crate::crypto::riscv_ext::SoftwareBackend))
+        });
+
+        let backend = create_backend("global-test-software").unwrap();
+        assert_eq!(
+            backend.copr_hash_sha256(b"abc").unwrap(),
+            Sha256Riscv::hash(b"abc")
+        );
+    }
+
+    #[test]
+    fn test_default_backend_resolved_once() {
+        // 测试进程未设置未知的后端名，默认后端可用且每次返回同一实例
+        let first = try_default_backend().unwrap();
+        let second = try_default_backend().unwrap();
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+        assert_eq!(
+            default_backend().copr_hash_sha256(b"abc").unwrap(),
+            Sha256Riscv::hash(b"abc")
+        );
+    }
+
+    #[test]
+    fn test_unavailable_backend_reports_configuration_error() {
+        let backend = UnavailableBackend::new("Unknown backend 'fpga'");
+        let error = backend.copr_sign_ed25519(&[1u8; 32], b"msg").unwrap_err();
+        assert!(error.to_string().contains("Unknown backend 'fpga'"));
+        assert!(backend.copr_ec_mul(&[1u8; 32], &[2u8; 32]).is_err());
+
+        // 哈希回退到软件实现，不 panic 且结果正确
+        let mut hasher = Sha256Riscv::with_accelerator(Box::new(backend.clone()));
+        hasher.update(&[0x61; 200]);
+        assert_eq!(hasher.finalize(), Sha256::digest([0x61; 200]).as_slice());
+
+        // 需要加速器的操作把配置错误作为 Err 返回
+        let aes = Aes256Riscv::with_accelerator(Box::new(backend));
+        let key = Aes256Key::new([7u8; 32]);
+        assert!(aes.encrypt_block_accelerated(&[0u8; 16], &key).is_err());
+    }
+}
@@ -0,0 +1,712 @@
+//! 标准测试向量一致性检验模块
+//!
+//! 读取 NIST CAVP（`.rsp`）和 RFC 8032 格式的测试向量文件，对每个已注册的
+//! `RiscVCryptoExt` 后端逐条检验，并按后端输出通过/失败报告。
+//! 往返测试发现不了加解密两侧同样错误的实现（例如只做异或的 "AES"），已知答案测试可以。
+//!
+//! # 支持的向量
+//!
+//! - `VectorKind::AesBlock`: AESAVS ECB 文件（`[ENCRYPT]` / `[DECRYPT]`，`KEY`、`PLAINTEXT`、
+//!   `CIPHERTEXT`），检验 `copr_aes256_encrypt_block` / `copr_aes256_decrypt_block`
+//!   和 `copr_aes256_key_schedule` 返回的分组变换
+//! - `VectorKind::AesGcm`: GCMVS 文件（`gcmEncryptExtIV256.rsp` / `gcmDecrypt256.rsp`），
+//!   检验 `copr_encrypt_aes256` / `copr_decrypt_aes256`，`FAIL` 记录要求认证失败
+//! - `VectorKind::Sha256`: SHAVS 短/长消息文件，分别经 `copr_hash_sha256` 和
+//!   `copr_sha256_compress` 计算
+//! - `VectorKind::Sha256Monte`: SHAVS Monte Carlo 文件
+//! - `VectorKind::Ed25519`: RFC 8032 第 7.1 节的文本格式，检验签名、验证、批量验证，
+//!   并确认篡改后的消息验证失败
+//!
+//! 非 256 位密钥、非 96 位 IV、截短标签和非整字节消息的记录计为跳过。
+//! 完整的 CAVP 文件可用 `VectorFile::load` 直接读取；`builtin_vectors()` 是随源码提供的
+//! 子集（`src/crypto/vectors/`）。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::backend::BackendRegistry;
+//! use crypto::conformance::{builtin_vectors, run_registry};
+//!
+//! for report in run_registry(&BackendRegistry::new(), &builtin_vectors()) {
+//!     println!("{}", report);
+//! }
+//! ```
+
+use crate::crypto::backend::BackendRegistry;
+use crate::crypto::riscv_ext::RiscVCryptoExt;
+use crate::crypto::sha256::SHA256_INITIAL_STATE;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// SHAVS Monte Carlo 每轮的迭代次数
+const MONTE_CARLO_ITERATIONS: usize = 1000;
+
+/// 一致性检验中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct ConformanceError {
+    message: String,
+}
+
+impl fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Conformance Error: {}", self.message)
+    }
+}
+
+impl Error for ConformanceError {}
+
+fn conformance_error(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(ConformanceError {
+        message: message.into(),
+    })
+}
+
+/// 向量文件的类型，决定解析格式和检验的后端操作
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VectorKind {
+    /// AES-256 单分组（CAVP AESAVS ECB）
+    AesBlock,
+    /// AES-256-GCM（CAVP GCMVS）
+    AesGcm,
+    /// SHA-256 短/长消息（CAVP SHAVS）
+    Sha256,
+    /// SHA-256 Monte Carlo（CAVP SHAVS）
+    Sha256Monte,
+    /// Ed25519（RFC 8032 第 7.1 节）
+    Ed25519,
+}
+
+/// 一条测试向量
+///
+/// 字段名统一为大写；CAVP 的节参数（如 `[Keylen = 256]`）也并入字段，
+/// `[ENCRYPT]` / `[DECRYPT]` 记为 `DIRECTION`，单独一行的 `FAIL` 记为空值字段 `FAIL`。
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectorRecord {
+    /// 记录在文件中的起始行号（从 1 开始）
+    pub line: usize,
+    /// 字段名到原始值的映射
+    pub fields: BTreeMap<String, String>,
+}
+
+#[allow(dead_code)]
+impl VectorRecord {
+    /// 是否包含字段
+    pub fn has(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+
+    /// 读取字段的原始值
+    pub fn get(&self, name: &str) -> Result<&str, Box<dyn Error>> {
+        self.fields
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| conformance_error(format!("line {}: missing field {}", self.line, name)))
+    }
+
+    /// 按十六进制解码字段
+    pub fn bytes(&self, name: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        decode_hex(self.get(name)?)
+            .map_err(|e| conformance_error(format!("line {}: field {}: {}", self.line, name, e)))
+    }
+
+    /// 按十六进制解码定长字段
+    pub fn array<const N: usize>(&self, name: &str) -> Result<[u8; N], Box<dyn Error>> {
+        let bytes = self.bytes(name)?;
+        let length = bytes.len();
+        bytes.try_into().map_err(|_| {
+            conformance_error(format!(
+                "line {}: field {} has {} bytes, expected {}",
+                self.line, name, length, N
+            ))
+        })
+    }
+
+    /// 报告中标识该记录的简短描述
+    fn label(&self) -> String {
+        let mut label = format!("line {}", self.line);
+        for name in ["DIRECTION", "TEST", "COUNT", "LEN"] {
+            if let Some(value) = self.fields.get(name) {
+                label.push_str(&format!(" {}={}", name, value));
+            }
+        }
+        label
+    }
+}
+
+/// 解析后的向量文件
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectorFile {
+    /// 文件名，用于报告
+    pub name: String,
+    /// 向量类型
+    pub kind: VectorKind,
+    /// 按文件顺序排列的记录
+    pub records: Vec<VectorRecord>,
+}
+
+#[allow(dead_code)]
+impl VectorFile {
+    /// 解析向量文件内容
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 文件名，用于报告
+    /// * `kind` - 向量类型，`Ed25519` 使用 RFC 8032 文本格式，其余使用 CAVP 格式
+    /// * `text` - 文件内容
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(VectorFile)`: 解析结果
+    /// - `Err(Box<dyn Error>)`: 出现无法识别的行
+    pub fn parse(name: &str, kind: VectorKind, text: &str) -> Result<Self, Box<dyn Error>> {
+        let records = match kind {
+            VectorKind::Ed25519 => parse_rfc8032(text)?,
+            _ => parse_cavp(text)?,
+        };
+        Ok(VectorFile {
+            name: name.to_string(),
+            kind,
+            records,
+        })
+    }
+
+    /// 从磁盘读取并解析向量文件
+    ///
+    /// # 参数
+    ///
+    /// * `path` - 文件路径，文件名用于报告
+    /// * `kind` - 向量类型
+    pub fn load(path: impl AsRef<Path>, kind: VectorKind) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Self::parse(&name, kind, &text)
+    }
+}
+
+/// 解码十六进制字符串，忽略空白
+pub(crate) fn decode_hex(text: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let digits: Vec<u8> = text
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(conformance_error("odd number of hex digits"));
+    }
+    digits
+        .chunks_exact(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| conformance_error("invalid hex digit"))
+        })
+        .collect()
+}
+
+/// 解析 CAVP `.rsp` 格式：`#` 注释、`[...]` 节参数、空行分隔的 `NAME = VALUE` 记录
+fn parse_cavp(text: &str) -> Result<Vec<VectorRecord>, Box<dyn Error>> {
+    let mut records = Vec::new();
+    let mut params: BTreeMap<String, String> = BTreeMap::new();
+    let mut current: Option<VectorRecord> = None;
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            records.extend(current.take());
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            records.extend(current.take());
+            match header.split_once('=') {
+                Some((name, value)) => {
+                    params.insert(name.trim().to_uppercase(), value.trim().to_string());
+                }
+                None => {
+                    params.insert("DIRECTION".to_string(), header.trim().to_uppercase());
+                }
+            }
+            continue;
+        }
+
+        let record = current.get_or_insert_with(|| VectorRecord {
+            line: index + 1,
+            fields: params.clone(),
+        });
+        match line.split_once('=') {
+            Some((name, value)) => {
+                record
+                    .fields
+                    .insert(name.trim().to_uppercase(), value.trim().to_string());
+            }
+            None if line.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                record.fields.insert(line.to_uppercase(), String::new());
+            }
+            None => {
+                return Err(conformance_error(format!(
+                    "line {}: unrecognized line '{}'",
+                    index + 1,
+                    line
+                )));
+            }
+        }
+    }
+
+    records.extend(current);
+    Ok(records)
+}
+
+/// 解析 RFC 8032 第 7.1 节的文本格式
+///
+/// `-----TEST <名称>` 开始一条记录，以冒号结尾的行是字段名（去掉 `(length ...)` 说明），
+/// 其后直到下一个字段名的各行拼接为字段值。
+fn parse_rfc8032(text: &str) -> Result<Vec<VectorRecord>, Box<dyn Error>> {
+    let mut records = Vec::new();
+    let mut current: Option<VectorRecord> = None;
+    let mut field: Option<String> = None;
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if let Some(name) = line.strip_prefix("-----TEST") {
+            records.extend(current.take());
+            let mut fields = BTreeMap::new();
+            fields.insert("TEST".to_string(), name.trim().to_string());
+            current = Some(VectorRecord {
+                line: index + 1,
+                fields,
+            });
+            field = None;
+            continue;
+        }
+
+        // 第一条记录之前是标题和说明
+        let Some(record) = current.as_mut() else {
+            continue;
+        };
+        if let Some(name) = line.strip_suffix(':') {
+            let name = name
+                .split(" (")
+                .next()
+                .unwrap_or(name)
+                .trim()
+                .to_uppercase();
+            record.fields.insert(name.clone(), String::new());
+            field = Some(name);
+        } else if !line.is_empty() {
+            let name = field.as_ref().ok_or_else(|| {
+                conformance_error(format!("line {}: value without a field name", index + 1))
+            })?;
+            record
+                .fields
+                .get_mut(name)
+                .expect("field inserted when its name was read")
+                .push_str(line);
+        }
+    }
+
+    records.extend(current);
+    Ok(records)
+}
+
+/// 随源码提供的测试向量
+///
+/// 内容来自 `src/crypto/vectors/`，编译时嵌入，运行时不依赖源码目录。
+#[allow(dead_code)]
+pub fn builtin_vectors() -> Vec<VectorFile> {
+    let files = [
+        (
+            "aes256_ecb.rsp",
+            VectorKind::AesBlock,
+            include_str!("vectors/aes256_ecb.rsp"),
+        ),
+        (
+            "aes256_gcm.rsp",
+            VectorKind::AesGcm,
+            include_str!("vectors/aes256_gcm.rsp"),
+        ),
+        (
+            "sha256_short.rsp",
+            VectorKind::Sha256,
+            include_str!("vectors/sha256_short.rsp"),
+        ),
+        (
+            "sha256_monte.rsp",
+            VectorKind::Sha256Monte,
+            include_str!("vectors/sha256_monte.rsp"),
+        ),
+        (
+            "ed25519_rfc8032.txt",
+            VectorKind::Ed25519,
+            include_str!("vectors/ed25519_rfc8032.txt"),
+        ),
+    ];
+    files
+        .iter()
+        .map(|(name, kind, text)| {
+            VectorFile::parse(name, *kind, text).expect("built-in vector files are well-formed")
+        })
+        .collect()
+}
+
+/// 单个向量文件的检验结果
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SuiteResult {
+    /// 向量文件名
+    pub suite: String,
+    /// 通过的记录数
+    pub passed: usize,
+    /// 跳过的记录数（参数不受支持）
+    pub skipped: usize,
+    /// 失败记录的描述
+    pub failures: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl SuiteResult {
+    /// 是否没有失败记录
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// 单个后端的检验报告
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackendReport {
+    /// 后端名称
+    pub backend: String,
+    /// 后端创建失败时的错误信息
+    pub error: Option<String>,
+    /// 各向量文件的结果
+    pub suites: Vec<SuiteResult>,
+}
+
+#[allow(dead_code)]
+impl BackendReport {
+    /// 后端创建成功且所有向量文件都没有失败记录
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.suites.iter().all(SuiteResult::is_ok)
+    }
+}
+
+impl fmt::Display for BackendReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verdict = if self.passed() { "PASS" } else { "FAIL" };
+        writeln!(f, "{}: {}", self.backend, verdict)?;
+        if let Some(error) = &self.error {
+            writeln!(f, "  backend unavailable: {}", error)?;
+        }
+        for suite in &self.suites {
+            writeln!(
+                f,
+                "  {}: {} passed, {} skipped, {} failed",
+                suite.suite,
+                suite.passed,
+                suite.skipped,
+                suite.failures.len()
+            )?;
+            for failure in &suite.failures {
+                writeln!(f, "    {}", failure)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 单条记录的检验结论
+enum Outcome {
+    Passed,
+    Skipped,
+    /// 记录只为后续用例提供参数（如 Monte Carlo 种子），不计数
+    Setup,
+}
+
+/// 比较期望值与实际值
+fn expect_eq(what: &str, expected: &[u8], actual: &[u8]) -> Result<(), Box<dyn Error>> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(conformance_error(format!(
+            "{} mismatch: expected {}, got {}",
+            what,
+            encode_hex(expected),
+            encode_hex(actual)
+        )))
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn check_aes_block(
+    backend: &dyn RiscVCryptoExt,
+    record: &VectorRecord,
+) -> Result<Outcome, Box<dyn Error>> {
+    if record.bytes("KEY")?.len() != 32 {
+        return Ok(Outcome::Skipped);
+    }
+    let key: [u8; 32] = record.array("KEY")?;
+    let plaintext: [u8; 16] = record.array("PLAINTEXT")?;
+    let ciphertext: [u8; 16] = record.array("CIPHERTEXT")?;
+
+    let schedule = backend.copr_aes256_key_schedule(&key)?;
+    if record.get("DIRECTION").ok() == Some("DECRYPT") {
+        let output = backend.copr_aes256_decrypt_block(&key, &ciphertext)?;
+        expect_eq("plaintext", &plaintext, &output)?;
+        let output = schedule.decrypt_block(&ciphertext)?;
+        expect_eq("key schedule plaintext", &plaintext, &output)?;
+    } else {
+        let output = backend.copr_aes256_encrypt_block(&key, &plaintext)?;
+        expect_eq("ciphertext", &ciphertext, &output)?;
+        let output = schedule.encrypt_block(&plaintext)?;
+        expect_eq("key schedule ciphertext", &ciphertext, &output)?;
+    }
+    Ok(Outcome::Passed)
+}
+
+fn check_aes_gcm(
+    backend: &dyn RiscVCryptoExt,
+    record: &VectorRecord,
+) -> Result<Outcome, Box<dyn Error>> {
+    if record.bytes("KEY")?.len() != 32
+        || record.bytes("IV")?.len() != 12
+        || record.bytes("TAG")?.len() != 16
+    {
+        return Ok(Outcome::Skipped);
+    }
+    let key: [u8; 32] = record.array("KEY")?;
+    let nonce: [u8; 12] = record.array("IV")?;
+    let tag: [u8; 16] = record.array("TAG")?;
+    let aad = record.bytes("AAD")?;
+    let ciphertext = record.bytes("CT")?;
+
+    if record.has("FAIL") {
+        if backend
+            .copr_decrypt_aes256(&key, &nonce, &aad, &ciphertext, &tag)
+            .is_ok()
+        {
+            return Err(conformance_error("forged tag was accepted"));
+        }
+        return Ok(Outcome::Passed);
+    }
+
+    let plaintext = record.bytes("PT")?;
+    let (sealed, sealed_tag) = backend.copr_encrypt_aes256(&key, &nonce, &aad, &plaintext)?;
+    expect_eq("ciphertext", &ciphertext, &sealed)?;
+    expect_eq("tag", &tag, &sealed_tag)?;
+    let opened = backend.copr_decrypt_aes256(&key, &nonce, &aad, &ciphertext, &tag)?;
+    expect_eq("plaintext", &plaintext, &opened)?;
+    Ok(Outcome::Passed)
+}
+
+/// 只用后端的压缩函数计算 SHA-256，检验 `copr_sha256_compress`
+fn sha256_by_compression(
+    backend: &dyn RiscVCryptoExt,
+    message: &[u8],
+) -> Result<[u8; 32], Box<dyn Error>> {
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((message.len() as u64) * 8).to_be_bytes());
+
+    let mut state = SHA256_INITIAL_STATE;
+    for block in padded.chunks_exact(64) {
+        state = backend.copr_sha256_compress(&state, block.try_into()?)?;
+    }
+    Ok(state)
+}
+
+fn check_sha256(
+    backend: &dyn RiscVCryptoExt,
+    record: &VectorRecord,
+) -> Result<Outcome, Box<dyn Error>> {
+    let bits: usize = record
+        .get("LEN")?
+        .parse()
+        .map_err(|_| conformance_error(format!("line {}: invalid Len", record.line)))?;
+    if !bits.is_multiple_of(8) {
+        return Ok(Outcome::Skipped);
+    }
+    let mut message = record.bytes("MSG")?;
+    // Len = 0 时 Msg 为占位的 00
+    message.truncate(bits / 8);
+    let digest: [u8; 32] = record.array("MD")?;
+
+    expect_eq("digest", &digest, &backend.copr_hash_sha256(&message)?)?;
+    expect_eq(
+        "compression digest",
+        &digest,
+        &sha256_by_compression(backend, &message)?,
+    )?;
+    Ok(Outcome::Passed)
+}
+
+/// SHAVS Monte Carlo 的一轮：`MD[i] = SHA(MD[i-3] || MD[i-2] || MD[i-1])`，
+/// 前三个值都取种子，返回第 1000 次迭代的结果
+fn sha256_monte_round(
+    backend: &dyn RiscVCryptoExt,
+    seed: &[u8; 32],
+) -> Result<[u8; 32], Box<dyn Error>> {
+    let mut window = [*seed, *seed, *seed];
+    for _ in 0..MONTE_CARLO_ITERATIONS {
+        let message = window.concat();
+        let digest = backend.copr_hash_sha256(&message)?;
+        window = [window[1], window[2], digest];
+    }
+    Ok(window[2])
+}
+
+fn check_sha256_monte(
+    backend: &dyn RiscVCryptoExt,
+    record: &VectorRecord,
+    seed: &mut Option<[u8; 32]>,
+) -> Result<Outcome, Box<dyn Error>> {
+    if record.has("SEED") {
+        *seed = Some(record.array("SEED")?);
+        return Ok(Outcome::Setup);
+    }
+    let start = seed.ok_or_else(|| conformance_error("COUNT before Seed"))?;
+    let expected: [u8; 32] = record.array("MD")?;
+    // 下一轮从期望值出发，一轮失败不会牵连后续各轮
+    *seed = Some(expected);
+    expect_eq("digest", &expected, &sha256_monte_round(backend, &start)?)?;
+    Ok(Outcome::Passed)
+}
+
+fn check_ed25519(
+    backend: &dyn RiscVCryptoExt,
+    record: &VectorRecord,
+) -> Result<Outcome, Box<dyn Error>> {
+    let secret: [u8; 32] = record.array("SECRET KEY")?;
+    let public: [u8; 32] = record.array("PUBLIC KEY")?;
+    let message = record.bytes("MESSAGE")?;
+    let signature: [u8; 64] = record.array("SIGNATURE")?;
+
+    expect_eq(
+        "signature",
+        &signature,
+        &backend.copr_sign_ed25519(&secret, &message)?,
+    )?;
+    if !backend.copr_verify_ed25519(&public, &message, &signature)? {
+        return Err(conformance_error("valid signature rejected"));
+    }
+
+    let mut tampered = message.clone();
+    match tampered.first_mut() {
+        Some(byte) => *byte ^= 0x01,
+        None => tampered.push(0),
+    }
+    if backend.copr_verify_ed25519(&public, &tampered, &signature)? {
+        return Err(conformance_error("signature accepted for tampered message"));
+    }
+
+    let batch = [
+        (public, message.as_slice(), signature),
+        (public, tampered.as_slice(), signature),
+    ];
+    let results = backend.copr_verify_ed25519_batch(&batch)?;
+    if results != [true, false] {
+        return Err(conformance_error(format!(
+            "batch verification returned {:?}, expected [true, false]",
+            results
+        )));
+    }
+    Ok(Outcome::Passed)
+}
+
+/// 用一个后端检验一个向量文件
+///
+/// 后端返回错误或结果不符都记为该记录失败，不会中断其余记录。
+///
+/// # 参数
+///
+/// * `backend` - 被检验的后端
+/// * `file` - 解析后的向量文件
+#[allow(dead_code)]
+pub fn run_suite(backend: &dyn RiscVCryptoExt, file: &VectorFile) -> SuiteResult {
+    let mut result = SuiteResult {
+        suite: file.name.clone(),
+        ..SuiteResult::default()
+    };
+    let mut monte_seed: Option<[u8; 32]> = None;
+
+    for record in &file.records {
+        let outcome = match file.kind {
+            VectorKind::AesBlock => check_aes_block(backend, record),
+            VectorKind::AesGcm => check_aes_gcm(backend, record),
+            VectorKind::Sha256 => check_sha256(backend, record),
+            VectorKind::Ed25519 => check_ed25519(backend, record),
+            VectorKind::Sha256Monte => check_sha256_monte(backend, record, &mut monte_seed),
+        };
+
+        match outcome {
+            Ok(Outcome::Setup) => {}
+            Ok(Outcome::Passed) => result.passed += 1,
+            Ok(Outcome::Skipped) => result.skipped += 1,
+            Err(e) => result.failures.push(format!("{}: {}", record.label(), e)),
+        }
+    }
+    result
+}
+
+/// 用一个后端检验所有向量文件
+///
+/// # 参数
+///
+/// * `name` - 报告中使用的后端名称
+/// * `backend` - 被检验的后端
+/// * `files` - 向量文件
+#[allow(dead_code)]
+pub fn run_backend(
+    name: &str,
+    backend: &dyn RiscVCryptoExt,
+    files: &[VectorFile],
+) -> BackendReport {
+    BackendReport {
+        backend: name.to_string(),
+        error: None,
+        suites: files.iter().map(|file| run_suite(backend, file)).collect(),
+    }
+}
+
+/// 对注册表中的每个后端检验所有向量文件
+///
+/// 无法创建的后端在报告中记为失败，不影响其余后端。
+///
+/// # 参数
+///
+/// * `registry` - 后端注册表
+/// * `files` - 向量文件
+///
+/// # 返回
+///
+/// 按后端名称字典序排列的报告
+#[allow(dead_code)]
+pub fn run_registry(registry: &BackendRegistry, files: &[VectorFile]) -> Vec<BackendReport> {
+    registry
+        .names()
+        .into_iter()
+        .map(|name| match registry.create(&name) {
+            Ok(backend) => run_backend(&name, backend.as_ref(), files),
+            Err(e) => BackendReport {
+                backend: name,
+                error: Some(e.to_string()),
+                suites: Vec::new(),
+            },
+        })
+        .collect()
+}
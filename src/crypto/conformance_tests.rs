@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod crypto_conformance_tests {
+    use crate::crypto::backend::BackendRegistry;
+    use crate::crypto::conformance::{
+        VectorFile, VectorKind, builtin_vectors, run_backend, run_registry, run_suite,
+    };
+    use crate::crypto::device::{DeviceClient, DeviceServer};
+    use crate::crypto::mock::{MockAccelerator, MockOperation};
+    use crate::crypto::riscv_ext::{HardwareAccelerator, SoftwareBackend};
+    use crate::crypto::test_util::TempPath;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_parse_cavp_sections_and_fail_records() {
+        let text = "\
+# comment
+[Keylen = 256]
+[IVlen = 96]
+
+Count = 0
+Key = 00ff
+PT =
+FAIL
+
+[DECRYPT]
+
+COUNT = 1
+KEY = 01
+";
+        let file = VectorFile::parse("sample.rsp", VectorKind::AesGcm, text).unwrap();
+        assert_eq!(file.records.len(), 2);
+
+        let first = &file.records[0];
+        assert_eq!(first.line, 5);
+        assert_eq!(first.get("KEYLEN").unwrap(), "256");
+        assert_eq!(first.bytes("KEY").unwrap(), vec![0x00, 0xff]);
+        assert!(first.bytes("PT").unwrap().is_empty());
+        assert!(first.has("FAIL"));
+        assert!(!first.has("DIRECTION"));
+
+        // 节参数会累积，记录字段不会泄漏到下一条
+        let second = &file.records[1];
+        assert_eq!(second.get("DIRECTION").unwrap(), "DECRYPT");
+        assert_eq!(second.get("IVLEN").unwrap(), "96");
+        assert!(!second.has("FAIL"));
+        assert!(second.array::<2>("KEY").is_err());
+
+        assert!(VectorFile::parse("bad.rsp", VectorKind::AesBlock, "KEY: 00").is_err());
+    }
+
+    #[test]
+    fn test_parse_rfc8032_layout() {
+        let file = builtin_vectors()
+            .into_iter()
+            .find(|file| file.kind == VectorKind::Ed25519)
+            .unwrap();
+        assert_eq!(file.records.len(), 4);
+
+        let first = &file.records[0];
+        assert_eq!(first.get("TEST").unwrap(), "1");
+        assert_eq!(first.get("ALGORITHM").unwrap(), "Ed25519");
+        assert!(first.bytes("MESSAGE").unwrap().is_empty());
+        // 跨行的十六进制值拼接为一个字段
+        assert_eq!(first.array::<64>("SIGNATURE").unwrap()[..2], [0xe5, 0x56]);
+        assert_eq!(file.records[3].bytes("MESSAGE").unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_load_matches_builtin_vectors() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/crypto/vectors");
+        for builtin in builtin_vectors() {
+            let loaded = VectorFile::load(directory.join(&builtin.name), builtin.kind).unwrap();
+            assert_eq!(loaded, builtin);
+        }
+        assert!(VectorFile::load(directory.join("missing.rsp"), VectorKind::Sha256).is_err());
+    }
+
+    #[test]
+    fn test_every_registered_backend_passes() {
+        // `device` 后端指向在临时 socket 上运行的替身守护进程
+        let socket = TempPath::new("conformance.sock");
+        let server =
+            DeviceServer::bind_with_backend(socket.path(), Arc::new(SoftwareBackend)).unwrap();
+        thread::spawn(move || {
+            let _ = server.serve();
+        });
+
+        let registry = BackendRegistry::new();
+        let path = socket.path().to_path_buf();
+        registry.register("device", move || Ok(Box::new(DeviceClient::new(&path))));
+
+        let vectors = builtin_vectors();
+        let reports = run_registry(&registry, &vectors);
+        assert_eq!(reports.len(), registry.names().len());
+        for report in &reports {
+            assert!(report.passed(), "{}", report);
+            assert_eq!(report.suites.len(), vectors.len());
+            assert!(report.suites.iter().all(|suite| suite.passed > 0));
+        }
+
+        let path = socket.path().to_path_buf();
+        drop(socket);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_reports_wrong_answers_per_backend() {
+        let vectors = builtin_vectors();
+
+        // 输出被篡改的加速器：往返测试察觉不到单分组的错误，已知答案测试可以
+        let corrupt = MockAccelerator::new().corrupt_operation(MockOperation::Aes256EncryptBlock);
+        let report = run_backend("corrupt-aes", &corrupt, &vectors);
+        assert!(!report.passed());
+        let failed: Vec<_> = report
+            .suites
+            .iter()
+            .filter(|suite| !suite.is_ok())
+            .map(|suite| suite.suite.as_str())
+            .collect();
+        assert_eq!(failed, ["aes256_ecb.rsp"]);
+        assert_eq!(report.suites[0].failures.len(), 10);
+        assert!(report.suites[0].failures[0].contains("DIRECTION=ENCRYPT COUNT=0"));
+        assert!(report.to_string().starts_with("corrupt-aes: FAIL"));
+
+        // 后端报错同样记为失败
+        let mut disabled = HardwareAccelerator::new();
+        disabled.set_enabled(false);
+        let report = run_backend("disabled", &disabled, &vectors);
+        let sha = report
+            .suites
+            .iter()
+            .find(|suite| suite.suite == "sha256_short.rsp")
+            .unwrap();
+        assert_eq!(sha.passed, 0);
+        assert_eq!(sha.failures.len(), 6);
+
+        // 无法创建的后端出现在报告中
+        let registry = BackendRegistry::new();
+        registry.register("broken", || Err("no such device".into()));
+        let broken = run_registry(&registry, &vectors)
+            .into_iter()
+            .find(|report| report.backend == "broken")
+            .unwrap();
+        assert!(!broken.passed());
+        assert!(broken.to_string().contains("no such device"));
+    }
+
+    #[test]
+    fn test_unsupported_parameters_are_skipped() {
+        let text = "\
+[ENCRYPT]
+
+COUNT = 0
+KEY = 000102030405060708090a0b0c0d0e0f
+PLAINTEXT = 00112233445566778899aabbccddeeff
+CIPHERTEXT = 69c4e0d86a7b0430d8cdb78070b4c55a
+";
+        let file = VectorFile::parse("ECBVarKey128.rsp", VectorKind::AesBlock, text).unwrap();
+        let result = run_suite(&SoftwareBackend, &file);
+        assert_eq!((result.passed, result.skipped), (0, 1));
+        assert!(result.is_ok());
+
+        let text = "[L = 32]\n\nLen = 5\nMsg = 48\nMD = 00\n";
+        let file = VectorFile::parse("SHA256ShortMsg.rsp", VectorKind::Sha256, text).unwrap();
+        assert_eq!(run_suite(&SoftwareBackend, &file).skipped, 1);
+    }
+}
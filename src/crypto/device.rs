@@ -0,0 +1,1046 @@
+//! 加密协处理器设备协议
+//!
+//! 定义把 `RiscVCryptoExt` 运算卸载到协处理器的请求/响应线协议，并提供两端实现：
+//!
+//! - `DeviceClient`：通过 Unix socket 或字符设备路径（如 `/dev/crypto0`）发送请求的后端
+//! - `DeviceServer`：在用户态用软件实现协议的替身守护进程，用于在普通 Linux 主机上
+//!   端到端开发驱动集成
+//!
+//! # 帧格式
+//!
+//! 请求和响应使用相同的 16 字节帧头，所有整数为小端序：
+//!
+//! ```text
+//! 偏移  长度  字段
+//! 0     4     magic = "ZKCD"
+//! 4     1     version = 1
+//! 5     1     code：请求为操作码（Opcode），响应为状态码（Status）
+//! 6     2     保留，必须为 0
+//! 8     4     request_id：响应原样返回请求的值
+//! 12    4     payload_len：负载字节数，不超过 MAX_PAYLOAD
+//! 16    ...   payload：若干字段，每个字段为 u32 长度 + 数据
+//! ```
+//!
+//! 每个操作码的字段与 `RiscVCryptoExt` 对应方法的参数顺序一致，
+//! 布尔结果编码为 1 字节字段。MSM 请求的第一个字段是 u32 点数 n，
+//! 随后是 n 个点和若干标量。批量验证请求按 (公钥, 消息, 签名) 三个一组排列字段，
+//! 响应为一个字段，每个签名占 1 字节结果。状态不为 `Ok` 时，响应的第一个字段为 UTF-8 错误信息。
+//!
+//! `copr_aes256_key_schedule` 没有对应的操作码：设备端不保存密钥状态，
+//! 返回的分组变换把密钥随每个分组请求发送。
+//!
+//! 字符设备路径上，每次 `write` 一个完整的请求帧，随后 `read` 对应的响应帧。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::device::{DeviceClient, DeviceServer};
+//! use crypto::riscv_ext::RiscVCryptoExt;
+//!
+//! let server = DeviceServer::bind("/tmp/zk-crypto0.sock").unwrap();
+//! std::thread::spawn(move || server.serve().unwrap());
+//!
+//! let client = DeviceClient::new("/tmp/zk-crypto0.sock");
+//! let digest = client.copr_hash_sha256(b"abc").unwrap();
+//! ```
+
+use crate::crypto::riscv_ext::{Aes256KeySchedule, RiscVCryptoExt, SoftwareBackend};
+use crate::crypto::secret::Aes256Key;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// 帧魔数
+pub const MAGIC: [u8; 4] = *b"ZKCD";
+/// 协议版本
+pub const PROTOCOL_VERSION: u8 = 1;
+/// 帧头长度
+pub const HEADER_LEN: usize = 16;
+/// 单帧负载上限（16 MiB）
+pub const MAX_PAYLOAD: usize = 16 * 1024 * 1024;
+/// `DeviceServer` 接受连接失败后再次尝试前的等待时间，避免持续失败时空转
+pub const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// 设备协议中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct DeviceError {
+    message: String,
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Crypto Device Error: {}", self.message)
+    }
+}
+
+impl Error for DeviceError {}
+
+fn device_error(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(DeviceError {
+        message: message.into(),
+    })
+}
+
+/// 请求操作码，与 `RiscVCryptoExt` 方法一一对应
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Opcode {
+    /// `copr_encrypt_aes256`：key, nonce, aad, plaintext → ciphertext, tag
+    EncryptAes256 = 0x01,
+    /// `copr_decrypt_aes256`：key, nonce, aad, ciphertext, tag → plaintext
+    DecryptAes256 = 0x02,
+    /// `copr_aes256_encrypt_block`：key, block → block
+    Aes256EncryptBlock = 0x03,
+    /// `copr_aes256_decrypt_block`：key, block → block
+    Aes256DecryptBlock = 0x04,
+    /// `copr_hash_sha256`：input → digest
+    HashSha256 = 0x10,
+    /// `copr_sha256_compress`：state, block → state
+    Sha256Compress = 0x11,
+    /// `copr_keccak_f1600`：state → state
+    KeccakF1600 = 0x12,
+    /// `copr_ec_mul`：scalar, point → point
+    EcMul = 0x20,
+    /// `copr_ec_add`：point1, point2 → point
+    EcAdd = 0x21,
+    /// `copr_sign_ed25519`：secret, message → signature
+    SignEd25519 = 0x22,
+    /// `copr_verify_ed25519`：public, message, signature → bool
+    VerifyEd25519 = 0x23,
+    /// `copr_verify_ed25519_batch`：(public, message, signature) × n → 每项 1 字节结果
+    VerifyEd25519Batch = 0x24,
+    /// `copr_zkp_prove`：witness → proof
+    ZkpProve = 0x30,
+    /// `copr_zkp_verify`：proof, public_input → bool
+    ZkpVerify = 0x31,
+    /// `copr_msm_accelerate`：n, points[n], scalars → result
+    Msm = 0x32,
+}
+
+impl Opcode {
+    /// 从帧中的操作码字节解析
+    ///
+    /// # 返回
+    ///
+    /// 未知操作码返回 `None`
+    pub fn from_u8(code: u8) -> Option<Self> {
+        let opcode = match code {
+            0x01 => Opcode::EncryptAes256,
+            0x02 => Opcode::DecryptAes256,
+            0x03 => Opcode::Aes256EncryptBlock,
+            0x04 => Opcode::Aes256DecryptBlock,
+            0x10 => Opcode::HashSha256,
+            0x11 => Opcode::Sha256Compress,
+            0x12 => Opcode::KeccakF1600,
+            0x20 => Opcode::EcMul,
+            0x21 => Opcode::EcAdd,
+            0x22 => Opcode::SignEd25519,
+            0x23 => Opcode::VerifyEd25519,
+            0x24 => Opcode::VerifyEd25519Batch,
+            0x30 => Opcode::ZkpProve,
+            0x31 => Opcode::ZkpVerify,
+            0x32 => Opcode::Msm,
+            _ => return None,
+        };
+        Some(opcode)
+    }
+}
+
+/// 响应状态码
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Status {
+    /// 成功，负载为运算结果
+    Ok = 0x00,
+    /// 运算失败（如 GCM 认证失败）
+    Failed = 0x01,
+    /// 设备不支持该操作码
+    Unsupported = 0x02,
+    /// 请求字段数量或长度不符合协议
+    Malformed = 0x03,
+}
+
+impl Status {
+    /// 从帧中的状态码字节解析
+    ///
+    /// # 返回
+    ///
+    /// 未知状态码返回 `None`
+    pub fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0x00 => Some(Status::Ok),
+            0x01 => Some(Status::Failed),
+            0x02 => Some(Status::Unsupported),
+            0x03 => Some(Status::Malformed),
+            _ => None,
+        }
+    }
+}
+
+/// 协议帧
+///
+/// 请求和响应共用此结构，`code` 分别解释为 `Opcode` 或 `Status`。
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// 操作码或状态码
+    pub code: u8,
+    /// 请求序号
+    pub request_id: u32,
+    /// 负载字段
+    pub fields: Vec<Vec<u8>>,
+}
+
+#[allow(dead_code)]
+impl Frame {
+    /// 编码为线上字节
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 帧头和负载
+    /// - `Err(Box<dyn Error>)`: 负载超过 `MAX_PAYLOAD`
+    pub fn encode(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let payload_len: usize = self.fields.iter().map(|field| 4 + field.len()).sum();
+        if payload_len > MAX_PAYLOAD {
+            return Err(device_error(format!(
+                "Payload of {} bytes exceeds limit of {}",
+                payload_len, MAX_PAYLOAD
+            )));
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload_len);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(PROTOCOL_VERSION);
+        bytes.push(self.code);
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&self.request_id.to_le_bytes());
+        bytes.extend_from_slice(&(payload_len as u32).to_le_bytes());
+        for field in &self.fields {
+            bytes.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        Ok(bytes)
+    }
+
+    /// 从字节流读取一帧
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Some(Frame))`: 读到完整的帧
+    /// - `Ok(None)`: 帧开始前对端关闭了连接
+    /// - `Err(Box<dyn Error>)`: I/O 错误或帧格式错误
+    pub fn read_from<R: Read + ?Sized>(reader: &mut R) -> Result<Option<Self>, Box<dyn Error>> {
+        let mut header = [0u8; HEADER_LEN];
+        match reader.read_exact(&mut header[..1]) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        reader.read_exact(&mut header[1..])?;
+
+        if header[0..4] != MAGIC {
+            return Err(device_error("Bad frame magic"));
+        }
+        if header[4] != PROTOCOL_VERSION {
+            return Err(device_error(format!(
+                "Unsupported protocol version {}",
+                header[4]
+            )));
+        }
+        if header[6..8] != [0, 0] {
+            return Err(device_error("Reserved header bytes must be zero"));
+        }
+        let code = header[5];
+        let request_id = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let payload_len = u32::from_le_bytes(header[12..16].try_into().unwrap()) as usize;
+        if payload_len > MAX_PAYLOAD {
+            return Err(device_error(format!(
+                "Payload of {} bytes exceeds limit of {}",
+                payload_len, MAX_PAYLOAD
+            )));
+        }
+
+        let mut payload = vec![0u8; payload_len];
+        reader.read_exact(&mut payload)?;
+
+        let mut fields = Vec::new();
+        let mut rest = payload.as_slice();
+        while !rest.is_empty() {
+            if rest.len() < 4 {
+                return Err(device_error("Truncated field length"));
+            }
+            let len = u32::from_le_bytes(rest[..4].try_into().unwrap()) as usize;
+            rest = &rest[4..];
+            if rest.len() < len {
+                return Err(device_error("Field extends past end of payload"));
+            }
+            fields.push(rest[..len].to_vec());
+            rest = &rest[len..];
+        }
+
+        Ok(Some(Frame {
+            code,
+            request_id,
+            fields,
+        }))
+    }
+}
+
+/// 字段转换为定长数组
+fn fixed<const N: usize>(field: &[u8], name: &str) -> Result<[u8; N], Box<dyn Error>> {
+    field.try_into().map_err(|_| {
+        device_error(format!(
+            "Field '{}' must be {} bytes, got {}",
+            name,
+            N,
+            field.len()
+        ))
+    })
+}
+
+/// 检查字段数量
+fn expect_fields(fields: &[Vec<u8>], count: usize) -> Result<(), Box<dyn Error>> {
+    if fields.len() != count {
+        return Err(device_error(format!(
+            "Expected {} fields, got {}",
+            count,
+            fields.len()
+        )));
+    }
+    Ok(())
+}
+
+fn bool_field(field: &[u8]) -> Result<bool, Box<dyn Error>> {
+    match field {
+        [0] => Ok(false),
+        [1] => Ok(true),
+        _ => Err(device_error("Boolean field must be a single 0 or 1 byte")),
+    }
+}
+
+/// 设备传输通道
+enum Transport {
+    Socket(UnixStream),
+    Device(File),
+}
+
+impl Transport {
+    fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let metadata = fs::metadata(path)
+            .map_err(|e| device_error(format!("Cannot open device {}: {}", path.display(), e)))?;
+        if metadata.file_type().is_socket() {
+            Ok(Transport::Socket(UnixStream::connect(path)?))
+        } else {
+            Ok(Transport::Device(
+                OpenOptions::new().read(true).write(true).open(path)?,
+            ))
+        }
+    }
+
+    fn stream(&mut self) -> &mut dyn ReadWrite {
+        match self {
+            Transport::Socket(stream) => stream,
+            Transport::Device(file) => file,
+        }
+    }
+}
+
+trait ReadWrite: Read + Write {}
+
+impl<T: Read + Write> ReadWrite for T {}
+
+/// 通过设备协议卸载运算的后端
+///
+/// 首次调用时才打开设备，因此创建本身不会失败；I/O 出错后丢弃连接，
+/// 下一次调用重新打开。同一时刻只有一个请求在途。
+/// 克隆的实例共享同一条连接。
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct DeviceClient {
+    shared: Arc<DeviceConnection>,
+}
+
+/// `DeviceClient` 各克隆共享的连接状态
+struct DeviceConnection {
+    path: PathBuf,
+    transport: Mutex<Option<Transport>>,
+    next_request_id: AtomicU32,
+}
+
+impl fmt::Debug for DeviceClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeviceClient")
+            .field("path", &self.shared.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(dead_code)]
+impl DeviceClient {
+    /// 创建设备客户端
+    ///
+    /// # 参数
+    ///
+    /// * `path` - Unix socket 或字符设备路径
+    ///
+    /// # 返回
+    ///
+    /// 尚未连接的 `DeviceClient`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        DeviceClient {
+            shared: Arc::new(DeviceConnection {
+                path: path.into(),
+                transport: Mutex::new(None),
+                next_request_id: AtomicU32::new(1),
+            }),
+        }
+    }
+
+    /// 设备路径
+    pub fn path(&self) -> &Path {
+        &self.shared.path
+    }
+
+    /// 发送一个请求并等待响应
+    ///
+    /// # 参数
+    ///
+    /// * `opcode` - 操作码
+    /// * `fields` - 请求字段
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<Vec<u8>>)`: 响应字段
+    /// - `Err(Box<dyn Error>)`: 传输错误、协议错误或设备返回的错误状态
+    pub fn call(
+        &self,
+        opcode: Opcode,
+        fields: Vec<Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+        let request = Frame {
+            code: opcode as u8,
+            request_id: self.shared.next_request_id.fetch_add(1, Ordering::Relaxed),
+            fields,
+        };
+        let bytes = request.encode()?;
+
+        let mut guard = self
+            .shared
+            .transport
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if guard.is_none() {
+            *guard = Some(Transport::open(&self.shared.path)?);
+        }
+        let stream = guard.as_mut().unwrap().stream();
+
+        let exchange = stream
+            .write_all(&bytes)
+            .map_err(Box::<dyn Error>::from)
+            .and_then(|()| Frame::read_from(stream));
+        let response = match exchange {
+            // 响应序号不符说明流已错位，丢弃连接，下次调用重新打开
+            Ok(Some(response)) if response.request_id != request.request_id => {
+                *guard = None;
+                return Err(device_error(format!(
+                    "Response id {} does not match request id {}",
+                    response.request_id, request.request_id
+                )));
+            }
+            Ok(Some(response)) => response,
+            Ok(None) => {
+                *guard = None;
+                return Err(device_error("Device closed the connection"));
+            }
+            Err(e) => {
+                *guard = None;
+                return Err(e);
+            }
+        };
+        drop(guard);
+
+        match Status::from_u8(response.code) {
+            Some(Status::Ok) => Ok(response.fields),
+            Some(status) => {
+                let message = response
+                    .fields
+                    .first()
+                    .map(|field| String::from_utf8_lossy(field).into_owned())
+                    .unwrap_or_default();
+                Err(device_error(format!("{:?}: {}", status, message)))
+            }
+            None => Err(device_error(format!(
+                "Unknown response status {:#04x}",
+                response.code
+            ))),
+        }
+    }
+
+    /// 发送请求并检查响应字段数量
+    fn call_expect(
+        &self,
+        opcode: Opcode,
+        fields: Vec<Vec<u8>>,
+        count: usize,
+    ) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+        let fields = self.call(opcode, fields)?;
+        expect_fields(&fields, count)?;
+        Ok(fields)
+    }
+}
+
+/// `DeviceClient` 的密钥调度
+///
+/// 协议不在设备端保存密钥状态，密钥随每个分组请求发送，由设备展开。
+struct DeviceKeySchedule {
+    client: DeviceClient,
+    key: Aes256Key,
+}
+
+impl Aes256KeySchedule for DeviceKeySchedule {
+    fn encrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        self.client
+            .copr_aes256_encrypt_block(self.key.expose_secret(), block)
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        self.client
+            .copr_aes256_decrypt_block(self.key.expose_secret(), block)
+    }
+}
+
+impl RiscVCryptoExt for DeviceClient {
+    fn copr_encrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        let mut fields = self.call_expect(
+            Opcode::EncryptAes256,
+            vec![
+                key.to_vec(),
+                nonce.to_vec(),
+                aad.to_vec(),
+                plaintext.to_vec(),
+            ],
+            2,
+        )?;
+        let tag = fixed(&fields[1], "tag")?;
+        Ok((fields.swap_remove(0), tag))
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut fields = self.call_expect(
+            Opcode::DecryptAes256,
+            vec![
+                key.to_vec(),
+                nonce.to_vec(),
+                aad.to_vec(),
+                ciphertext.to_vec(),
+                tag.to_vec(),
+            ],
+            1,
+        )?;
+        Ok(fields.swap_remove(0))
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        let fields = self.call_expect(
+            Opcode::Aes256EncryptBlock,
+            vec![key.to_vec(), block.to_vec()],
+            1,
+        )?;
+        fixed(&fields[0], "block")
+    }
+
+    fn copr_aes256_decrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        let fields = self.call_expect(
+            Opcode::Aes256DecryptBlock,
+            vec![key.to_vec(), block.to_vec()],
+            1,
+        )?;
+        fixed(&fields[0], "block")
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        Ok(Box::new(DeviceKeySchedule {
+            client: self.clone(),
+            key: Aes256Key::new(*key),
+        }))
+    }
+
+    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        let fields = self.call_expect(Opcode::HashSha256, vec![input.to_vec()], 1)?;
+        fixed(&fields[0], "digest")
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        let fields = self.call_expect(
+            Opcode::Sha256Compress,
+            vec![state.to_vec(), block.to_vec()],
+            1,
+        )?;
+        fixed(&fields[0], "state")
+    }
+
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        let fields = self.call_expect(Opcode::KeccakF1600, vec![state.to_vec()], 1)?;
+        fixed(&fields[0], "state")
+    }
+
+    fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>> {
+        let fields = self.call_expect(Opcode::EcMul, vec![scalar.to_vec(), point.to_vec()], 1)?;
+        fixed(&fields[0], "point")
+    }
+
+    fn copr_ec_add(
+        &self,
+        point1: &[u8; 32],
+        point2: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        let fields = self.call_expect(Opcode::EcAdd, vec![point1.to_vec(), point2.to_vec()], 1)?;
+        fixed(&fields[0], "point")
+    }
+
+    fn copr_sign_ed25519(
+        &self,
+        secret: &[u8; 32],
+        message: &[u8],
+    ) -> Result<[u8; 64], Box<dyn Error>> {
+        let fields = self.call_expect(
+            Opcode::SignEd25519,
+            vec![secret.to_vec(), message.to_vec()],
+            1,
+        )?;
+        fixed(&fields[0], "signature")
+    }
+
+    fn copr_verify_ed25519(
+        &self,
+        public: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, Box<dyn Error>> {
+        let fields = self.call_expect(
+            Opcode::VerifyEd25519,
+            vec![public.to_vec(), message.to_vec(), signature.to_vec()],
+            1,
+        )?;
+        bool_field(&fields[0])
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        let mut fields = Vec::with_capacity(3 * items.len());
+        for (public, message, signature) in items {
+            fields.push(public.to_vec());
+            fields.push(message.to_vec());
+            fields.push(signature.to_vec());
+        }
+
+        let fields = self.call_expect(Opcode::VerifyEd25519Batch, fields, 1)?;
+        if fields[0].len() != items.len() {
+            return Err(device_error(format!(
+                "Expected {} batch results, got {}",
+                items.len(),
+                fields[0].len()
+            )));
+        }
+        fields[0].chunks(1).map(bool_field).collect()
+    }
+
+    fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut fields = self.call_expect(Opcode::ZkpProve, vec![witness.to_vec()], 1)?;
+        Ok(fields.swap_remove(0))
+    }
+
+    fn copr_zkp_verify(&self, proof: &[u8], public_input: &[u8]) -> Result<bool, Box<dyn Error>> {
+        let fields = self.call_expect(
+            Opcode::ZkpVerify,
+            vec![proof.to_vec(), public_input.to_vec()],
+            1,
+        )?;
+        bool_field(&fields[0])
+    }
+
+    fn copr_msm_accelerate(
+        &self,
+        points: &[Vec<u8>],
+        scalars: &[Vec<u8>],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut fields = Vec::with_capacity(1 + points.len() + scalars.len());
+        fields.push((points.len() as u32).to_le_bytes().to_vec());
+        fields.extend(points.iter().cloned());
+        fields.extend(scalars.iter().cloned());
+
+        let mut fields = self.call_expect(Opcode::Msm, fields, 1)?;
+        Ok(fields.swap_remove(0))
+    }
+}
+
+/// 请求处理失败的原因，决定响应状态码
+enum DispatchError {
+    Malformed(Box<dyn Error>),
+    Failed(Box<dyn Error>),
+}
+
+/// 在后端上执行一个请求
+///
+/// # 参数
+///
+/// * `backend` - 执行运算的后端
+/// * `opcode` - 操作码
+/// * `fields` - 请求字段
+fn dispatch(
+    backend: &dyn RiscVCryptoExt,
+    opcode: Opcode,
+    fields: &[Vec<u8>],
+) -> Result<Vec<Vec<u8>>, DispatchError> {
+    use DispatchError::{Failed, Malformed};
+
+    let arity = match opcode {
+        Opcode::EncryptAes256 => Some(4),
+        Opcode::DecryptAes256 => Some(5),
+        Opcode::VerifyEd25519 => Some(3),
+        Opcode::HashSha256 | Opcode::KeccakF1600 | Opcode::ZkpProve => Some(1),
+        Opcode::Msm | Opcode::VerifyEd25519Batch => None,
+        _ => Some(2),
+    };
+    if let Some(count) = arity {
+        expect_fields(fields, count).map_err(Malformed)?;
+    }
+
+    let output = match opcode {
+        Opcode::EncryptAes256 => {
+            let key = fixed(&fields[0], "key").map_err(Malformed)?;
+            let nonce = fixed(&fields[1], "nonce").map_err(Malformed)?;
+            let (ciphertext, tag) = backend
+                .copr_encrypt_aes256(&key, &nonce, &fields[2], &fields[3])
+                .map_err(Failed)?;
+            vec![ciphertext, tag.to_vec()]
+        }
+        Opcode::DecryptAes256 => {
+            let key = fixed(&fields[0], "key").map_err(Malformed)?;
+            let nonce = fixed(&fields[1], "nonce").map_err(Malformed)?;
+            let tag = fixed(&fields[4], "tag").map_err(Malformed)?;
+            vec![
+                backend
+                    .copr_decrypt_aes256(&key, &nonce, &fields[2], &fields[3], &tag)
+                    .map_err(Failed)?,
+            ]
+        }
+        Opcode::Aes256EncryptBlock | Opcode::Aes256DecryptBlock => {
+            let key = fixed(&fields[0], "key").map_err(Malformed)?;
+            let block = fixed(&fields[1], "block").map_err(Malformed)?;
+            let output = if opcode == Opcode::Aes256EncryptBlock {
+                backend.copr_aes256_encrypt_block(&key, &block)
+            } else {
+                backend.copr_aes256_decrypt_block(&key, &block)
+            };
+            vec![output.map_err(Failed)?.to_vec()]
+        }
+        Opcode::HashSha256 => vec![
+            backend
+                .copr_hash_sha256(&fields[0])
+                .map_err(Failed)?
+                .to_vec(),
+        ],
+        Opcode::Sha256Compress => {
+            let state = fixed(&fields[0], "state").map_err(Malformed)?;
+            let block = fixed(&fields[1], "block").map_err(Malformed)?;
+            vec![
+                backend
+                    .copr_sha256_compress(&state, &block)
+                    .map_err(Failed)?
+                    .to_vec(),
+            ]
+        }
+        Opcode::KeccakF1600 => {
+            let state = fixed(&fields[0], "state").map_err(Malformed)?;
+            vec![backend.copr_keccak_f1600(&state).map_err(Failed)?.to_vec()]
+        }
+        Opcode::EcMul => {
+            let scalar = fixed(&fields[0], "scalar").map_err(Malformed)?;
+            let point = fixed(&fields[1], "point").map_err(Malformed)?;
+            vec![
+                backend
+                    .copr_ec_mul(&scalar, &point)
+                    .map_err(Failed)?
+                    .to_vec(),
+            ]
+        }
+        Opcode::EcAdd => {
+            let point1 = fixed(&fields[0], "point1").map_err(Malformed)?;
+            let point2 = fixed(&fields[1], "point2").map_err(Malformed)?;
+            vec![
+                backend
+                    .copr_ec_add(&point1, &point2)
+                    .map_err(Failed)?
+                    .to_vec(),
+            ]
+        }
+        Opcode::SignEd25519 => {
+            let secret = fixed(&fields[0], "secret").map_err(Malformed)?;
+            vec![
+                backend
+                    .copr_sign_ed25519(&secret, &fields[1])
+                    .map_err(Failed)?
+                    .to_vec(),
+            ]
+        }
+        Opcode::VerifyEd25519 => {
+            let public = fixed(&fields[0], "public").map_err(Malformed)?;
+            let signature = fixed(&fields[2], "signature").map_err(Malformed)?;
+            let valid = backend
+                .copr_verify_ed25519(&public, &fields[1], &signature)
+                .map_err(Failed)?;
+            vec![vec![valid as u8]]
+        }
+        Opcode::VerifyEd25519Batch => {
+            if !fields.len().is_multiple_of(3) {
+                return Err(Malformed(device_error(
+                    "Batch fields must come in (public, message, signature) triples",
+                )));
+            }
+            let items = fields
+                .chunks(3)
+                .map(|item| {
+                    Ok((
+                        fixed(&item[0], "public")?,
+                        item[1].as_slice(),
+                        fixed(&item[2], "signature")?,
+                    ))
+                })
+                .collect::<Result<Vec<_>, Box<dyn Error>>>()
+                .map_err(Malformed)?;
+            let valid = backend.copr_verify_ed25519_batch(&items).map_err(Failed)?;
+            vec![valid.into_iter().map(u8::from).collect()]
+        }
+        Opcode::ZkpProve => vec![backend.copr_zkp_prove(&fields[0]).map_err(Failed)?],
+        Opcode::ZkpVerify => {
+            let valid = backend
+                .copr_zkp_verify(&fields[0], &fields[1])
+                .map_err(Failed)?;
+            vec![vec![valid as u8]]
+        }
+        Opcode::Msm => {
+            let count = fields
+                .first()
+                .ok_or_else(|| device_error("Missing point count"))
+                .and_then(|field| fixed::<4>(field, "count"))
+                .map_err(Malformed)?;
+            let count = u32::from_le_bytes(count) as usize;
+            if fields.len() - 1 < count {
+                return Err(Malformed(device_error("Point count exceeds field count")));
+            }
+            let (points, scalars) = fields[1..].split_at(count);
+            vec![
+                backend
+                    .copr_msm_accelerate(points, scalars)
+                    .map_err(Failed)?,
+            ]
+        }
+    };
+    Ok(output)
+}
+
+/// 处理一个请求帧，返回响应帧
+///
+/// # 参数
+///
+/// * `backend` - 执行运算的后端
+/// * `request` - 请求帧
+///
+/// # 返回
+///
+/// 与请求序号相同的响应帧；未知操作码、字段错误和运算失败都编码为对应状态码
+pub fn handle_request(backend: &dyn RiscVCryptoExt, request: &Frame) -> Frame {
+    let (status, fields) = match Opcode::from_u8(request.code) {
+        None => (
+            Status::Unsupported,
+            vec![format!("Unknown opcode {:#04x}", request.code).into_bytes()],
+        ),
+        Some(opcode) => match dispatch(backend, opcode, &request.fields) {
+            Ok(fields) => (Status::Ok, fields),
+            Err(DispatchError::Malformed(e)) => {
+                (Status::Malformed, vec![e.to_string().into_bytes()])
+            }
+            Err(DispatchError::Failed(e)) => (Status::Failed, vec![e.to_string().into_bytes()]),
+        },
+    };
+
+    Frame {
+        code: status as u8,
+        request_id: request.request_id,
+        fields,
+    }
+}
+
+/// 在一条连接上循环处理请求，直到对端关闭
+///
+/// # 参数
+///
+/// * `stream` - 双向字节流
+/// * `backend` - 执行运算的后端
+///
+/// # 返回
+///
+/// - `Ok(())`: 对端正常关闭
+/// - `Err(Box<dyn Error>)`: I/O 错误或收到无法解析的帧（连接随即关闭）
+pub fn serve_stream<S: Read + Write + ?Sized>(
+    stream: &mut S,
+    backend: &dyn RiscVCryptoExt,
+) -> Result<(), Box<dyn Error>> {
+    while let Some(request) = Frame::read_from(stream)? {
+        let response = handle_request(backend, &request);
+        stream.write_all(&response.encode()?)?;
+        stream.flush()?;
+    }
+    Ok(())
+}
+
+/// 用户态替身守护进程
+///
+/// 在 Unix socket 上实现设备协议，每个连接一个线程，
+/// 运算委托给指定后端（默认 `SoftwareBackend`）。
+#[allow(dead_code)]
+pub struct DeviceServer {
+    listener: UnixListener,
+    path: PathBuf,
+    backend: Arc<dyn RiscVCryptoExt>,
+}
+
+#[allow(dead_code)]
+impl DeviceServer {
+    /// 在 `path` 上监听，使用 `SoftwareBackend` 执行运算
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(DeviceServer)`: 监听成功
+    /// - `Err(Box<dyn Error>)`: 路径已被非 socket 文件占用或绑定失败
+    pub fn bind(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        Self::bind_with_backend(path, Arc::new(SoftwareBackend))
+    }
+
+    /// 在 `path` 上监听，使用指定后端执行运算
+    ///
+    /// 路径上遗留的 socket 文件会被删除；其他类型的文件不会被覆盖。
+    ///
+    /// # 参数
+    ///
+    /// * `path` - Unix socket 路径
+    /// * `backend` - 执行运算的后端
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(DeviceServer)`: 监听成功
+    /// - `Err(Box<dyn Error>)`: 路径已被非 socket 文件占用或绑定失败
+    pub fn bind_with_backend(
+        path: impl AsRef<Path>,
+        backend: Arc<dyn RiscVCryptoExt>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        if let Ok(metadata) = fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                return Err(device_error(format!(
+                    "{} exists and is not a socket",
+                    path.display()
+                )));
+            }
+            fs::remove_file(path)?;
+        }
+
+        Ok(DeviceServer {
+            listener: UnixListener::bind(path)?,
+            path: path.to_path_buf(),
+            backend,
+        })
+    }
+
+    /// 监听路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 接受连接并处理请求
+    ///
+    /// 单个连接的错误只关闭该连接，不做报告；需要记录时使用 `serve_with`。
+    pub fn serve(self) -> Result<(), Box<dyn Error>> {
+        self.serve_with(|_| {})
+    }
+
+    /// 接受连接并处理请求，单个连接出错时调用 `on_connection_error`
+    ///
+    /// 连接错误的回调在处理该连接的线程中执行，随后关闭该连接。
+    /// 接受连接失败（如文件描述符暂时耗尽）同样报告给回调，等待 `ACCEPT_RETRY_DELAY`
+    /// 后继续监听，已建立的连接不受影响。
+    ///
+    /// # 参数
+    ///
+    /// * `on_connection_error` - 连接错误回调，如写入日志
+    ///
+    /// # 返回
+    ///
+    /// 监听 socket 没有结束状态，正常运行时不返回
+    pub fn serve_with<F>(self, on_connection_error: F) -> Result<(), Box<dyn Error>>
+    where
+        F: Fn(&dyn Error) + Send + Sync + 'static,
+    {
+        let on_connection_error = Arc::new(on_connection_error);
+        for stream in self.listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    on_connection_error(&e);
+                    thread::sleep(ACCEPT_RETRY_DELAY);
+                    continue;
+                }
+            };
+            let backend = Arc::clone(&self.backend);
+            let on_connection_error = Arc::clone(&on_connection_error);
+            thread::spawn(move || {
+                if let Err(e) = serve_stream(&mut stream, backend.as_ref()) {
+                    on_connection_error(e.as_ref());
+                }
+            });
+        }
+        Ok(())
+    }
+}
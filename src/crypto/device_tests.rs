@@ -0,0 +1,272 @@
+#[cfg(test)]
+mod crypto_device_tests {
+    use crate::crypto::aes::Aes256Riscv;
+    use crate::crypto::device::{
+        DeviceClient, DeviceServer, Frame, HEADER_LEN, MAGIC, Opcode, Status, handle_request,
+    };
+    use crate::crypto::ec::Ed25519Riscv;
+    use crate::crypto::mock::{MockAccelerator, MockOperation};
+    use crate::crypto::riscv_ext::{HardwareAccelerator, RiscVCryptoExt, SoftwareBackend};
+    use crate::crypto::secret::Aes256Key;
+    use crate::crypto::test_util::TempPath;
+    use std::io::{Read, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// 在临时 socket 上启动替身守护进程，返回的路径离开作用域时删除 socket
+    fn spawn_daemon(name: &str, backend: Arc<dyn RiscVCryptoExt>) -> TempPath {
+        let socket = TempPath::new(&format!("{}.sock", name));
+        let server = DeviceServer::bind_with_backend(socket.path(), backend).unwrap();
+        thread::spawn(move || {
+            let _ = server.serve();
+        });
+        socket
+    }
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let frame = Frame {
+            code: Opcode::EncryptAes256 as u8,
+            request_id: 0xdead_beef,
+            fields: vec![vec![1, 2, 3], vec![], vec![9u8; 300]],
+        };
+        let bytes = frame.encode().unwrap();
+
+        assert_eq!(&bytes[..4], &MAGIC);
+        assert_eq!(bytes.len(), HEADER_LEN + 3 * 4 + 3 + 300);
+        assert_eq!(
+            Frame::read_from(&mut bytes.as_slice()).unwrap(),
+            Some(frame)
+        );
+        assert_eq!(Frame::read_from(&mut [].as_slice()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_frame_rejects_malformed_input() {
+        let frame = Frame {
+            code: Opcode::HashSha256 as u8,
+            request_id: 1,
+            fields: vec![b"abc".to_vec()],
+        };
+        let bytes = frame.encode().unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(Frame::read_from(&mut bad_magic.as_slice()).is_err());
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 2;
+        assert!(Frame::read_from(&mut bad_version.as_slice()).is_err());
+
+        // 字段长度超出负载
+        let mut bad_field = bytes.clone();
+        bad_field[HEADER_LEN] = 4;
+        assert!(Frame::read_from(&mut bad_field.as_slice()).is_err());
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(Frame::read_from(&mut &truncated[..]).is_err());
+    }
+
+    #[test]
+    fn test_handle_request_status_codes() {
+        let unknown = Frame {
+            code: 0x7f,
+            request_id: 5,
+            fields: vec![],
+        };
+        let response = handle_request(&SoftwareBackend, &unknown);
+        assert_eq!(response.code, Status::Unsupported as u8);
+        assert_eq!(response.request_id, 5);
+
+        let short_key = Frame {
+            code: Opcode::Aes256EncryptBlock as u8,
+            request_id: 6,
+            fields: vec![vec![0u8; 16], vec![0u8; 16]],
+        };
+        let response = handle_request(&SoftwareBackend, &short_key);
+        assert_eq!(response.code, Status::Malformed as u8);
+
+        let bad_tag = Frame {
+            code: Opcode::DecryptAes256 as u8,
+            request_id: 7,
+            fields: vec![
+                vec![0u8; 32],
+                vec![0u8; 12],
+                vec![],
+                b"ciphertext".to_vec(),
+                vec![0u8; 16],
+            ],
+        };
+        let response = handle_request(&SoftwareBackend, &bad_tag);
+        assert_eq!(response.code, Status::Failed as u8);
+    }
+
+    #[test]
+    fn test_client_matches_software_backend_over_socket() {
+        let socket = spawn_daemon("device-e2e", Arc::new(SoftwareBackend));
+        let client = DeviceClient::new(socket.path());
+        let key = [0x42u8; 32];
+        let block = [0x17u8; 16];
+        let data: Vec<u8> = (0..=255).collect();
+
+        assert_eq!(
+            client.copr_aes256_encrypt_block(&key, &block).unwrap(),
+            SoftwareBackend
+                .copr_aes256_encrypt_block(&key, &block)
+                .unwrap()
+        );
+        assert_eq!(
+            client.copr_aes256_decrypt_block(&key, &block).unwrap(),
+            SoftwareBackend
+                .copr_aes256_decrypt_block(&key, &block)
+                .unwrap()
+        );
+        assert_eq!(
+            client.copr_hash_sha256(&data).unwrap(),
+            SoftwareBackend.copr_hash_sha256(&data).unwrap()
+        );
+        let mut state = [0u8; 200];
+        state.copy_from_slice(&data[..200]);
+        assert_eq!(
+            client.copr_keccak_f1600(&state).unwrap(),
+            SoftwareBackend.copr_keccak_f1600(&state).unwrap()
+        );
+
+        let (ciphertext, tag) = client
+            .copr_encrypt_aes256(&key, &[1u8; 12], b"aad", &data)
+            .unwrap();
+        assert_eq!(
+            client
+                .copr_decrypt_aes256(&key, &[1u8; 12], b"aad", &ciphertext, &tag)
+                .unwrap(),
+            data
+        );
+        assert!(
+            client
+                .copr_decrypt_aes256(&key, &[1u8; 12], b"other", &ciphertext, &tag)
+                .is_err()
+        );
+
+        let points = vec![vec![0xffu8; 32], vec![0x0fu8; 16]];
+        let scalars = vec![vec![0x3cu8; 32]];
+        assert_eq!(
+            client.copr_msm_accelerate(&points, &scalars).unwrap(),
+            SoftwareBackend
+                .copr_msm_accelerate(&points, &scalars)
+                .unwrap()
+        );
+
+        // 高层类型可直接使用设备后端
+        let ed25519 = Ed25519Riscv::with_accelerator(Box::new(DeviceClient::new(socket.path())));
+        let (secret, public) = ed25519.keygen_from_seed(&[3u8; 32]);
+        let signature = ed25519.sign(&secret, b"message").unwrap();
+        assert!(ed25519.verify(&public, b"message", &signature).unwrap());
+        assert!(!ed25519.verify(&public, b"tampered", &signature).unwrap());
+        let mut forged = signature;
+        forged[0] ^= 1;
+        let batch = [
+            (public, b"message".as_slice(), signature),
+            (public, b"message".as_slice(), forged),
+        ];
+        assert_eq!(ed25519.find_invalid(&batch).unwrap(), vec![1]);
+
+        let aes = Aes256Riscv::with_accelerator(Box::new(DeviceClient::new(socket.path())));
+        let encrypted = aes
+            .encrypt_aes256(b"payload", &Aes256Key::new(key))
+            .unwrap();
+        assert_eq!(
+            aes.decrypt_aes256(&encrypted, &Aes256Key::new(key))
+                .unwrap(),
+            b"payload"
+        );
+    }
+
+    #[test]
+    fn test_client_reports_daemon_side_failures() {
+        let mock = MockAccelerator::new().fail_operation(MockOperation::SignEd25519);
+        let socket = spawn_daemon("device-fail", Arc::new(mock.clone()));
+        let client = DeviceClient::new(socket.path());
+
+        let err = client.copr_sign_ed25519(&[1u8; 32], b"msg").unwrap_err();
+        assert!(err.to_string().contains("Failed"));
+        // 运算失败不影响同一连接上的后续请求
+        assert!(client.copr_hash_sha256(b"abc").is_ok());
+        assert_eq!(mock.call_count(MockOperation::SignEd25519), 1);
+    }
+
+    #[test]
+    fn test_client_drops_desynchronized_connection() {
+        let socket = TempPath::new("device-desync.sock");
+        let _ = std::fs::remove_file(socket.path());
+        let listener = UnixListener::bind(socket.path()).unwrap();
+        thread::spawn(move || {
+            // 第一条连接：回应错误的序号，并在流中多留一帧过期响应
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = Frame::read_from(&mut stream).unwrap().unwrap();
+            for (request_id, field) in [
+                (request.request_id.wrapping_add(7), vec![0u8; 32]),
+                (request.request_id.wrapping_add(1), b"stale".to_vec()),
+            ] {
+                let frame = Frame {
+                    code: Status::Ok as u8,
+                    request_id,
+                    fields: vec![field],
+                };
+                stream.write_all(&frame.encode().unwrap()).unwrap();
+            }
+            // 第二条连接：正常服务
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = crate::crypto::device::serve_stream(&mut stream, &SoftwareBackend);
+            drop(stream);
+        });
+
+        let client = DeviceClient::new(socket.path());
+        let err = client.copr_hash_sha256(b"abc").unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+        // 错位的连接已丢弃，不会读到过期响应
+        assert_eq!(
+            client.copr_hash_sha256(b"abc").unwrap(),
+            SoftwareBackend.copr_hash_sha256(b"abc").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_server_reports_connection_errors_to_callback() {
+        let socket = TempPath::new("device-errors.sock");
+        let server = DeviceServer::bind(socket.path()).unwrap();
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&errors);
+        thread::spawn(move || {
+            let _ = server.serve_with(move |e| sink.lock().unwrap().push(e.to_string()));
+        });
+
+        let mut stream = UnixStream::connect(socket.path()).unwrap();
+        stream.write_all(&[b'X'; HEADER_LEN]).unwrap();
+        // 服务端在回调返回后关闭连接
+        let mut rest = Vec::new();
+        assert_eq!(stream.read_to_end(&mut rest).unwrap(), 0);
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("magic"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_client_without_device_fails_per_call() {
+        let mut accelerator = HardwareAccelerator::new();
+        accelerator.set_device_path("/nonexistent/zk-rollup-crypto0");
+        let client = accelerator.open_device();
+
+        assert_eq!(client.path().to_str(), Some(accelerator.device_path()));
+        assert!(client.copr_hash_sha256(b"abc").is_err());
+    }
+
+    #[test]
+    fn test_bind_refuses_to_replace_regular_file() {
+        let file = TempPath::new("device-file");
+        std::fs::write(file.path(), b"keep").unwrap();
+
+        assert!(DeviceServer::bind(file.path()).is_err());
+        assert_eq!(std::fs::read(file.path()).unwrap(), b"keep");
+    }
+}
@@ -0,0 +1,310 @@
+//! BN254 标量域运算
+//!
+//! 证明系统（Groth16 over BN254）的标量域
+//! `Fr = Z/rZ`，`r = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001`。
+//! Poseidon 哈希和 `rollup::zk_proof` 中的约束系统都在这个域上计算。
+//!
+//! 元素以 4 个 64 位小端序 limb 的 Montgomery 形式（`a * 2^256 mod r`）存储，
+//! 乘法使用 CIOS Montgomery 约减。外部编码统一为 32 字节大端序，与十六进制写法一致。
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// 域运算中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct FieldError {
+    message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Field Error: {}", self.message)
+    }
+}
+
+impl Error for FieldError {}
+
+/// 模数 r 的 limb 表示（小端序）
+const MODULUS: [u64; 4] = [
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+
+/// -r^{-1} mod 2^64
+const INV: u64 = 0xc2e1f593efffffff;
+
+/// R = 2^256 mod r，即 Montgomery 形式的 1
+const R: [u64; 4] = [
+    0xac96341c4ffffffb,
+    0x36fc76959f60cd29,
+    0x666ea36f7879462e,
+    0x0e0a77c19a07df2f,
+];
+
+/// R^2 mod r，用于转入 Montgomery 形式
+const R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+/// a + b + carry，返回 (和, 进位)
+#[inline]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+/// a - b - borrow，返回 (差, 借位)
+#[inline]
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let diff = (a as u128).wrapping_sub(b as u128 + (borrow >> 63) as u128);
+    (diff as u64, (diff >> 64) as u64)
+}
+
+/// a + b * c + carry，返回 (低位, 高位)
+#[inline]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let product = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (product as u64, (product >> 64) as u64)
+}
+
+/// BN254 标量域元素
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Fr([u64; 4]);
+
+#[allow(dead_code)]
+impl Fr {
+    /// 加法单位元 0
+    pub const ZERO: Fr = Fr([0; 4]);
+    /// 乘法单位元 1
+    pub const ONE: Fr = Fr(R);
+    /// 编码长度（字节）
+    pub const BYTES: usize = 32;
+
+    /// 由 64 位整数构造
+    pub fn from_u64(value: u64) -> Self {
+        Fr([value, 0, 0, 0]).mont_mul(&Fr(R2))
+    }
+
+    /// 从 32 字节大端序规范编码解码
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Fr)`: 域元素
+    /// - `Err(Box<dyn Error>)`: 数值不小于模数 r
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Result<Self, Box<dyn Error>> {
+        let limbs = Self::limbs_from_be(bytes);
+
+        // 规范性检查：limbs - MODULUS 必须产生借位
+        let mut borrow = 0;
+        for i in 0..4 {
+            (_, borrow) = sbb(limbs[i], MODULUS[i], borrow);
+        }
+        if borrow == 0 {
+            return Err(Box::new(FieldError {
+                message: "Value is not less than the field modulus".to_string(),
+            }));
+        }
+
+        Ok(Fr(limbs).mont_mul(&Fr(R2)))
+    }
+
+    /// 把 32 字节大端序整数约减到域中
+    ///
+    /// 任意 256 位输入都有效，适合把哈希输出映射为域元素。
+    pub fn from_bytes_be_mod_order(bytes: &[u8; 32]) -> Self {
+        // x = x_hi * 2^128 + x_lo，两半都小于 r，分别转入 Montgomery 形式后在域中组合
+        let limbs = Self::limbs_from_be(bytes);
+        let lo = Fr([limbs[0], limbs[1], 0, 0]).mont_mul(&Fr(R2));
+        let hi = Fr([limbs[2], limbs[3], 0, 0]).mont_mul(&Fr(R2));
+        let shift = Fr::from_u64(1 << 32).square().square();
+        lo + hi * shift
+    }
+
+    /// 编码为 32 字节大端序
+    pub fn to_bytes_be(self) -> [u8; 32] {
+        let limbs = self.mont_mul(&Fr([1, 0, 0, 0])).0;
+        let mut bytes = [0u8; 32];
+        for (i, limb) in limbs.iter().enumerate() {
+            bytes[(3 - i) * 8..(4 - i) * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn limbs_from_be(bytes: &[u8; 32]) -> [u64; 4] {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[(3 - i) * 8..(4 - i) * 8]);
+            *limb = u64::from_be_bytes(word);
+        }
+        limbs
+    }
+
+    /// 是否为 0
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// 若结果不小于模数则减去模数
+    fn subtract_modulus(limbs: [u64; 4], carry: u64) -> [u64; 4] {
+        let mut reduced = [0u64; 4];
+        let mut borrow = 0;
+        for i in 0..4 {
+            (reduced[i], borrow) = sbb(limbs[i], MODULUS[i], borrow);
+        }
+        // 借位掩码：有借位且没有进位时保留原值
+        let (_, borrow) = sbb(carry, 0, borrow);
+        let mut result = [0u64; 4];
+        for i in 0..4 {
+            result[i] = (limbs[i] & borrow) | (reduced[i] & !borrow);
+        }
+        result
+    }
+
+    fn add_mod(&self, rhs: &Self) -> Self {
+        let mut sum = [0u64; 4];
+        let mut carry = 0;
+        for (i, limb) in sum.iter_mut().enumerate() {
+            (*limb, carry) = adc(self.0[i], rhs.0[i], carry);
+        }
+        Fr(Self::subtract_modulus(sum, carry))
+    }
+
+    fn sub_mod(&self, rhs: &Self) -> Self {
+        let mut diff = [0u64; 4];
+        let mut borrow = 0;
+        for (i, limb) in diff.iter_mut().enumerate() {
+            (*limb, borrow) = sbb(self.0[i], rhs.0[i], borrow);
+        }
+        // 有借位时加回模数
+        let mut carry = 0;
+        for (limb, modulus) in diff.iter_mut().zip(MODULUS) {
+            (*limb, carry) = adc(*limb, modulus & borrow, carry);
+        }
+        Fr(diff)
+    }
+
+    /// CIOS Montgomery 乘法：返回 a * b * R^{-1} mod r
+    fn mont_mul(&self, rhs: &Self) -> Self {
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0;
+            for (t_j, limb) in t.iter_mut().zip(self.0) {
+                (*t_j, carry) = mac(*t_j, limb, rhs.0[i], carry);
+            }
+            (t[4], t[5]) = adc(t[4], carry, 0);
+
+            let m = t[0].wrapping_mul(INV);
+            let (_, mut carry) = mac(t[0], m, MODULUS[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, MODULUS[j], carry);
+            }
+            (t[3], carry) = adc(t[4], carry, 0);
+            t[4] = t[5] + carry;
+            t[5] = 0;
+        }
+        Fr(Self::subtract_modulus([t[0], t[1], t[2], t[3]], t[4]))
+    }
+
+    /// 平方
+    pub fn square(&self) -> Self {
+        self.mont_mul(self)
+    }
+
+    /// 计算 self^exponent，指数为小端序 limb
+    pub fn pow(&self, exponent: &[u64]) -> Self {
+        let mut result = Self::ONE;
+        for limb in exponent.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.square();
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mont_mul(self);
+                }
+            }
+        }
+        result
+    }
+
+    /// 乘法逆元，由费马小定理 a^(r-2) 计算；0 没有逆元
+    pub fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut exponent = MODULUS;
+        exponent[0] -= 2;
+        Some(self.pow(&exponent))
+    }
+}
+
+impl fmt::Debug for Fr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fr(0x")?;
+        for byte in self.to_bytes_be() {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl From<u64> for Fr {
+    fn from(value: u64) -> Self {
+        Self::from_u64(value)
+    }
+}
+
+impl Add for Fr {
+    type Output = Fr;
+
+    fn add(self, rhs: Fr) -> Fr {
+        self.add_mod(&rhs)
+    }
+}
+
+impl Sub for Fr {
+    type Output = Fr;
+
+    fn sub(self, rhs: Fr) -> Fr {
+        self.sub_mod(&rhs)
+    }
+}
+
+impl Mul for Fr {
+    type Output = Fr;
+
+    fn mul(self, rhs: Fr) -> Fr {
+        self.mont_mul(&rhs)
+    }
+}
+
+impl Neg for Fr {
+    type Output = Fr;
+
+    fn neg(self) -> Fr {
+        Fr::ZERO - self
+    }
+}
+
+impl AddAssign for Fr {
+    fn add_assign(&mut self, rhs: Fr) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fr {
+    fn sub_assign(&mut self, rhs: Fr) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fr {
+    fn mul_assign(&mut self, rhs: Fr) {
+        *self = *self * rhs;
+    }
+}
@@ -1,9 +1,141 @@
-/// 高级哈希操作模块
-///
-/// 该模块提供基于 SHA-256 的高级哈希功能，包括双 SHA-256、哈希组合和 Merkle 树操作。
+//! 高级哈希操作模块
+//!
+//! 该模块提供双 SHA-256、哈希组合和 Merkle 树操作。
+//! `MerkleTree` 可生成单叶子和多叶子包含证明（`MerkleProof` / `MerkleMultiProof`，
+//! 可用 serde 序列化），轻客户端用 `verify_proof` / `verify_multi_proof` 验证账户成员关系。
+//! 存款队列和交易日志使用只追加的 `IncrementalMerkleTree`，只保存 frontier，追加为 O(log n)。
+//! Merkle 树函数对 `Hasher` trait 泛型，可选用以下实现：
+//!
+//! - `Sha256Hasher`：单次 SHA-256（经 `Sha256Riscv`，使用所选加速器）
+//! - `DoubleSha256Hasher`：双 SHA-256，比特币风格
+//! - `Blake2bHasher`：输出 256 位的 BLAKE2b，64 位平台上软件实现最快
+//! - `Blake2sHasher`：BLAKE2s-256，适合 32 位平台
+//!
+//! # 域分隔
+//!
+//! 树的构造函数只接受叶子数据，由树自身计算 `merkle_leaf`（前缀 0x00），
+//! 分支一律为 `merkle_branch`（前缀 0x01）。因此内部节点无法冒充叶子，
+//! 例如 `[H(a, b), c]` 与 `[a, b, c]` 的根不同。落单节点的处理见 `MerkleLayout`。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::hash::{Blake2bHasher, DoubleSha256Hasher, MerkleLayout, calculate_merkle_root};
+//!
+//! let items = [b"a", b"b", b"c"];
+//! let root = calculate_merkle_root::<Blake2bHasher>(&items, MerkleLayout::Rfc6962);
+//! assert_ne!(root, calculate_merkle_root::<Blake2bHasher>(&items, MerkleLayout::ZeroPad));
+//! assert_ne!(root, calculate_merkle_root::<DoubleSha256Hasher>(&items, MerkleLayout::Rfc6962));
+//! ```
 
 #[allow(dead_code)]
 use crate::crypto::sha256::Sha256Riscv;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Blake2s256, Digest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// 输出 32 字节摘要的哈希函数
+///
+/// 无状态的关联函数接口，便于在 Merkle 树等泛型代码中按类型选择哈希算法。
+#[allow(dead_code)]
+pub trait Hasher {
+    /// 算法名称
+    const NAME: &'static str;
+
+    /// 计算数据的 32 字节摘要
+    fn hash(data: &[u8]) -> [u8; 32];
+
+    /// 计算多段数据拼接后的摘要
+    ///
+    /// 默认实现先拼接再调用 `hash`；支持增量更新的实现可以覆盖以避免复制。
+    fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+        Self::hash(&parts.concat())
+    }
+}
+
+/// 单次 SHA-256
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sha256Hasher;
+
+impl Hasher for Sha256Hasher {
+    const NAME: &'static str = "sha256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Sha256Riscv::hash(data)
+    }
+
+    fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Sha256Riscv::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    }
+}
+
+/// 双 SHA-256：`SHA-256(SHA-256(data))`
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DoubleSha256Hasher;
+
+impl Hasher for DoubleSha256Hasher {
+    const NAME: &'static str = "double-sha256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        double_sha256(data)
+    }
+
+    fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+        Sha256Riscv::hash(&Sha256Hasher::hash_parts(parts))
+    }
+}
+
+/// BLAKE2b，输出截断参数为 32 字节（RFC 7693，不同于 BLAKE2b-512 的前 32 字节）
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Blake2bHasher;
+
+impl Hasher for Blake2bHasher {
+    const NAME: &'static str = "blake2b-256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Blake2b::<U32>::digest(data).into()
+    }
+
+    fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Blake2b::<U32>::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// BLAKE2s-256（RFC 7693）
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Blake2sHasher;
+
+impl Hasher for Blake2sHasher {
+    const NAME: &'static str = "blake2s-256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Blake2s256::digest(data).into()
+    }
+
+    fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Blake2s256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
 
 /// 计算双 SHA-256 哈希
 ///
@@ -18,6 +150,7 @@ use crate::crypto::sha256::Sha256Riscv;
 /// # 返回
 ///
 /// 32 字节双哈希结果
+#[allow(dead_code)]
 pub fn double_sha256(data: &[u8]) -> [u8; 32] {
     let first = Sha256Riscv::hash(data);
     Sha256Riscv::hash(&first)
@@ -36,6 +169,7 @@ pub fn double_sha256(data: &[u8]) -> [u8; 32] {
 /// # 返回
 ///
 /// 合并后的 32 字节哈希值
+#[allow(dead_code)]
 pub fn hash_combine(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
     let mut combined = [0u8; 64];
     combined[..32].copy_from_slice(a);
@@ -46,85 +180,711 @@ pub fn hash_combine(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
 /// 创建 Merkle 树叶子节点
 ///
 /// 将数据转换为 Merkle 树的叶子节点。
-/// 叶子节点通过前缀 0x00 标识，然后进行双 SHA-256 哈希。
+/// 叶子节点通过前缀 0x00 标识，然后用 `H` 哈希。
 ///
 /// # 参数
 ///
+/// * `H` - 哈希算法，如 `DoubleSha256Hasher`
 /// * `data` - 原始数据，将被哈希后作为叶子
 ///
 /// # 返回
 ///
 /// Merkle 叶子节点的 32 字节哈希
-pub fn merkle_leaf(data: &[u8]) -> [u8; 32] {
-    let mut prefixed = Vec::with_capacity(data.len() + 1);
-    prefixed.push(0x00);
-    prefixed.extend_from_slice(data);
-    double_sha256(&prefixed)
+#[allow(dead_code)]
+pub fn merkle_leaf<H: Hasher>(data: &[u8]) -> [u8; 32] {
+    H::hash_parts(&[&[0x00], data])
 }
 
 /// 创建 Merkle 树分支节点
 ///
 /// 计算两个子节点的父节点哈希。
-/// 分支节点通过前缀 0x01 标识，然后进行双 SHA-256 哈希。
+/// 分支节点通过前缀 0x01 标识，然后用 `H` 哈希。
 ///
 /// # 参数
 ///
+/// * `H` - 哈希算法，必须与叶子节点使用的算法相同
 /// * `left` - 左子节点哈希（32 字节）
 /// * `right` - 右子节点哈希（32 字节）
 ///
 /// # 返回
 ///
 /// 父节点的 32 字节哈希
-pub fn merkle_branch(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
-    let mut combined = [0u8; 65];
-    combined[0] = 0x01;
-    combined[1..33].copy_from_slice(left);
-    combined[33..65].copy_from_slice(right);
-    double_sha256(&combined)
+#[allow(dead_code)]
+pub fn merkle_branch<H: Hasher>(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    H::hash_parts(&[&[0x01], left, right])
+}
+
+/// Merkle 树的形状，决定落单节点和叶子数不是 2 的幂时的处理方式
+///
+/// 三种形状都由树自身对叶子加 0x00 前缀、对分支加 0x01 前缀，
+/// 调用方无法把内部节点当作叶子传入，单叶子树的根也是叶子域的哈希。
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MerkleLayout {
+    /// 比特币风格：每层落单的最后一个节点与自身配对
+    ///
+    /// 叶子 `[a, b, c]` 与 `[a, b, c, c]` 的根相同（CVE-2012-2459），
+    /// 验证方必须从可信来源核对叶子数。
+    Duplicate,
+    /// RFC 6962（证书透明度）风格：落单节点原样进入上一层
+    ///
+    /// 等价于按小于叶子数的最大 2 的幂把叶子切成左右两棵子树递归计算。
+    #[default]
+    Rfc6962,
+    /// 叶子补全 0 值到 2 的幂，得到满二叉树
+    ///
+    /// 补位节点不是任何数据的叶子哈希，因此不会与真实叶子混淆；
+    /// 同样数量的叶子证明长度固定，适合电路内验证。
+    ZeroPad,
+}
+
+#[allow(dead_code)]
+impl MerkleLayout {
+    /// 第 0 层（含补位）的节点数
+    ///
+    /// 证明中的 `leaf_count` 不可信；补位后超出 `usize` 范围时返回 `None`。
+    fn base_width(self, leaf_count: usize) -> Option<usize> {
+        match self {
+            MerkleLayout::ZeroPad if leaf_count > 0 => leaf_count.checked_next_power_of_two(),
+            _ => Some(leaf_count),
+        }
+    }
+
+    /// 落单节点在上一层的值
+    fn unpaired<H: Hasher>(self, node: &[u8; 32]) -> [u8; 32] {
+        match self {
+            MerkleLayout::Duplicate => merkle_branch::<H>(node, node),
+            MerkleLayout::Rfc6962 | MerkleLayout::ZeroPad => *node,
+        }
+    }
+
+    /// 计算上一层节点
+    fn next_level<H: Hasher>(self, level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        level
+            .chunks(2)
+            .map(|chunk| {
+                if chunk.len() == 2 {
+                    merkle_branch::<H>(&chunk[0], &chunk[1])
+                } else {
+                    self.unpaired::<H>(&chunk[0])
+                }
+            })
+            .collect()
+    }
 }
 
 /// 计算 Merkle 树的根哈希
 ///
-/// 从一组叶子节点递归计算 Merkle 根。
-/// 如果叶子数为奇数，最后一个节点会重复使用。
+/// 每个数据项先经 `merkle_leaf` 转为叶子，再按 `layout` 逐层计算分支。
 ///
 /// # 参数
 ///
-/// * `leaves` - Merkle 树的叶子节点数组，每个为 32 字节哈希
+/// * `H` - 哈希算法
+/// * `items` - 叶子数据，如账户编码或交易哈希
+/// * `layout` - 树的形状
 ///
 /// # 返回
 ///
-/// Merkle 根的 32 字节哈希
+/// Merkle 根的 32 字节哈希；没有叶子时为空串的哈希 `H("")`（与 RFC 6962 一致）
+#[allow(dead_code)]
+pub fn calculate_merkle_root<H: Hasher>(
+    items: &[impl AsRef<[u8]>],
+    layout: MerkleLayout,
+) -> [u8; 32] {
+    let leaves: Vec<[u8; 32]> = items
+        .iter()
+        .map(|item| merkle_leaf::<H>(item.as_ref()))
+        .collect();
+    root_from_leaves::<H>(leaves, layout)
+}
+
+/// 由叶子哈希计算根，不保留中间层
+fn root_from_leaves<H: Hasher>(mut level: Vec<[u8; 32]>, layout: MerkleLayout) -> [u8; 32] {
+    if level.is_empty() {
+        return H::hash(&[]);
+    }
+    let width = layout
+        .base_width(level.len())
+        .expect("in-memory leaf count cannot overflow");
+    level.resize(width, [0u8; 32]);
+    while level.len() > 1 {
+        level = layout.next_level::<H>(&level);
+    }
+    level[0]
+}
+
+/// Merkle 证明相关的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct MerkleError {
+    message: String,
+}
+
+impl fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Merkle Error: {}", self.message)
+    }
+}
+
+impl Error for MerkleError {}
+
+fn merkle_error(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(MerkleError {
+        message: message.into(),
+    })
+}
+
+/// 单个叶子的包含证明
+///
+/// `siblings` 自底向上列出路径上的兄弟节点；某一层该节点是落单的最后一个节点时
+/// 没有兄弟，也不占位。`leaf_count` 和 `layout` 决定每层的节点数，验证方据此判断
+/// 哪些层需要兄弟；两者由证明方提供，验证方应与根一起从可信来源核对。
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// 树的形状
+    pub layout: MerkleLayout,
+    /// 树的叶子总数（不含补位）
+    pub leaf_count: usize,
+    /// 自底向上的兄弟节点哈希
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// 多个叶子的包含证明
+///
+/// 只包含无法由被证明叶子推出的节点，按自底向上、同层从左到右的顺序排列；
+/// 多个叶子共享的路径节点不会重复出现。
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleMultiProof {
+    /// 树的形状
+    pub layout: MerkleLayout,
+    /// 树的叶子总数（不含补位）
+    pub leaf_count: usize,
+    /// 被证明叶子的序号，严格递增
+    pub indices: Vec<usize>,
+    /// 验证所需的其余节点哈希
+    pub hashes: Vec<[u8; 32]>,
+}
+
+/// 保留全部层的 Merkle 树，根与 `calculate_merkle_root` 一致
+///
+/// # 使用示例
 ///
-/// # 算法
+/// ```rust
+/// use crypto::hash::{MerkleTree, Sha256Hasher, merkle_leaf, verify_proof};
 ///
-/// 1. 如果只有一片叶子，返回该叶子
-/// 2. 否则成对计算每层的分支哈希
-/// 3. 重复直到只剩一个哈希，即为根
-pub fn calculate_merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
-    if leaves.is_empty() {
-        return [0u8; 32];
+/// let tree = MerkleTree::<Sha256Hasher>::from_data(&[b"alice", b"bob", b"carol"]);
+/// let proof = tree.proof(2).unwrap();
+/// let leaf = merkle_leaf::<Sha256Hasher>(b"carol");
+/// assert!(verify_proof::<Sha256Hasher>(&tree.root(), &leaf, 2, &proof));
+/// ```
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree<H: Hasher> {
+    layout: MerkleLayout,
+    leaf_count: usize,
+    /// `levels[0]` 为叶子（含补位），最后一层只有根
+    levels: Vec<Vec<[u8; 32]>>,
+    hasher: PhantomData<H>,
+}
+
+#[allow(dead_code)]
+impl<H: Hasher> MerkleTree<H> {
+    /// 按默认形状（RFC 6962）构建 Merkle 树
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 叶子数据，逐个经 `merkle_leaf` 转为叶子
+    pub fn from_data(items: &[impl AsRef<[u8]>]) -> Self {
+        Self::with_layout(items, MerkleLayout::default())
     }
 
-    if leaves.len() == 1 {
-        return leaves[0];
+    /// 按指定形状构建 Merkle 树
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 叶子数据，逐个经 `merkle_leaf` 转为叶子
+    /// * `layout` - 树的形状
+    pub fn with_layout(items: &[impl AsRef<[u8]>], layout: MerkleLayout) -> Self {
+        let mut leaves: Vec<[u8; 32]> = items
+            .iter()
+            .map(|item| merkle_leaf::<H>(item.as_ref()))
+            .collect();
+        let leaf_count = leaves.len();
+        let width = layout
+            .base_width(leaf_count)
+            .expect("in-memory leaf count cannot overflow");
+        leaves.resize(width, [0u8; 32]);
+
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = layout.next_level::<H>(&levels[levels.len() - 1]);
+            levels.push(next);
+        }
+        MerkleTree {
+            layout,
+            leaf_count,
+            levels,
+            hasher: PhantomData,
+        }
     }
 
-    let mut level = leaves.to_vec();
+    /// 根哈希；空树为 `H("")`
+    pub fn root(&self) -> [u8; 32] {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => *root,
+            None => H::hash(&[]),
+        }
+    }
 
-    while level.len() > 1 {
-        let mut next_level = Vec::new();
+    /// 树的形状
+    pub fn layout(&self) -> MerkleLayout {
+        self.layout
+    }
+
+    /// 叶子哈希（不含补位）
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.levels[0][..self.leaf_count]
+    }
+
+    /// 叶子数（不含补位）
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// 树高（根到叶子的层数），单叶子或空树为 0
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
 
-        for chunk in level.chunks(2) {
-            if chunk.len() == 2 {
-                next_level.push(merkle_branch(&chunk[0], &chunk[1]));
+    /// 生成单个叶子的包含证明
+    ///
+    /// # 参数
+    ///
+    /// * `index` - 叶子序号
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(MerkleProof)`: 兄弟路径
+    /// - `Err(Box<dyn Error>)`: 序号越界
+    pub fn proof(&self, index: usize) -> Result<MerkleProof, Box<dyn Error>> {
+        if index >= self.leaf_count {
+            return Err(merkle_error(format!(
+                "Leaf index {} out of range for {} leaves",
+                index, self.leaf_count
+            )));
+        }
+
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.depth()] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+
+        Ok(MerkleProof {
+            layout: self.layout,
+            leaf_count: self.leaf_count,
+            siblings,
+        })
+    }
+
+    /// 生成多个叶子的合并包含证明
+    ///
+    /// # 参数
+    ///
+    /// * `indices` - 叶子序号，顺序任意，重复的序号只证明一次
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(MerkleMultiProof)`: 合并证明，`indices` 已排序去重
+    /// - `Err(Box<dyn Error>)`: 序号为空或越界
+    pub fn multi_proof(&self, indices: &[usize]) -> Result<MerkleMultiProof, Box<dyn Error>> {
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        match sorted.last() {
+            None => return Err(merkle_error("No leaf indices to prove")),
+            Some(&last) if last >= self.leaf_count => {
+                return Err(merkle_error(format!(
+                    "Leaf index {} out of range for {} leaves",
+                    last, self.leaf_count
+                )));
+            }
+            Some(_) => {}
+        }
+
+        let mut hashes = Vec::new();
+        let mut known = sorted.clone();
+        for level in &self.levels[..self.depth()] {
+            let mut next = Vec::with_capacity(known.len());
+            let mut i = 0;
+            while i < known.len() {
+                let sibling = known[i] ^ 1;
+                if known.get(i + 1) == Some(&sibling) {
+                    // 兄弟也已知，无需提供
+                    i += 1;
+                } else if let Some(hash) = level.get(sibling) {
+                    hashes.push(*hash);
+                }
+                next.push(known[i] / 2);
+                i += 1;
+            }
+            known = next;
+        }
+
+        Ok(MerkleMultiProof {
+            layout: self.layout,
+            leaf_count: self.leaf_count,
+            indices: sorted,
+            hashes,
+        })
+    }
+}
+
+/// 验证单个叶子的包含证明
+///
+/// # 参数
+///
+/// * `H` - 构建树时使用的哈希算法
+/// * `root` - 可信的根哈希
+/// * `leaf` - 叶子哈希，由验证方用 `merkle_leaf` 从数据计算
+/// * `index` - 叶子序号
+/// * `proof` - `MerkleTree::proof` 生成的证明
+///
+/// # 返回
+///
+/// 叶子位于根所代表的树的 `index` 位置时返回 `true`
+#[allow(dead_code)]
+pub fn verify_proof<H: Hasher>(
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    index: usize,
+    proof: &MerkleProof,
+) -> bool {
+    if index >= proof.leaf_count {
+        return false;
+    }
+
+    let mut siblings = proof.siblings.iter();
+    let mut hash = *leaf;
+    let mut position = index;
+    let Some(mut width) = proof.layout.base_width(proof.leaf_count) else {
+        return false;
+    };
+    while width > 1 {
+        hash = if position ^ 1 < width {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            if position.is_multiple_of(2) {
+                merkle_branch::<H>(&hash, sibling)
+            } else {
+                merkle_branch::<H>(sibling, &hash)
+            }
+        } else {
+            proof.layout.unpaired::<H>(&hash)
+        };
+        position /= 2;
+        width = width.div_ceil(2);
+    }
+
+    siblings.next().is_none() && hash == *root
+}
+
+/// 验证多个叶子的合并包含证明
+///
+/// # 参数
+///
+/// * `H` - 构建树时使用的哈希算法
+/// * `root` - 可信的根哈希
+/// * `leaves` - 叶子哈希，与 `proof.indices` 一一对应
+/// * `proof` - `MerkleTree::multi_proof` 生成的证明
+///
+/// # 返回
+///
+/// 全部叶子都位于根所代表的树的对应位置时返回 `true`
+#[allow(dead_code)]
+pub fn verify_multi_proof<H: Hasher>(
+    root: &[u8; 32],
+    leaves: &[[u8; 32]],
+    proof: &MerkleMultiProof,
+) -> bool {
+    if leaves.is_empty()
+        || leaves.len() != proof.indices.len()
+        || !proof.indices.windows(2).all(|pair| pair[0] < pair[1])
+        || proof.indices[proof.indices.len() - 1] >= proof.leaf_count
+    {
+        return false;
+    }
+
+    let mut hashes = proof.hashes.iter();
+    let mut known: Vec<(usize, [u8; 32])> = proof
+        .indices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .collect();
+    let Some(mut width) = proof.layout.base_width(proof.leaf_count) else {
+        return false;
+    };
+    while width > 1 {
+        let mut next = Vec::with_capacity(known.len());
+        let mut i = 0;
+        while i < known.len() {
+            let (position, hash) = known[i];
+            let sibling = position ^ 1;
+            let parent = if known.get(i + 1).map(|(p, _)| *p) == Some(sibling) {
+                i += 1;
+                merkle_branch::<H>(&hash, &known[i].1)
+            } else if sibling < width {
+                let Some(sibling_hash) = hashes.next() else {
+                    return false;
+                };
+                if position.is_multiple_of(2) {
+                    merkle_branch::<H>(&hash, sibling_hash)
+                } else {
+                    merkle_branch::<H>(sibling_hash, &hash)
+                }
             } else {
-                next_level.push(chunk[0]);
+                proof.layout.unpaired::<H>(&hash)
+            };
+            next.push((position / 2, parent));
+            i += 1;
+        }
+        known = next;
+        width = width.div_ceil(2);
+    }
+
+    hashes.next().is_none() && known[0].1 == *root
+}
+
+/// 高为 0 到 `depth - 1` 的全 0 子树的根，与 `MerkleLayout::ZeroPad` 的补位一致
+fn zero_hashes<H: Hasher>(depth: usize) -> Vec<[u8; 32]> {
+    let mut zeros = Vec::with_capacity(depth);
+    let mut node = [0u8; 32];
+    for _ in 0..depth {
+        zeros.push(node);
+        node = merkle_branch::<H>(&node, &node);
+    }
+    zeros
+}
+
+/// 只追加的增量 Merkle 树
+///
+/// 与以太坊存款合约相同的 frontier 算法：只保存最后一片叶子、其路径上的左兄弟和叶子数，
+/// 追加和计算根都是 O(log n)，不需要保留全部叶子。叶子由树自身经 `merkle_leaf` 计算，
+/// 树高随叶子数增长，根与 `MerkleLayout::ZeroPad` 下 `calculate_merkle_root` 的结果一致。
+///
+/// 只凭 frontier 无法为任意旧叶子生成证明，需要证明的叶子应使用 `append_tracked` 追加：
+/// 树为其保存一条路径，后续追加补全右侧兄弟时同步更新，每片被跟踪的叶子占用 O(log n) 空间。
+///
+/// # 使用示例
+///
+/// ```rust
+/// use crypto::hash::{IncrementalMerkleTree, Sha256Hasher, merkle_leaf, verify_proof};
+///
+/// let mut deposits = IncrementalMerkleTree::<Sha256Hasher>::new();
+/// deposits.append(b"deposit-0");
+/// let mine = deposits.append_tracked(b"deposit-1");
+/// deposits.append(b"deposit-2");
+///
+/// let proof = deposits.proof(mine).unwrap();
+/// let leaf = merkle_leaf::<Sha256Hasher>(b"deposit-1");
+/// assert!(verify_proof::<Sha256Hasher>(&deposits.root(), &leaf, mine, &proof));
+/// ```
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncrementalMerkleTree<H: Hasher> {
+    /// 已追加的叶子数
+    count: usize,
+    /// 最后一片叶子的哈希
+    last: [u8; 32],
+    /// 最后一片叶子路径上的左兄弟；`ommers[h]` 仅在 `count - 1` 的第 h 位为 1 时有效
+    ommers: Vec<[u8; 32]>,
+    /// 被跟踪叶子的兄弟路径，`None` 表示该层兄弟在右侧且尚未完整
+    tracked: BTreeMap<usize, Vec<Option<[u8; 32]>>>,
+    hasher: PhantomData<H>,
+}
+
+#[allow(dead_code)]
+impl<H: Hasher> IncrementalMerkleTree<H> {
+    /// 创建空树
+    pub fn new() -> Self {
+        IncrementalMerkleTree {
+            count: 0,
+            last: [0u8; 32],
+            ommers: Vec::new(),
+            tracked: BTreeMap::new(),
+            hasher: PhantomData,
+        }
+    }
+
+    /// 叶子数
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// 是否没有叶子
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// 当前树高，等于补全到 2 的幂后的层数；单叶子或空树为 0
+    pub fn depth(&self) -> usize {
+        self.count.next_power_of_two().trailing_zeros() as usize
+    }
+
+    /// 追加一片叶子
+    ///
+    /// # 参数
+    ///
+    /// * `data` - 叶子数据，经 `merkle_leaf` 转为叶子
+    ///
+    /// # 返回
+    ///
+    /// 叶子序号
+    pub fn append(&mut self, data: &[u8]) -> usize {
+        let leaf = merkle_leaf::<H>(data);
+
+        if self.count > 0 {
+            // 上一片叶子向上合并，途经的节点都已完整，最后一个成为新叶子路径上的左兄弟
+            let position = self.count - 1;
+            let mut carry = self.last;
+            let mut level = 0;
+            loop {
+                self.fill_tracked(level, position >> level, &carry);
+                if (position >> level) & 1 == 0 {
+                    if self.ommers.len() <= level {
+                        self.ommers.resize(level + 1, [0u8; 32]);
+                    }
+                    self.ommers[level] = carry;
+                    break;
+                }
+                carry = merkle_branch::<H>(&self.ommers[level], &carry);
+                level += 1;
             }
         }
 
-        level = next_level;
+        self.last = leaf;
+        self.count += 1;
+        self.count - 1
     }
 
-    level[0]
+    /// 追加一片叶子并跟踪其路径，之后可对它调用 `proof`
+    ///
+    /// # 参数
+    ///
+    /// * `data` - 叶子数据，经 `merkle_leaf` 转为叶子
+    ///
+    /// # 返回
+    ///
+    /// 叶子序号
+    pub fn append_tracked(&mut self, data: &[u8]) -> usize {
+        let index = self.append(data);
+        let levels = (usize::BITS - index.leading_zeros()) as usize;
+        let path = (0..levels)
+            .map(|level| ((index >> level) & 1 == 1).then(|| self.ommers[level]))
+            .collect();
+        self.tracked.insert(index, path);
+        index
+    }
+
+    /// 停止跟踪叶子，释放其路径
+    ///
+    /// # 返回
+    ///
+    /// 该叶子此前是否被跟踪
+    pub fn untrack(&mut self, index: usize) -> bool {
+        self.tracked.remove(&index).is_some()
+    }
+
+    /// 节点 `(level, index)` 刚刚完整，填入以它为兄弟的被跟踪路径
+    fn fill_tracked(&mut self, level: usize, index: usize, node: &[u8; 32]) {
+        for (&leaf_index, path) in self.tracked.iter_mut() {
+            if (leaf_index >> level) ^ 1 == index {
+                if path.len() <= level {
+                    path.resize(level + 1, None);
+                }
+                path[level] = Some(*node);
+            }
+        }
+    }
+
+    /// 最后一片叶子在第 0 到 `depth` 层的祖先（未满部分按全 0 子树计算）
+    fn frontier_nodes(&self, depth: usize) -> Vec<[u8; 32]> {
+        let zeros = zero_hashes::<H>(depth);
+        let position = self.count - 1;
+        let mut nodes = Vec::with_capacity(depth + 1);
+        let mut node = self.last;
+        nodes.push(node);
+        for (level, zero) in zeros.iter().enumerate() {
+            node = if (position >> level) & 1 == 1 {
+                merkle_branch::<H>(&self.ommers[level], &node)
+            } else {
+                merkle_branch::<H>(&node, zero)
+            };
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// 根哈希；空树为 `H("")`
+    pub fn root(&self) -> [u8; 32] {
+        if self.count == 0 {
+            return H::hash(&[]);
+        }
+        self.frontier_nodes(self.depth())[self.depth()]
+    }
+
+    /// 生成被跟踪叶子相对当前根的包含证明
+    ///
+    /// # 参数
+    ///
+    /// * `index` - 由 `append_tracked` 返回的叶子序号
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(MerkleProof)`: 形状为 `MerkleLayout::ZeroPad` 的证明，可用 `verify_proof` 验证
+    /// - `Err(Box<dyn Error>)`: 序号越界或该叶子未被跟踪
+    pub fn proof(&self, index: usize) -> Result<MerkleProof, Box<dyn Error>> {
+        if index >= self.count {
+            return Err(merkle_error(format!(
+                "Leaf index {} out of range for {} leaves",
+                index, self.count
+            )));
+        }
+        let path = self
+            .tracked
+            .get(&index)
+            .ok_or_else(|| merkle_error(format!("Leaf {} is not tracked", index)))?;
+
+        let depth = self.depth();
+        let frontier = self.frontier_nodes(depth);
+        let zeros = zero_hashes::<H>(depth);
+        let last = self.count - 1;
+        let siblings = (0..depth)
+            .map(|level| match path.get(level).copied().flatten() {
+                Some(node) => node,
+                // 右侧兄弟尚未完整：包含最后一片叶子时取 frontier 上的部分子树，否则为空子树
+                None if (index >> level) ^ 1 == last >> level => frontier[level],
+                None => zeros[level],
+            })
+            .collect();
+
+        Ok(MerkleProof {
+            layout: MerkleLayout::ZeroPad,
+            leaf_count: self.count,
+            siblings,
+        })
+    }
+}
+
+impl<H: Hasher> Default for IncrementalMerkleTree<H> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
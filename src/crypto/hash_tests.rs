@@ -1,7 +1,7 @@
-
 #[cfg(test)]
-mod hash_tests {
+mod crypto_hash_tests {
     use crate::crypto::hash::*;
+    use crate::crypto::test_util::hex;
 
     #[test]
     fn test_double_sha256() {
@@ -24,7 +24,7 @@ mod hash_tests {
     #[test]
     fn test_merkle_leaf() {
         let data = b"leaf";
-        let leaf_hash = merkle_leaf(data);
+        let leaf_hash = merkle_leaf::<DoubleSha256Hasher>(data);
         assert_eq!(leaf_hash.len(), 32);
     }
 
@@ -32,14 +32,462 @@ mod hash_tests {
     fn test_merkle_branch() {
         let left = [1u8; 32];
         let right = [2u8; 32];
-        let branch_hash = merkle_branch(&left, &right);
+        let branch_hash = merkle_branch::<DoubleSha256Hasher>(&left, &right);
         assert_eq!(branch_hash.len(), 32);
     }
 
     #[test]
     fn test_calculate_merkle_root() {
         let leaves = vec![[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
-        let root = calculate_merkle_root(&leaves);
+        let root = calculate_merkle_root::<DoubleSha256Hasher>(&leaves, MerkleLayout::Rfc6962);
         assert_eq!(root.len(), 32);
     }
+
+    #[test]
+    fn test_hasher_known_vectors() {
+        assert_eq!(
+            Sha256Hasher::hash(b"abc").to_vec(),
+            hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            DoubleSha256Hasher::hash(b"abc").to_vec(),
+            hex("4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358")
+        );
+        // RFC 7693 附录 A 的输入 "abc"，BLAKE2b 输出长度参数为 32
+        assert_eq!(
+            Blake2bHasher::hash(b"abc").to_vec(),
+            hex("bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319")
+        );
+        assert_eq!(
+            Blake2sHasher::hash(b"abc").to_vec(),
+            hex("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+        );
+    }
+
+    fn check_hash_parts<H: Hasher>() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            H::hash_parts(&[&data[..4], &data[4..20], &[], &data[20..]]),
+            H::hash(data),
+            "{}",
+            H::NAME
+        );
+    }
+
+    #[test]
+    fn test_hasher_hash_parts_matches_hash() {
+        check_hash_parts::<Sha256Hasher>();
+        check_hash_parts::<DoubleSha256Hasher>();
+        check_hash_parts::<Blake2bHasher>();
+        check_hash_parts::<Blake2sHasher>();
+    }
+
+    #[test]
+    fn test_merkle_functions_are_generic_over_hasher() {
+        let leaves = [
+            merkle_leaf::<Blake2bHasher>(b"a"),
+            merkle_leaf::<Blake2bHasher>(b"b"),
+            merkle_leaf::<Blake2bHasher>(b"c"),
+        ];
+        assert_eq!(leaves[0].to_vec(), Blake2bHasher::hash(b"\x00a").to_vec());
+
+        let mut branch_input = vec![0x01];
+        branch_input.extend_from_slice(&leaves[0]);
+        branch_input.extend_from_slice(&leaves[1]);
+        let branch = merkle_branch::<Blake2bHasher>(&leaves[0], &leaves[1]);
+        assert_eq!(branch, Blake2bHasher::hash(&branch_input));
+
+        // RFC 6962 形状下奇数个节点时最后一个直接进入上一层
+        let items = [b"a", b"b", b"c"];
+        assert_eq!(
+            calculate_merkle_root::<Blake2bHasher>(&items, MerkleLayout::Rfc6962),
+            merkle_branch::<Blake2bHasher>(&branch, &leaves[2])
+        );
+
+        // 双 SHA-256 保持原有编码
+        let mut prefixed = vec![0x00];
+        prefixed.extend_from_slice(b"a");
+        assert_eq!(
+            merkle_leaf::<DoubleSha256Hasher>(b"a"),
+            double_sha256(&prefixed)
+        );
+
+        // 不同哈希算法得到不同的根
+        let roots = [
+            calculate_merkle_root::<Sha256Hasher>(&items, MerkleLayout::Rfc6962),
+            calculate_merkle_root::<DoubleSha256Hasher>(&items, MerkleLayout::Rfc6962),
+            calculate_merkle_root::<Blake2bHasher>(&items, MerkleLayout::Rfc6962),
+            calculate_merkle_root::<Blake2sHasher>(&items, MerkleLayout::Rfc6962),
+        ];
+        for i in 0..roots.len() {
+            for j in i + 1..roots.len() {
+                assert_ne!(roots[i], roots[j]);
+            }
+        }
+    }
+
+    const LAYOUTS: [MerkleLayout; 3] = [
+        MerkleLayout::Duplicate,
+        MerkleLayout::Rfc6962,
+        MerkleLayout::ZeroPad,
+    ];
+
+    #[test]
+    fn test_merkle_tree_matches_root_and_proves_every_leaf() {
+        for layout in LAYOUTS {
+            for count in 1..=17 {
+                let data: Vec<Vec<u8>> = (0..count).map(|i: u8| vec![i; 3]).collect();
+                let tree = MerkleTree::<Sha256Hasher>::with_layout(&data, layout);
+                let root = tree.root();
+                assert_eq!(root, calculate_merkle_root::<Sha256Hasher>(&data, layout));
+                assert_eq!(tree.leaf_count(), count as usize);
+                assert_eq!(tree.layout(), layout);
+
+                for (index, leaf) in tree.leaves().iter().enumerate() {
+                    assert_eq!(*leaf, merkle_leaf::<Sha256Hasher>(&data[index]));
+                    let proof = tree.proof(index).unwrap();
+                    assert!(proof.siblings.len() <= tree.depth());
+                    assert!(verify_proof::<Sha256Hasher>(&root, leaf, index, &proof));
+
+                    // 错误的位置、叶子、形状或哈希算法都无法通过
+                    let other = (index + 1) % tree.leaf_count();
+                    if other != index {
+                        assert!(!verify_proof::<Sha256Hasher>(&root, leaf, other, &proof));
+                    }
+                    assert!(!verify_proof::<Sha256Hasher>(
+                        &root, &[0u8; 32], index, &proof
+                    ));
+                    if count > 1 {
+                        assert!(!verify_proof::<Blake2bHasher>(&root, leaf, index, &proof));
+                    }
+                }
+                assert!(tree.proof(count as usize).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_merkle_layout_shapes() {
+        let items = [b"a", b"b", b"c"];
+        let [a, b, c] = items.map(|item| merkle_leaf::<Sha256Hasher>(item));
+        let ab = merkle_branch::<Sha256Hasher>(&a, &b);
+
+        assert_eq!(
+            calculate_merkle_root::<Sha256Hasher>(&items, MerkleLayout::Rfc6962),
+            merkle_branch::<Sha256Hasher>(&ab, &c)
+        );
+        assert_eq!(
+            calculate_merkle_root::<Sha256Hasher>(&items, MerkleLayout::Duplicate),
+            merkle_branch::<Sha256Hasher>(&ab, &merkle_branch::<Sha256Hasher>(&c, &c))
+        );
+        assert_eq!(
+            calculate_merkle_root::<Sha256Hasher>(&items, MerkleLayout::ZeroPad),
+            merkle_branch::<Sha256Hasher>(&ab, &merkle_branch::<Sha256Hasher>(&c, &[0u8; 32]))
+        );
+
+        // 叶子数为 2 的幂时三种形状一致
+        let four = [b"a", b"b", b"c", b"d"];
+        let roots = LAYOUTS.map(|layout| calculate_merkle_root::<Sha256Hasher>(&four, layout));
+        assert!(roots.iter().all(|root| *root == roots[0]));
+
+        // 单叶子树的根是叶子域哈希，空树的根是空串的哈希
+        for layout in LAYOUTS {
+            assert_eq!(calculate_merkle_root::<Sha256Hasher>(&[b"a"], layout), a);
+            assert_eq!(
+                calculate_merkle_root::<Sha256Hasher>(&[] as &[&[u8]], layout),
+                Sha256Hasher::hash(b"")
+            );
+        }
+
+        // 补位使证明长度只取决于树高
+        let tree = MerkleTree::<Sha256Hasher>::with_layout(
+            &[b"a", b"b", b"c", b"d", b"e"],
+            MerkleLayout::ZeroPad,
+        );
+        assert_eq!(tree.depth(), 3);
+        for index in 0..5 {
+            assert_eq!(tree.proof(index).unwrap().siblings.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_inner_node_cannot_masquerade_as_leaf() {
+        let items = [b"a", b"b", b"c", b"d"];
+        for layout in LAYOUTS {
+            let tree = MerkleTree::<Sha256Hasher>::with_layout(&items, layout);
+            let ab = merkle_branch::<Sha256Hasher>(&tree.leaves()[0], &tree.leaves()[1]);
+            let cd = merkle_branch::<Sha256Hasher>(&tree.leaves()[2], &tree.leaves()[3]);
+
+            // 把内部节点作为叶子数据重新建树得到不同的根
+            assert_ne!(
+                calculate_merkle_root::<Sha256Hasher>(&[ab, cd], layout),
+                tree.root()
+            );
+            assert_ne!(
+                calculate_merkle_root::<Sha256Hasher>(&[tree.root()], layout),
+                tree.root()
+            );
+
+            // 内部节点经 merkle_leaf 后也无法通过包含证明
+            let shallow = MerkleProof {
+                layout,
+                leaf_count: 2,
+                siblings: vec![cd],
+            };
+            assert!(!verify_proof::<Sha256Hasher>(
+                &tree.root(),
+                &merkle_leaf::<Sha256Hasher>(&ab),
+                0,
+                &shallow
+            ));
+        }
+    }
+
+    #[test]
+    fn test_duplicate_layout_mutation_is_bound_by_leaf_count() {
+        // CVE-2012-2459：复制最后一片叶子不改变根，证明中的叶子数可以区分
+        let three =
+            MerkleTree::<Sha256Hasher>::with_layout(&[b"a", b"b", b"c"], MerkleLayout::Duplicate);
+        let four = MerkleTree::<Sha256Hasher>::with_layout(
+            &[b"a", b"b", b"c", b"c"],
+            MerkleLayout::Duplicate,
+        );
+        assert_eq!(three.root(), four.root());
+        assert_ne!(
+            three.proof(2).unwrap().leaf_count,
+            four.proof(3).unwrap().leaf_count
+        );
+
+        // 其余形状没有这种歧义
+        for layout in [MerkleLayout::Rfc6962, MerkleLayout::ZeroPad] {
+            assert_ne!(
+                calculate_merkle_root::<Sha256Hasher>(&[b"a", b"b", b"c"], layout),
+                calculate_merkle_root::<Sha256Hasher>(&[b"a", b"b", b"c", b"c"], layout)
+            );
+        }
+    }
+
+    #[test]
+    fn test_merkle_proof_rejects_tampering() {
+        let tree = MerkleTree::<Blake2bHasher>::from_data(&[b"a", b"b", b"c", b"d", b"e"]);
+        let root = tree.root();
+        let leaf = tree.leaves()[1];
+        let proof = tree.proof(1).unwrap();
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(proof.siblings.len(), 3);
+
+        let mut flipped = proof.clone();
+        flipped.siblings[1][0] ^= 1;
+        assert!(!verify_proof::<Blake2bHasher>(&root, &leaf, 1, &flipped));
+
+        let mut truncated = proof.clone();
+        truncated.siblings.pop();
+        assert!(!verify_proof::<Blake2bHasher>(&root, &leaf, 1, &truncated));
+
+        let mut extended = proof.clone();
+        extended.siblings.push([0u8; 32]);
+        assert!(!verify_proof::<Blake2bHasher>(&root, &leaf, 1, &extended));
+
+        // 叶子数决定每层是否需要兄弟节点
+        let mut wrong_count = proof.clone();
+        wrong_count.leaf_count = 4;
+        assert!(!verify_proof::<Blake2bHasher>(
+            &root,
+            &leaf,
+            1,
+            &wrong_count
+        ));
+        wrong_count.leaf_count = 1;
+        assert!(!verify_proof::<Blake2bHasher>(
+            &root,
+            &leaf,
+            1,
+            &wrong_count
+        ));
+    }
+
+    #[test]
+    fn test_merkle_proofs_reject_oversized_leaf_count() {
+        let tree =
+            MerkleTree::<Sha256Hasher>::with_layout(&[b"a", b"b", b"c"], MerkleLayout::ZeroPad);
+        let root = tree.root();
+        let leaf = tree.leaves()[0];
+
+        // 补位宽度超出 usize 时验证失败，而不是 panic
+        let mut proof = tree.proof(0).unwrap();
+        proof.leaf_count = usize::MAX;
+        assert!(!verify_proof::<Sha256Hasher>(&root, &leaf, 0, &proof));
+
+        let mut multi = tree.multi_proof(&[0, 2]).unwrap();
+        multi.leaf_count = usize::MAX;
+        assert!(!verify_multi_proof::<Sha256Hasher>(
+            &root,
+            &[leaf, tree.leaves()[2]],
+            &multi
+        ));
+    }
+
+    #[test]
+    fn test_merkle_multi_proof() {
+        for layout in LAYOUTS {
+            check_multi_proof(layout);
+        }
+    }
+
+    fn check_multi_proof(layout: MerkleLayout) {
+        let data: Vec<[u8; 1]> = (0..11u8).map(|i| [i]).collect();
+        let tree = MerkleTree::<Sha256Hasher>::with_layout(&data, layout);
+        let root = tree.root();
+
+        for indices in [
+            vec![0],
+            vec![10],
+            vec![0, 1],
+            vec![3, 4, 9, 10],
+            vec![7, 2, 2, 5],
+        ] {
+            let proof = tree.multi_proof(&indices).unwrap();
+            let leaves: Vec<[u8; 32]> = proof.indices.iter().map(|&i| tree.leaves()[i]).collect();
+            assert!(verify_multi_proof::<Sha256Hasher>(&root, &leaves, &proof));
+
+            // 合并证明不比逐个证明大
+            let separate: usize = proof
+                .indices
+                .iter()
+                .map(|&i| tree.proof(i).unwrap().siblings.len())
+                .sum();
+            assert!(proof.hashes.len() <= separate);
+
+            let mut wrong = leaves.clone();
+            wrong[0][0] ^= 1;
+            assert!(!verify_multi_proof::<Sha256Hasher>(&root, &wrong, &proof));
+        }
+
+        // 相邻叶子共享路径
+        let pair = tree.multi_proof(&[4, 5]).unwrap();
+        assert_eq!(pair.indices, vec![4, 5]);
+        assert_eq!(pair.hashes.len(), tree.proof(4).unwrap().siblings.len() - 1);
+
+        let all: Vec<usize> = (0..11).collect();
+        let full = tree.multi_proof(&all).unwrap();
+        assert_eq!(full.hashes.is_empty(), layout != MerkleLayout::ZeroPad);
+        assert!(verify_multi_proof::<Sha256Hasher>(
+            &root,
+            tree.leaves(),
+            &full
+        ));
+
+        let mut unsorted = tree.multi_proof(&[1, 6]).unwrap();
+        let leaves = [tree.leaves()[1], tree.leaves()[6]];
+        assert!(verify_multi_proof::<Sha256Hasher>(
+            &root, &leaves, &unsorted
+        ));
+        unsorted.indices.reverse();
+        assert!(!verify_multi_proof::<Sha256Hasher>(
+            &root,
+            &[leaves[1], leaves[0]],
+            &unsorted
+        ));
+        assert!(!verify_multi_proof::<Sha256Hasher>(
+            &root,
+            &leaves[..1],
+            &unsorted
+        ));
+
+        assert!(tree.multi_proof(&[]).is_err());
+        assert!(tree.multi_proof(&[3, 11]).is_err());
+    }
+
+    #[test]
+    fn test_merkle_proof_serde_roundtrip() {
+        let tree = MerkleTree::<Sha256Hasher>::from_data(&[b"x", b"y", b"z"]);
+        let proof = tree.proof(2).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: MerkleProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(verify_proof::<Sha256Hasher>(
+            &tree.root(),
+            &tree.leaves()[2],
+            2,
+            &decoded
+        ));
+
+        let multi = tree.multi_proof(&[0, 2]).unwrap();
+        let json = serde_json::to_string(&multi).unwrap();
+        assert_eq!(
+            serde_json::from_str::<MerkleMultiProof>(&json).unwrap(),
+            multi
+        );
+    }
+
+    #[test]
+    fn test_empty_and_single_leaf_trees() {
+        let empty = MerkleTree::<Sha256Hasher>::from_data(&[] as &[&[u8]]);
+        assert_eq!(empty.root(), Sha256Hasher::hash(b""));
+        assert_eq!(empty.depth(), 0);
+        assert!(empty.proof(0).is_err());
+
+        let single = MerkleTree::<Sha256Hasher>::from_data(&[b"only"]);
+        let proof = single.proof(0).unwrap();
+        assert!(proof.siblings.is_empty());
+        assert_eq!(single.root(), single.leaves()[0]);
+        assert!(verify_proof::<Sha256Hasher>(
+            &single.root(),
+            &single.leaves()[0],
+            0,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn test_incremental_tree_matches_zero_pad_root() {
+        let items: Vec<Vec<u8>> = (0..40u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let mut tree = IncrementalMerkleTree::<Sha256Hasher>::new();
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), Sha256Hasher::hash(b""));
+
+        for (count, item) in items.iter().enumerate() {
+            assert_eq!(tree.append(item), count);
+            let expected =
+                MerkleTree::<Sha256Hasher>::with_layout(&items[..count + 1], MerkleLayout::ZeroPad);
+            assert_eq!(tree.len(), count + 1);
+            assert_eq!(tree.depth(), expected.depth());
+            assert_eq!(tree.root(), expected.root());
+        }
+    }
+
+    #[test]
+    fn test_incremental_tree_tracked_proofs() {
+        let items: Vec<Vec<u8>> = (0..21u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let mut tree = IncrementalMerkleTree::<Sha256Hasher>::new();
+
+        // 跟踪偶数序号的叶子，每次追加后所有证明都对当前根成立
+        for (count, item) in items.iter().enumerate() {
+            if count % 2 == 0 {
+                tree.append_tracked(item);
+            } else {
+                tree.append(item);
+            }
+
+            let full =
+                MerkleTree::<Sha256Hasher>::with_layout(&items[..count + 1], MerkleLayout::ZeroPad);
+            for index in (0..=count).step_by(2) {
+                let proof = tree.proof(index).unwrap();
+                assert_eq!(proof, full.proof(index).unwrap());
+                assert!(verify_proof::<Sha256Hasher>(
+                    &tree.root(),
+                    &merkle_leaf::<Sha256Hasher>(&items[index]),
+                    index,
+                    &proof
+                ));
+            }
+        }
+
+        assert!(tree.proof(1).is_err());
+        assert!(tree.proof(items.len()).is_err());
+        assert!(tree.untrack(4));
+        assert!(!tree.untrack(4));
+        assert!(tree.proof(4).is_err());
+        assert!(tree.proof(6).is_ok());
+    }
 }
@@ -0,0 +1,612 @@
+//! 异步加密作业队列
+//!
+//! `RiscVCryptoExt` 的每个 `copr_*` 调用都是同步的，逐个调用会让协处理器卸载串行化。
+//! 本模块在其上提供提交/完成分离的接口：
+//!
+//! - `CryptoJob`：一次 `RiscVCryptoExt` 调用的参数
+//! - `CryptoJobQueue`：提交作业的 trait，队列深度有界，满时 `submit` 阻塞、`try_submit` 报错
+//! - `JobHandle`：作业句柄，可轮询（`try_wait`）、阻塞等待（`wait`）或作为 `Future` 等待
+//! - `ThreadPoolQueue`：工作线程池实现，每个线程持有独立的后端实例（默认 `SoftwareBackend`）
+//!
+//! `run_batch` 在队列深度内流水线提交整批作业并按提交顺序收集结果，
+//! 适合整块交易的签名验证和哈希计算。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::job_queue::{CryptoJob, CryptoJobQueue, ThreadPoolQueue};
+//!
+//! let queue = ThreadPoolQueue::new(4, 64);
+//! let handle = queue.submit(CryptoJob::HashSha256 { input: b"abc".to_vec() }).unwrap();
+//! let digest = handle.wait().unwrap().into_digest().unwrap();
+//! ```
+
+use crate::crypto::backend::BackendFactory;
+use crate::crypto::riscv_ext::{RiscVCryptoExt, SoftwareBackend};
+use crate::crypto::secret::{Aes256Key, Ed25519SecretKey};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+
+/// 作业队列中发生的错误
+///
+/// 后端返回的错误在工作线程中转换为消息字符串，以便跨线程传递。
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JobError {
+    message: String,
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Crypto Job Error: {}", self.message)
+    }
+}
+
+impl Error for JobError {}
+
+fn job_error(message: impl Into<String>) -> JobError {
+    JobError {
+        message: message.into(),
+    }
+}
+
+/// 一次 `RiscVCryptoExt` 调用的参数
+///
+/// 密钥字段使用秘密类型，作业在队列中等待或被 `Debug` 打印时不会泄露密钥。
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CryptoJob {
+    /// `copr_encrypt_aes256`
+    EncryptAes256 {
+        key: Aes256Key,
+        nonce: [u8; 12],
+        aad: Vec<u8>,
+        plaintext: Vec<u8>,
+    },
+    /// `copr_decrypt_aes256`
+    DecryptAes256 {
+        key: Aes256Key,
+        nonce: [u8; 12],
+        aad: Vec<u8>,
+        ciphertext: Vec<u8>,
+        tag: [u8; 16],
+    },
+    /// `copr_aes256_encrypt_block`
+    Aes256EncryptBlock { key: Aes256Key, block: [u8; 16] },
+    /// `copr_aes256_decrypt_block`
+    Aes256DecryptBlock { key: Aes256Key, block: [u8; 16] },
+    /// `copr_hash_sha256`
+    HashSha256 { input: Vec<u8> },
+    /// `copr_sha256_compress`
+    Sha256Compress { state: [u8; 32], block: [u8; 64] },
+    /// `copr_keccak_f1600`
+    KeccakF1600 { state: [u8; 200] },
+    /// `copr_ec_mul`
+    EcMul { scalar: [u8; 32], point: [u8; 32] },
+    /// `copr_ec_add`
+    EcAdd { point1: [u8; 32], point2: [u8; 32] },
+    /// `copr_sign_ed25519`
+    SignEd25519 {
+        secret: Ed25519SecretKey,
+        message: Vec<u8>,
+    },
+    /// `copr_verify_ed25519`
+    VerifyEd25519 {
+        public: [u8; 32],
+        message: Vec<u8>,
+        signature: [u8; 64],
+    },
+    /// `copr_verify_ed25519_batch`，每项为 `(public, message, signature)`
+    VerifyEd25519Batch {
+        items: Vec<([u8; 32], Vec<u8>, [u8; 64])>,
+    },
+    /// `copr_zkp_prove`
+    ZkpProve { witness: Vec<u8> },
+    /// `copr_zkp_verify`
+    ZkpVerify {
+        proof: Vec<u8>,
+        public_input: Vec<u8>,
+    },
+    /// `copr_msm_accelerate`
+    Msm {
+        points: Vec<Vec<u8>>,
+        scalars: Vec<Vec<u8>>,
+    },
+}
+
+#[allow(dead_code)]
+impl CryptoJob {
+    /// 在指定后端上同步执行作业
+    ///
+    /// # 参数
+    ///
+    /// * `backend` - 执行运算的后端
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(JobOutput)`: 与作业类型对应的输出
+    /// - `Err(Box<dyn Error>)`: 后端返回的错误
+    pub fn execute(&self, backend: &dyn RiscVCryptoExt) -> Result<JobOutput, Box<dyn Error>> {
+        let output = match self {
+            CryptoJob::EncryptAes256 {
+                key,
+                nonce,
+                aad,
+                plaintext,
+            } => {
+                let (ciphertext, tag) =
+                    backend.copr_encrypt_aes256(key.expose_secret(), nonce, aad, plaintext)?;
+                JobOutput::Sealed(ciphertext, tag)
+            }
+            CryptoJob::DecryptAes256 {
+                key,
+                nonce,
+                aad,
+                ciphertext,
+                tag,
+            } => JobOutput::Bytes(backend.copr_decrypt_aes256(
+                key.expose_secret(),
+                nonce,
+                aad,
+                ciphertext,
+                tag,
+            )?),
+            CryptoJob::Aes256EncryptBlock { key, block } => {
+                JobOutput::Block(backend.copr_aes256_encrypt_block(key.expose_secret(), block)?)
+            }
+            CryptoJob::Aes256DecryptBlock { key, block } => {
+                JobOutput::Block(backend.copr_aes256_decrypt_block(key.expose_secret(), block)?)
+            }
+            CryptoJob::HashSha256 { input } => JobOutput::Digest(backend.copr_hash_sha256(input)?),
+            CryptoJob::Sha256Compress { state, block } => {
+                JobOutput::Digest(backend.copr_sha256_compress(state, block)?)
+            }
+            CryptoJob::KeccakF1600 { state } => {
+                JobOutput::Bytes(backend.copr_keccak_f1600(state)?.to_vec())
+            }
+            CryptoJob::EcMul { scalar, point } => {
+                JobOutput::Digest(backend.copr_ec_mul(scalar, point)?)
+            }
+            CryptoJob::EcAdd { point1, point2 } => {
+                JobOutput::Digest(backend.copr_ec_add(point1, point2)?)
+            }
+            CryptoJob::SignEd25519 { secret, message } => {
+                JobOutput::Signature(backend.copr_sign_ed25519(secret.expose_secret(), message)?)
+            }
+            CryptoJob::VerifyEd25519 {
+                public,
+                message,
+                signature,
+            } => JobOutput::Bool(backend.copr_verify_ed25519(public, message, signature)?),
+            CryptoJob::VerifyEd25519Batch { items } => {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|(public, message, signature)| (*public, message.as_slice(), *signature))
+                    .collect();
+                JobOutput::Bools(backend.copr_verify_ed25519_batch(&items)?)
+            }
+            CryptoJob::ZkpProve { witness } => JobOutput::Bytes(backend.copr_zkp_prove(witness)?),
+            CryptoJob::ZkpVerify {
+                proof,
+                public_input,
+            } => JobOutput::Bool(backend.copr_zkp_verify(proof, public_input)?),
+            CryptoJob::Msm { points, scalars } => {
+                JobOutput::Bytes(backend.copr_msm_accelerate(points, scalars)?)
+            }
+        };
+        Ok(output)
+    }
+}
+
+/// 作业输出
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JobOutput {
+    /// 变长字节（解密明文、Keccak 置换状态、证明、MSM 结果）
+    Bytes(Vec<u8>),
+    /// GCM 密文和标签
+    Sealed(Vec<u8>, [u8; 16]),
+    /// 16 字节分组
+    Block([u8; 16]),
+    /// 32 字节值（哈希、压缩状态、曲线点）
+    Digest([u8; 32]),
+    /// Ed25519 签名
+    Signature([u8; 64]),
+    /// 验证结果
+    Bool(bool),
+    /// 批量验证结果
+    Bools(Vec<bool>),
+}
+
+#[allow(dead_code)]
+impl JobOutput {
+    /// 取出 32 字节值，类型不符时返回 `None`
+    pub fn into_digest(self) -> Option<[u8; 32]> {
+        match self {
+            JobOutput::Digest(digest) => Some(digest),
+            _ => None,
+        }
+    }
+
+    /// 取出签名，类型不符时返回 `None`
+    pub fn into_signature(self) -> Option<[u8; 64]> {
+        match self {
+            JobOutput::Signature(signature) => Some(signature),
+            _ => None,
+        }
+    }
+
+    /// 取出验证结果，类型不符时返回 `None`
+    pub fn into_bool(self) -> Option<bool> {
+        match self {
+            JobOutput::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// 作业结果槽，由工作线程填写、句柄读取
+#[derive(Default)]
+struct JobSlot {
+    state: Mutex<SlotState>,
+    done: Condvar,
+}
+
+#[derive(Default)]
+struct SlotState {
+    result: Option<Result<JobOutput, JobError>>,
+    taken: bool,
+    waker: Option<Waker>,
+}
+
+impl SlotState {
+    /// 取出结果；已被取走时返回错误而不是永远等待
+    fn take(&mut self) -> Option<Result<JobOutput, JobError>> {
+        if let Some(result) = self.result.take() {
+            self.taken = true;
+            Some(result)
+        } else if self.taken {
+            Some(Err(job_error("Job result already taken")))
+        } else {
+            None
+        }
+    }
+}
+
+impl JobSlot {
+    fn lock(&self) -> std::sync::MutexGuard<'_, SlotState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 作业完成端
+///
+/// 未调用 `complete` 就被丢弃时（队列关闭、工作线程 panic），
+/// 以错误结束作业，等待方不会永久阻塞。
+#[allow(dead_code)]
+pub struct JobCompleter {
+    slot: Option<Arc<JobSlot>>,
+}
+
+#[allow(dead_code)]
+impl JobCompleter {
+    /// 写入结果并唤醒等待方
+    pub fn complete(mut self, result: Result<JobOutput, JobError>) {
+        if let Some(slot) = self.slot.take() {
+            Self::finish(&slot, result);
+        }
+    }
+
+    /// 不写入结果地丢弃完成端
+    fn discard(mut self) {
+        self.slot.take();
+    }
+
+    fn finish(slot: &JobSlot, result: Result<JobOutput, JobError>) {
+        let waker = {
+            let mut state = slot.lock();
+            state.result = Some(result);
+            state.waker.take()
+        };
+        slot.done.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for JobCompleter {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            Self::finish(&slot, Err(job_error("Job dropped before completion")));
+        }
+    }
+}
+
+/// 已提交作业的句柄
+#[allow(dead_code)]
+pub struct JobHandle {
+    slot: Arc<JobSlot>,
+}
+
+impl fmt::Debug for JobHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JobHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+#[allow(dead_code)]
+impl JobHandle {
+    /// 创建一对未完成的句柄和完成端
+    ///
+    /// 供 `CryptoJobQueue` 的实现使用。
+    pub fn pair() -> (JobHandle, JobCompleter) {
+        let slot = Arc::new(JobSlot::default());
+        (
+            JobHandle {
+                slot: Arc::clone(&slot),
+            },
+            JobCompleter { slot: Some(slot) },
+        )
+    }
+
+    /// 作业是否已完成
+    pub fn is_finished(&self) -> bool {
+        let state = self.slot.lock();
+        state.result.is_some() || state.taken
+    }
+
+    /// 非阻塞地取结果
+    ///
+    /// # 返回
+    ///
+    /// - `None`: 作业尚未完成
+    /// - `Some(result)`: 作业结果；结果只能取出一次，之后再取返回错误
+    pub fn try_wait(&mut self) -> Option<Result<JobOutput, Box<dyn Error>>> {
+        self.slot
+            .lock()
+            .take()
+            .map(|result| result.map_err(|e| e.into()))
+    }
+
+    /// 阻塞等待作业完成
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(JobOutput)`: 作业输出
+    /// - `Err(Box<dyn Error>)`: 后端错误或作业被丢弃
+    pub fn wait(self) -> Result<JobOutput, Box<dyn Error>> {
+        let mut state = self.slot.lock();
+        loop {
+            if let Some(result) = state.take() {
+                return result.map_err(|e| e.into());
+            }
+            state = self
+                .slot
+                .done
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl Future for JobHandle {
+    type Output = Result<JobOutput, JobError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.slot.lock();
+        match state.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// 加密作业队列
+///
+/// 实现者负责在有界队列中排队作业并在完成时通过 `JobCompleter` 写回结果。
+#[allow(dead_code)]
+pub trait CryptoJobQueue {
+    /// 提交作业，队列已满时阻塞直到有空位
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(JobHandle)`: 作业句柄
+    /// - `Err(Box<dyn Error>)`: 队列已关闭
+    fn submit(&self, job: CryptoJob) -> Result<JobHandle, Box<dyn Error>>;
+
+    /// 提交作业，队列已满时立即返回错误
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(JobHandle)`: 作业句柄
+    /// - `Err(Box<dyn Error>)`: 队列已满或已关闭
+    fn try_submit(&self, job: CryptoJob) -> Result<JobHandle, Box<dyn Error>>;
+
+    /// 队列深度上限（等待执行的作业数）
+    fn capacity(&self) -> usize;
+}
+
+/// 在队列深度内流水线执行一批作业
+///
+/// 维持最多 `queue.capacity()` 个在途作业，按提交顺序返回每个作业的结果；
+/// 单个作业失败不影响其他作业。
+///
+/// # 参数
+///
+/// * `queue` - 作业队列
+/// * `jobs` - 作业列表
+///
+/// # 返回
+///
+/// - `Ok(Vec<Result<JobOutput, JobError>>)`: 与 `jobs` 一一对应的结果
+/// - `Err(Box<dyn Error>)`: 队列已关闭
+#[allow(dead_code)]
+pub fn run_batch<Q: CryptoJobQueue + ?Sized>(
+    queue: &Q,
+    jobs: impl IntoIterator<Item = CryptoJob>,
+) -> Result<Vec<Result<JobOutput, JobError>>, Box<dyn Error>> {
+    let window = queue.capacity().max(1);
+    let mut in_flight = VecDeque::with_capacity(window);
+    let mut results = Vec::new();
+
+    let collect = |handle: JobHandle| {
+        handle.wait().map_err(|e| JobError {
+            message: e.to_string(),
+        })
+    };
+
+    for job in jobs {
+        if in_flight.len() == window {
+            results.push(collect(in_flight.pop_front().unwrap()));
+        }
+        in_flight.push_back(queue.submit(job)?);
+    }
+    results.extend(in_flight.into_iter().map(collect));
+    Ok(results)
+}
+
+type QueuedJob = (CryptoJob, JobCompleter);
+
+/// 工作线程池作业队列
+///
+/// 作业放入深度为 `depth` 的有界通道，由 `workers` 个线程取出执行。
+/// 每个线程启动时调用一次后端工厂，因此后端实例不需要跨线程共享。
+/// 丢弃队列时等待已排队的作业执行完毕。
+#[allow(dead_code)]
+pub struct ThreadPoolQueue {
+    sender: Option<SyncSender<QueuedJob>>,
+    workers: Vec<JoinHandle<()>>,
+    depth: usize,
+}
+
+impl fmt::Debug for ThreadPoolQueue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ThreadPoolQueue")
+            .field("workers", &self.workers.len())
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+#[allow(dead_code)]
+impl ThreadPoolQueue {
+    /// 创建使用 `SoftwareBackend` 的线程池
+    ///
+    /// # 参数
+    ///
+    /// * `workers` - 工作线程数（至少为 1）
+    /// * `depth` - 队列深度上限（至少为 1）
+    pub fn new(workers: usize, depth: usize) -> Self {
+        Self::with_backend(workers, depth, Arc::new(|| Ok(Box::new(SoftwareBackend))))
+    }
+
+    /// 创建使用指定后端的线程池
+    ///
+    /// 工厂在某个线程中失败时，该线程取到的作业都以该错误结束。
+    ///
+    /// # 参数
+    ///
+    /// * `workers` - 工作线程数（至少为 1）
+    /// * `depth` - 队列深度上限（至少为 1）
+    /// * `factory` - 为每个工作线程创建后端实例
+    pub fn with_backend(workers: usize, depth: usize, factory: BackendFactory) -> Self {
+        let depth = depth.max(1);
+        let (sender, receiver) = mpsc::sync_channel::<QueuedJob>(depth);
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..workers.max(1))
+            .map(|index| {
+                let receiver = Arc::clone(&receiver);
+                let factory = Arc::clone(&factory);
+                thread::Builder::new()
+                    .name(format!("crypto-job-{}", index))
+                    .spawn(move || Self::worker(&receiver, &factory))
+                    .expect("failed to spawn crypto job worker")
+            })
+            .collect();
+
+        ThreadPoolQueue {
+            sender: Some(sender),
+            workers,
+            depth,
+        }
+    }
+
+    /// 工作线程数
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    fn worker(receiver: &Mutex<Receiver<QueuedJob>>, factory: &BackendFactory) {
+        let backend = factory().map_err(|e| job_error(e.to_string()));
+        loop {
+            // 只在取作业时持锁，执行期间其他线程可以继续取
+            let next = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+            let Ok((job, completer)) = next else {
+                return;
+            };
+            let result = match &backend {
+                Ok(backend) => job
+                    .execute(backend.as_ref())
+                    .map_err(|e| job_error(e.to_string())),
+                Err(e) => Err(e.clone()),
+            };
+            completer.complete(result);
+        }
+    }
+
+    fn sender(&self) -> Result<&SyncSender<QueuedJob>, Box<dyn Error>> {
+        self.sender
+            .as_ref()
+            .ok_or_else(|| job_error("Queue is shut down").into())
+    }
+}
+
+impl CryptoJobQueue for ThreadPoolQueue {
+    fn submit(&self, job: CryptoJob) -> Result<JobHandle, Box<dyn Error>> {
+        let (handle, completer) = JobHandle::pair();
+        self.sender()?
+            .send((job, completer))
+            .map_err(|_| job_error("Queue is shut down"))?;
+        Ok(handle)
+    }
+
+    fn try_submit(&self, job: CryptoJob) -> Result<JobHandle, Box<dyn Error>> {
+        let (handle, completer) = JobHandle::pair();
+        match self.sender()?.try_send((job, completer)) {
+            Ok(()) => Ok(handle),
+            Err(TrySendError::Full((_, completer))) => {
+                // 作业未入队，句柄随即丢弃，无需通知
+                completer.discard();
+                Err(job_error(format!("Queue is full (depth {})", self.depth)).into())
+            }
+            Err(TrySendError::Disconnected(_)) => Err(job_error("Queue is shut down").into()),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.depth
+    }
+}
+
+impl Drop for ThreadPoolQueue {
+    fn drop(&mut self) {
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod crypto_job_queue_tests {
+    use crate::crypto::ec::software;
+    use crate::crypto::job_queue::{
+        CryptoJob, CryptoJobQueue, JobHandle, JobOutput, ThreadPoolQueue, run_batch,
+    };
+    use crate::crypto::mock::{MockAccelerator, MockOperation};
+    use crate::crypto::riscv_ext::{RiscVCryptoExt, SoftwareBackend};
+    use crate::crypto::secret::{Aes256Key, Ed25519SecretKey};
+    use crate::crypto::sha256::Sha256Riscv;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+    use std::time::Duration;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// 最小的单线程执行器
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_job_execute_matches_backend() {
+        let job = CryptoJob::Aes256EncryptBlock {
+            key: Aes256Key::new([1u8; 32]),
+            block: [2u8; 16],
+        };
+        assert_eq!(
+            job.execute(&SoftwareBackend).unwrap(),
+            JobOutput::Block(
+                SoftwareBackend
+                    .copr_aes256_encrypt_block(&[1u8; 32], &[2u8; 16])
+                    .unwrap()
+            )
+        );
+        assert_eq!(JobOutput::Bool(true).into_digest(), None);
+    }
+
+    #[test]
+    fn test_submit_wait_poll_and_await() {
+        let queue = ThreadPoolQueue::new(2, 8);
+        assert_eq!(queue.workers(), 2);
+        assert_eq!(queue.capacity(), 8);
+
+        let handle = queue
+            .submit(CryptoJob::HashSha256 {
+                input: b"abc".to_vec(),
+            })
+            .unwrap();
+        assert_eq!(
+            handle.wait().unwrap().into_digest(),
+            Some(Sha256Riscv::hash(b"abc"))
+        );
+
+        let mut handle = queue
+            .submit(CryptoJob::HashSha256 {
+                input: b"poll".to_vec(),
+            })
+            .unwrap();
+        let result = loop {
+            if let Some(result) = handle.try_wait() {
+                break result;
+            }
+            thread::yield_now();
+        };
+        assert_eq!(
+            result.unwrap().into_digest(),
+            Some(Sha256Riscv::hash(b"poll"))
+        );
+        assert!(handle.is_finished());
+        assert!(handle.try_wait().unwrap().is_err());
+
+        let handle = queue
+            .submit(CryptoJob::HashSha256 {
+                input: b"await".to_vec(),
+            })
+            .unwrap();
+        assert_eq!(
+            block_on(handle).unwrap().into_digest(),
+            Some(Sha256Riscv::hash(b"await"))
+        );
+    }
+
+    #[test]
+    fn test_try_submit_respects_queue_depth() {
+        let mock = MockAccelerator::new().with_latency(Duration::from_millis(200));
+        let queue =
+            ThreadPoolQueue::with_backend(1, 1, Arc::new(move || Ok(Box::new(mock.clone()))));
+        let job = || CryptoJob::HashSha256 {
+            input: b"x".to_vec(),
+        };
+
+        // 第一个作业占住工作线程，第二个占满深度为 1 的队列
+        let first = queue.submit(job()).unwrap();
+        let second = loop {
+            if let Ok(handle) = queue.try_submit(job()) {
+                break handle;
+            }
+            thread::yield_now();
+        };
+        let err = queue.try_submit(job()).unwrap_err();
+        assert!(err.to_string().contains("full"));
+
+        assert!(first.wait().is_ok());
+        assert!(second.wait().is_ok());
+    }
+
+    #[test]
+    fn test_run_batch_verifies_and_hashes_in_order() {
+        let queue = ThreadPoolQueue::new(4, 4);
+        let secret = [7u8; 32];
+        let public = software::public_key(&secret);
+
+        let mut jobs = Vec::new();
+        for i in 0..32u8 {
+            let message = vec![i; 40];
+            let mut signature = software::sign(&secret, &message);
+            if i % 5 == 0 {
+                signature[0] ^= 1;
+            }
+            jobs.push(CryptoJob::VerifyEd25519 {
+                public,
+                message: message.clone(),
+                signature,
+            });
+            jobs.push(CryptoJob::HashSha256 { input: message });
+        }
+
+        let results = run_batch(&queue, jobs).unwrap();
+        assert_eq!(results.len(), 64);
+        for (i, pair) in results.chunks(2).enumerate() {
+            assert_eq!(
+                pair[0].clone().unwrap().into_bool(),
+                Some(i % 5 != 0),
+                "signature {}",
+                i
+            );
+            assert_eq!(
+                pair[1].clone().unwrap().into_digest(),
+                Some(Sha256Riscv::hash(&[i as u8; 40]))
+            );
+        }
+    }
+
+    #[test]
+    fn test_backend_errors_are_reported_per_job() {
+        let mock = MockAccelerator::new().fail_operation(MockOperation::SignEd25519);
+        let factory_mock = mock.clone();
+        let queue = ThreadPoolQueue::with_backend(
+            2,
+            4,
+            Arc::new(move || Ok(Box::new(factory_mock.clone()))),
+        );
+
+        let results = run_batch(
+            &queue,
+            vec![
+                CryptoJob::SignEd25519 {
+                    secret: Ed25519SecretKey::new([1u8; 32]),
+                    message: b"m".to_vec(),
+                },
+                CryptoJob::HashSha256 {
+                    input: b"m".to_vec(),
+                },
+            ],
+        )
+        .unwrap();
+        assert!(results[0].is_err());
+        assert!(results[1].is_ok());
+        assert_eq!(mock.call_count(MockOperation::SignEd25519), 1);
+
+        let failing = ThreadPoolQueue::with_backend(1, 1, Arc::new(|| Err("no device".into())));
+        let err = failing
+            .submit(CryptoJob::HashSha256 { input: vec![] })
+            .unwrap()
+            .wait()
+            .unwrap_err();
+        assert!(err.to_string().contains("no device"));
+    }
+
+    #[test]
+    fn test_dropped_completer_fails_handle() {
+        let (handle, completer) = JobHandle::pair();
+        drop(completer);
+        assert!(handle.wait().is_err());
+    }
+}
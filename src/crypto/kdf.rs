@@ -0,0 +1,485 @@
+//! 密钥派生模块
+//!
+//! 从单个根秘密派生各用途的独立密钥（密封密钥、存储密钥、会话密钥、确定性账户密钥）。
+//! 全部构造都建立在 `Sha256Riscv` 之上，因此会随 SHA-256 一起使用所选的加速器后端：
+//!
+//! - `HmacSha256`：RFC 2104 HMAC-SHA256
+//! - `Hkdf`：RFC 5869 HKDF-SHA256（Extract / Expand）
+//! - `DerivationPath` / `ExtendedKey`：与 SLIP-10 Ed25519 思路相同的分层派生，
+//!   只支持强化（hardened）子密钥，父节点的公开信息无法推导子密钥
+//!
+//! # 分层派生
+//!
+//! SLIP-10 使用 HMAC-SHA512 一次得到 64 字节；这里用 HKDF-SHA256 输出 64 字节代替：
+//!
+//! - 主节点：`I = HKDF(salt = "zk-rollup hd seed", ikm = seed, info = "master", 64)`
+//! - 子节点：`I = HKDF-Expand(prk = 父链码, info = 0x00 || 父私钥 || ser32(index | 2^31), 64)`
+//!
+//! `I` 的前 32 字节为节点私钥（即 RFC 8032 私钥种子），后 32 字节为链码。
+//! 该派生与 SLIP-10 的输出不兼容，不能与外部钱包互换路径。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::ec::Ed25519Riscv;
+//! use crypto::kdf::{DerivationPath, ExtendedKey, Hkdf};
+//!
+//! let root = ExtendedKey::from_seed(&[7u8; 32]).unwrap();
+//! let path: DerivationPath = "m/0'/42'".parse().unwrap();
+//! let account = root.derive_path(&path).unwrap();
+//! let signature = Ed25519Riscv::new().sign(account.secret_key(), b"tx").unwrap();
+//!
+//! let sealing_key = Hkdf::new(b"salt", b"root secret")
+//!     .expand_key::<32>(b"tee sealing")
+//!     .unwrap();
+//! ```
+
+use crate::crypto::secret::{Ed25519SecretKey, SecretBytes, SecretVec};
+use crate::crypto::sha256::Sha256Riscv;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// 密钥派生过程中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct KdfError {
+    message: String,
+}
+
+impl fmt::Display for KdfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KDF Error: {}", self.message)
+    }
+}
+
+impl Error for KdfError {}
+
+fn kdf_error(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(KdfError {
+        message: message.into(),
+    })
+}
+
+/// HMAC-SHA256 输出长度（字节）
+pub const HMAC_SHA256_SIZE: usize = 32;
+/// SHA-256 分组长度，即 HMAC 的密钥块长度
+const HMAC_BLOCK_SIZE: usize = 64;
+/// HKDF-SHA256 单次 Expand 的最大输出长度（255 * HashLen）
+pub const HKDF_MAX_OUTPUT: usize = 255 * HMAC_SHA256_SIZE;
+/// 强化子密钥索引的最高位
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// 主节点派生使用的 HKDF salt
+const MASTER_SALT: &[u8] = b"zk-rollup hd seed";
+/// 主种子最短长度（128 位，与 SLIP-10 相同）
+const MIN_SEED_LEN: usize = 16;
+
+/// HMAC-SHA256 计算器（RFC 2104）
+///
+/// `HMAC(K, m) = H((K' ^ opad) || H((K' ^ ipad) || m))`，其中 `K'` 为补零到 64 字节的密钥，
+/// 长于 64 字节的密钥先做一次 SHA-256。
+#[allow(dead_code)]
+pub struct HmacSha256 {
+    inner: Sha256Riscv,
+    outer_key: SecretBytes<HMAC_BLOCK_SIZE>,
+}
+
+#[allow(dead_code)]
+impl HmacSha256 {
+    /// 创建 HMAC-SHA256 计算器
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 任意长度的 MAC 密钥
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; HMAC_BLOCK_SIZE];
+        if key.len() > HMAC_BLOCK_SIZE {
+            block[..HMAC_SHA256_SIZE].copy_from_slice(&Sha256Riscv::hash(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner_key = block.map(|byte| byte ^ 0x36);
+        let outer_key = SecretBytes::new(block.map(|byte| byte ^ 0x5c));
+        let mut inner = Sha256Riscv::new();
+        inner.update(&inner_key);
+        block.zeroize();
+        inner_key.zeroize();
+
+        HmacSha256 { inner, outer_key }
+    }
+
+    /// 追加消息数据
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// 完成计算并返回 32 字节 MAC
+    pub fn finalize(self) -> [u8; HMAC_SHA256_SIZE] {
+        let inner_hash = self.inner.finalize();
+        let mut outer = Sha256Riscv::new();
+        outer.update(self.outer_key.expose_secret());
+        outer.update(&inner_hash);
+        outer.finalize()
+    }
+
+    /// 一次性计算 HMAC-SHA256
+    ///
+    /// # 参数
+    ///
+    /// * `key` - MAC 密钥
+    /// * `data` - 消息
+    ///
+    /// # 返回
+    ///
+    /// 32 字节 MAC
+    pub fn mac(key: &[u8], data: &[u8]) -> [u8; HMAC_SHA256_SIZE] {
+        let mut hmac = Self::new(key);
+        hmac.update(data);
+        hmac.finalize()
+    }
+
+    /// 以常数时间校验 MAC
+    ///
+    /// 允许 RFC 2104 第 5 节的截断标签，但不短于 16 字节。
+    ///
+    /// # 参数
+    ///
+    /// * `key` - MAC 密钥
+    /// * `data` - 消息
+    /// * `tag` - 待校验的标签，长度 16 到 32 字节
+    ///
+    /// # 返回
+    ///
+    /// 标签正确时返回 `true`；标签长度不合法或不匹配时返回 `false`
+    pub fn verify(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+        if tag.len() < HMAC_SHA256_SIZE / 2 || tag.len() > HMAC_SHA256_SIZE {
+            return false;
+        }
+        let expected = Self::mac(key, data);
+        expected[..tag.len()].ct_eq(tag).into()
+    }
+}
+
+/// HKDF-SHA256（RFC 5869）
+///
+/// 持有 Extract 阶段得到的伪随机密钥（PRK），可多次 Expand 出不同用途的密钥。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct Hkdf {
+    prk: SecretBytes<HMAC_SHA256_SIZE>,
+}
+
+#[allow(dead_code)]
+impl Hkdf {
+    /// HKDF-Extract：`PRK = HMAC(salt, ikm)`
+    ///
+    /// # 参数
+    ///
+    /// * `salt` - 可选的非秘密盐值，为空时按 RFC 5869 使用 32 个零字节
+    /// * `ikm` - 输入密钥材料
+    pub fn new(salt: &[u8], ikm: &[u8]) -> Self {
+        let salt = if salt.is_empty() {
+            &[0u8; HMAC_SHA256_SIZE][..]
+        } else {
+            salt
+        };
+        Hkdf {
+            prk: SecretBytes::new(HmacSha256::mac(salt, ikm)),
+        }
+    }
+
+    /// 跳过 Extract，直接使用已是均匀随机的 PRK
+    ///
+    /// # 参数
+    ///
+    /// * `prk` - 32 字节伪随机密钥
+    pub fn from_prk(prk: SecretBytes<HMAC_SHA256_SIZE>) -> Self {
+        Hkdf { prk }
+    }
+
+    /// Extract 阶段得到的伪随机密钥
+    pub fn prk(&self) -> &SecretBytes<HMAC_SHA256_SIZE> {
+        &self.prk
+    }
+
+    /// HKDF-Expand：`T(i) = HMAC(PRK, T(i-1) || info || i)`，输出 `T(1) || T(2) || ...` 的前 `len` 字节
+    ///
+    /// # 参数
+    ///
+    /// * `info` - 上下文与用途标签，不同用途必须使用不同的 `info`
+    /// * `len` - 输出长度
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecretVec)`: 派生出的密钥材料
+    /// - `Err(Box<dyn Error>)`: `len` 超过 255 * 32 字节
+    pub fn expand(&self, info: &[u8], len: usize) -> Result<SecretVec, Box<dyn Error>> {
+        if len > HKDF_MAX_OUTPUT {
+            return Err(kdf_error(format!(
+                "Output length {} exceeds {}",
+                len, HKDF_MAX_OUTPUT
+            )));
+        }
+
+        let mut okm = Vec::with_capacity(len);
+        let mut previous = [0u8; HMAC_SHA256_SIZE];
+        let mut counter = 1u8;
+        while okm.len() < len {
+            let mut hmac = HmacSha256::new(self.prk.expose_secret());
+            if counter > 1 {
+                hmac.update(&previous);
+            }
+            hmac.update(info);
+            hmac.update(&[counter]);
+            previous = hmac.finalize();
+
+            let take = (len - okm.len()).min(HMAC_SHA256_SIZE);
+            okm.extend_from_slice(&previous[..take]);
+            counter = counter.wrapping_add(1);
+        }
+        previous.zeroize();
+
+        Ok(SecretVec::new(okm))
+    }
+
+    /// 派生定长密钥
+    ///
+    /// # 参数
+    ///
+    /// * `info` - 上下文与用途标签
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecretBytes<N>)`: `N` 字节密钥，如 `Aes256Key`
+    /// - `Err(Box<dyn Error>)`: `N` 超过 255 * 32 字节
+    pub fn expand_key<const N: usize>(
+        &self,
+        info: &[u8],
+    ) -> Result<SecretBytes<N>, Box<dyn Error>> {
+        SecretBytes::from_slice(self.expand(info, N)?.expose_secret())
+    }
+}
+
+/// 分层派生路径，如 `m/44'/0'/1'`
+///
+/// 只包含强化索引；字符串形式中每一级必须带 `'` 或 `h` 后缀。
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DerivationPath {
+    indices: Vec<u32>,
+}
+
+#[allow(dead_code)]
+impl DerivationPath {
+    /// 根路径 `m`
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// 追加一级强化子索引
+    ///
+    /// # 参数
+    ///
+    /// * `index` - 不含强化位的子索引，必须小于 2^31
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(DerivationPath)`: 新路径
+    /// - `Err(Box<dyn Error>)`: 索引超出范围
+    pub fn child(&self, index: u32) -> Result<Self, Box<dyn Error>> {
+        if index >= HARDENED_OFFSET {
+            return Err(kdf_error(format!("Child index {} out of range", index)));
+        }
+        let mut indices = self.indices.clone();
+        indices.push(index | HARDENED_OFFSET);
+        Ok(DerivationPath { indices })
+    }
+
+    /// 路径中各级的索引（已包含强化位）
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(kdf_error(format!("Path must start with 'm': {}", s)));
+        }
+
+        let mut path = DerivationPath::root();
+        for part in parts {
+            let Some(index) = part.strip_suffix('\'').or_else(|| part.strip_suffix('h')) else {
+                return Err(kdf_error(format!(
+                    "Only hardened derivation is supported: {}",
+                    part
+                )));
+            };
+            let index: u32 = index
+                .parse()
+                .map_err(|_| kdf_error(format!("Invalid path component: {}", part)))?;
+            path = path.child(index)?;
+        }
+        Ok(path)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.indices {
+            write!(f, "/{}'", index & !HARDENED_OFFSET)?;
+        }
+        Ok(())
+    }
+}
+
+/// 分层派生树中的一个节点
+///
+/// 私钥可直接作为 Ed25519 私钥签名，也可经 `derive_key` 派生其他用途的密钥。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct ExtendedKey {
+    secret_key: Ed25519SecretKey,
+    chain_code: SecretBytes<32>,
+    depth: u8,
+    child_number: u32,
+}
+
+#[allow(dead_code)]
+impl ExtendedKey {
+    /// 由主种子生成根节点
+    ///
+    /// # 参数
+    ///
+    /// * `seed` - 至少 16 字节的高熵种子
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(ExtendedKey)`: 深度为 0 的根节点
+    /// - `Err(Box<dyn Error>)`: 种子过短
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if seed.len() < MIN_SEED_LEN {
+            return Err(kdf_error(format!(
+                "Seed must be at least {} bytes",
+                MIN_SEED_LEN
+            )));
+        }
+        let okm = Hkdf::new(MASTER_SALT, seed).expand(b"master", 64)?;
+        Self::from_okm(&okm, 0, 0)
+    }
+
+    /// 把 64 字节派生输出拆分为私钥与链码
+    fn from_okm(okm: &SecretVec, depth: u8, child_number: u32) -> Result<Self, Box<dyn Error>> {
+        let (secret_key, chain_code) = okm.expose_secret().split_at(32);
+        Ok(ExtendedKey {
+            secret_key: SecretBytes::from_slice(secret_key)?,
+            chain_code: SecretBytes::from_slice(chain_code)?,
+            depth,
+            child_number,
+        })
+    }
+
+    /// 派生强化子节点
+    ///
+    /// # 参数
+    ///
+    /// * `index` - 不含强化位的子索引，必须小于 2^31
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(ExtendedKey)`: 子节点
+    /// - `Err(Box<dyn Error>)`: 索引超出范围或深度超过 255
+    pub fn derive_child(&self, index: u32) -> Result<Self, Box<dyn Error>> {
+        if index >= HARDENED_OFFSET {
+            return Err(kdf_error(format!("Child index {} out of range", index)));
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or_else(|| kdf_error("Derivation depth exceeds 255"))?;
+        let child_number = index | HARDENED_OFFSET;
+
+        let mut info = [0u8; 37];
+        info[1..33].copy_from_slice(self.secret_key.expose_secret());
+        info[33..].copy_from_slice(&child_number.to_be_bytes());
+        let okm = Hkdf::from_prk(self.chain_code.clone()).expand(&info, 64);
+        info.zeroize();
+
+        Self::from_okm(&okm?, depth, child_number)
+    }
+
+    /// 沿路径逐级派生
+    ///
+    /// # 参数
+    ///
+    /// * `path` - 相对当前节点的派生路径
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(ExtendedKey)`: 路径末端的节点
+    /// - `Err(Box<dyn Error>)`: 深度超过 255
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Box<dyn Error>> {
+        let mut node = self.clone();
+        for index in path.indices() {
+            node = node.derive_child(index & !HARDENED_OFFSET)?;
+        }
+        Ok(node)
+    }
+
+    /// 节点私钥，可直接用于 `Ed25519Riscv::sign`
+    pub fn secret_key(&self) -> &Ed25519SecretKey {
+        &self.secret_key
+    }
+
+    /// 节点私钥对应的 Ed25519 公钥
+    pub fn public_key(&self) -> [u8; 32] {
+        crate::crypto::ec::software::public_key(self.secret_key.expose_secret())
+    }
+
+    /// 节点链码
+    pub fn chain_code(&self) -> &SecretBytes<32> {
+        &self.chain_code
+    }
+
+    /// 节点深度，根节点为 0
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// 派生本节点时使用的索引（含强化位），根节点为 0
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// 从节点私钥派生其他用途的密钥
+    ///
+    /// `HKDF(salt = 链码, ikm = 私钥, info = purpose)`，
+    /// 不同 `purpose` 得到的密钥相互独立，也与节点私钥本身独立。
+    ///
+    /// # 参数
+    ///
+    /// * `purpose` - 用途标签，如 `b"tee sealing"`
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecretBytes<N>)`: `N` 字节密钥
+    /// - `Err(Box<dyn Error>)`: `N` 超过 255 * 32 字节
+    pub fn derive_key<const N: usize>(
+        &self,
+        purpose: &[u8],
+    ) -> Result<SecretBytes<N>, Box<dyn Error>> {
+        Hkdf::new(
+            self.chain_code.expose_secret(),
+            self.secret_key.expose_secret(),
+        )
+        .expand_key(purpose)
+    }
+}
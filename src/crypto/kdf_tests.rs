@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod crypto_kdf_tests {
+    use crate::crypto::ec::Ed25519Riscv;
+    use crate::crypto::kdf::{DerivationPath, ExtendedKey, HARDENED_OFFSET, Hkdf, HmacSha256};
+    use crate::crypto::secret::Aes256Key;
+    use crate::crypto::test_util::hex;
+
+    #[test]
+    fn test_hmac_sha256_rfc4231_vectors() {
+        let long_key = vec![0xaau8; 131];
+        let vectors: [(Vec<u8>, Vec<u8>, &str); 6] = [
+            (
+                vec![0x0b; 20],
+                b"Hi There".to_vec(),
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                vec![0xaa; 20],
+                vec![0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            ),
+            (
+                (1..=25).collect(),
+                vec![0xcd; 50],
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            ),
+            (
+                long_key.clone(),
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+            (
+                long_key,
+                b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.".to_vec(),
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ),
+        ];
+
+        for (key, data, mac) in vectors {
+            assert_eq!(HmacSha256::mac(&key, &data).to_vec(), hex(mac));
+            assert!(HmacSha256::verify(&key, &data, &hex(mac)));
+
+            // 增量接口与一次性接口一致
+            let mut hmac = HmacSha256::new(&key);
+            for chunk in data.chunks(7) {
+                hmac.update(chunk);
+            }
+            assert_eq!(hmac.finalize().to_vec(), hex(mac));
+        }
+    }
+
+    #[test]
+    fn test_hmac_sha256_truncated_verify() {
+        // RFC 4231 Test Case 5：截断为 128 位
+        let key = [0x0cu8; 20];
+        let data = b"Test With Truncation";
+        let tag = hex("a3b6167473100ee06e0c796c2955552b");
+
+        assert!(HmacSha256::verify(&key, data, &tag));
+        let mut bad = tag.clone();
+        bad[15] ^= 1;
+        assert!(!HmacSha256::verify(&key, data, &bad));
+        assert!(!HmacSha256::verify(&key, data, &tag[..15]));
+        assert!(!HmacSha256::verify(&key, b"other", &tag));
+    }
+
+    #[test]
+    fn test_hkdf_rfc5869_vectors() {
+        let vectors = [
+            // Test Case 1
+            (
+                hex("000102030405060708090a0b0c"),
+                vec![0x0bu8; 22],
+                hex("f0f1f2f3f4f5f6f7f8f9"),
+                "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+            ),
+            // Test Case 2：较长输入
+            (
+                (0x60..=0xaf).collect(),
+                (0x00..=0x4f).collect(),
+                (0xb0..=0xff).collect(),
+                "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+                "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71cc30c58179ec3e87c14c01d5c1f3434f1d87",
+            ),
+            // Test Case 3：空 salt 与空 info
+            (
+                vec![],
+                vec![0x0bu8; 22],
+                vec![],
+                "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+            ),
+        ];
+
+        for (salt, ikm, info, prk, okm) in vectors {
+            let hkdf = Hkdf::new(&salt, &ikm);
+            assert_eq!(hkdf.prk().expose_secret().to_vec(), hex(prk));
+            let output = hkdf.expand(&info, okm.len() / 2).unwrap();
+            assert_eq!(output.expose_secret(), hex(okm).as_slice());
+        }
+    }
+
+    #[test]
+    fn test_hkdf_output_limits_and_separation() {
+        let hkdf = Hkdf::new(b"salt", b"root secret");
+        assert_eq!(hkdf.expand(b"info", 255 * 32).unwrap().len(), 255 * 32);
+        assert!(hkdf.expand(b"info", 255 * 32 + 1).is_err());
+        assert!(hkdf.expand(b"info", 0).unwrap().is_empty());
+
+        let sealing: Aes256Key = hkdf.expand_key(b"tee sealing").unwrap();
+        let storage: Aes256Key = hkdf.expand_key(b"tee storage").unwrap();
+        assert_ne!(sealing, storage);
+        // 较短输出是较长输出的前缀
+        assert_eq!(
+            sealing.expose_secret()[..],
+            hkdf.expand(b"tee sealing", 48).unwrap().expose_secret()[..32]
+        );
+    }
+
+    #[test]
+    fn test_derivation_path_parse_and_display() {
+        let path: DerivationPath = "m/44'/0h/7'".parse().unwrap();
+        assert_eq!(
+            path.indices(),
+            &[44 | HARDENED_OFFSET, HARDENED_OFFSET, 7 | HARDENED_OFFSET]
+        );
+        assert_eq!(path.to_string(), "m/44'/0'/7'");
+        assert_eq!(
+            DerivationPath::root()
+                .child(44)
+                .unwrap()
+                .child(0)
+                .unwrap()
+                .child(7)
+                .unwrap(),
+            path
+        );
+        assert_eq!(
+            "m".parse::<DerivationPath>().unwrap(),
+            DerivationPath::root()
+        );
+
+        assert!("44'/0'".parse::<DerivationPath>().is_err());
+        assert!("m/44".parse::<DerivationPath>().is_err());
+        assert!("m/x'".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648'".parse::<DerivationPath>().is_err());
+        assert!(DerivationPath::root().child(HARDENED_OFFSET).is_err());
+    }
+
+    #[test]
+    fn test_extended_key_derivation_is_deterministic_and_hierarchical() {
+        let root = ExtendedKey::from_seed(&hex("000102030405060708090a0b0c0d0e0f")).unwrap();
+        assert_eq!(root.depth(), 0);
+        assert!(ExtendedKey::from_seed(&[0u8; 15]).is_err());
+
+        let path: DerivationPath = "m/0'/1'/2'".parse().unwrap();
+        let leaf = root.derive_path(&path).unwrap();
+        assert_eq!(leaf.depth(), 3);
+        assert_eq!(leaf.child_number(), 2 | HARDENED_OFFSET);
+
+        // 逐级派生与按路径派生一致，且结果可复现
+        let stepwise = root
+            .derive_child(0)
+            .unwrap()
+            .derive_child(1)
+            .unwrap()
+            .derive_child(2)
+            .unwrap();
+        assert_eq!(stepwise.secret_key(), leaf.secret_key());
+        assert_eq!(stepwise.chain_code(), leaf.chain_code());
+        let again = ExtendedKey::from_seed(&hex("000102030405060708090a0b0c0d0e0f"))
+            .unwrap()
+            .derive_path(&path)
+            .unwrap();
+        assert_eq!(again.public_key(), leaf.public_key());
+
+        // 兄弟节点与不同种子互不相同
+        assert_ne!(
+            root.derive_child(0).unwrap().secret_key(),
+            root.derive_child(1).unwrap().secret_key()
+        );
+        assert_ne!(
+            ExtendedKey::from_seed(&[1u8; 16]).unwrap().secret_key(),
+            root.secret_key()
+        );
+        assert!(root.derive_child(HARDENED_OFFSET).is_err());
+    }
+
+    #[test]
+    fn test_extended_key_signs_and_derives_purpose_keys() {
+        let account = ExtendedKey::from_seed(&[7u8; 32])
+            .unwrap()
+            .derive_path(&"m/0'/42'".parse().unwrap())
+            .unwrap();
+
+        let ed25519 = Ed25519Riscv::new();
+        let signature = ed25519.sign(account.secret_key(), b"tx").unwrap();
+        assert!(
+            ed25519
+                .verify(&account.public_key(), b"tx", &signature)
+                .unwrap()
+        );
+
+        let sealing: Aes256Key = account.derive_key(b"tee sealing").unwrap();
+        let session: Aes256Key = account.derive_key(b"session").unwrap();
+        assert_ne!(sealing, session);
+        assert_ne!(
+            sealing.expose_secret(),
+            account.secret_key().expose_secret()
+        );
+        assert!(format!("{:?}", account).contains("[REDACTED]"));
+    }
+}
@@ -0,0 +1,262 @@
+//! Keccak-256 哈希模块，支持 RISC-V 硬件加速
+//!
+//! 以太坊使用的是 SHA-3 标准化之前的原始 Keccak 填充（`0x01 ... 0x80`），
+//! 与 FIPS 202 SHA3-256（填充 `0x06 ... 0x80`）的结果不同。
+//! Rollup 与 L1 交互的数据（存款事件、提款根、calldata 承诺）都需要这种 Keccak-256。
+//!
+//! 置换 Keccak-f[1600] 通过 `RiscVCryptoExt::copr_keccak_f1600` 交给加速器，
+//! 状态为 25 个 64 位 lane 的小端序编码（200 字节），lane `(x, y)` 位于第 `x + 5y` 个。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::keccak::Keccak256Riscv;
+//!
+//! let digest = Keccak256Riscv::hash(b"");
+//! assert_eq!(digest[..4], [0xc5, 0xd2, 0x46, 0x01]);
+//! ```
+
+use crate::crypto::hash::Hasher;
+#[allow(dead_code)]
+use crate::crypto::riscv_ext::RiscVCryptoExt;
+
+/// Keccak-f[1600] 状态大小（字节）
+pub const KECCAK_STATE_SIZE: usize = 200;
+
+/// Keccak-256 的速率（字节），容量为 512 位
+pub const KECCAK256_RATE: usize = 136;
+
+/// Keccak-f[1600] 轮数
+const KECCAK_ROUNDS: usize = 24;
+
+/// ι 步骤的轮常数
+const ROUND_CONSTANTS: [u64; KECCAK_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// ρ 步骤的旋转量，按 π 步骤的遍历顺序排列
+const RHO_OFFSETS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// π 步骤的 lane 遍历顺序，从 lane 1 出发
+const PI_LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Keccak-f[1600] 置换（软件实现）
+///
+/// # 参数
+///
+/// * `lanes` - 25 个 lane，第 `x + 5y` 个为 lane `(x, y)`
+pub fn keccak_f1600(lanes: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        // θ
+        let mut columns = [0u64; 5];
+        for (x, column) in columns.iter_mut().enumerate() {
+            *column = lanes[x] ^ lanes[x + 5] ^ lanes[x + 10] ^ lanes[x + 15] ^ lanes[x + 20];
+        }
+        for x in 0..5 {
+            let d = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                lanes[x + 5 * y] ^= d;
+            }
+        }
+
+        // ρ 和 π
+        let mut current = lanes[1];
+        for (&lane, &offset) in PI_LANES.iter().zip(RHO_OFFSETS.iter()) {
+            let next = lanes[lane];
+            lanes[lane] = current.rotate_left(offset);
+            current = next;
+        }
+
+        // χ
+        for y in 0..5 {
+            let row = [
+                lanes[5 * y],
+                lanes[5 * y + 1],
+                lanes[5 * y + 2],
+                lanes[5 * y + 3],
+                lanes[5 * y + 4],
+            ];
+            for x in 0..5 {
+                lanes[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // ι
+        lanes[0] ^= round_constant;
+    }
+}
+
+/// 对 200 字节小端序状态执行 Keccak-f[1600]，供软件后端直接复用
+///
+/// # 参数
+///
+/// * `state` - 25 个 lane 的小端序编码
+///
+/// # 返回
+///
+/// 置换后的状态
+pub(crate) fn keccak_f1600_bytes(state: &[u8; KECCAK_STATE_SIZE]) -> [u8; KECCAK_STATE_SIZE] {
+    let mut lanes = [0u64; 25];
+    for (lane, bytes) in lanes.iter_mut().zip(state.chunks_exact(8)) {
+        *lane = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    keccak_f1600(&mut lanes);
+
+    let mut output = [0u8; KECCAK_STATE_SIZE];
+    for (bytes, lane) in output.chunks_exact_mut(8).zip(lanes.iter()) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    output
+}
+
+/// Keccak-256 哈希计算器，支持 RISC-V 硬件加速
+///
+/// 每次置换优先交给加速器的 `copr_keccak_f1600` 处理；
+/// 加速器返回错误时回退到软件实现 `keccak_f1600`，两条路径结果一致。
+#[allow(dead_code)]
+pub struct Keccak256Riscv {
+    accelerator: Box<dyn RiscVCryptoExt>,
+    state: [u8; KECCAK_STATE_SIZE],
+    /// 当前块中已吸收的字节数
+    offset: usize,
+}
+
+#[allow(dead_code)]
+impl Keccak256Riscv {
+    /// 创建新的 Keccak-256 哈希计算器
+    ///
+    /// 加速器由 `crate::crypto::backend::default_backend()` 按环境变量选择。
+    pub fn new() -> Self {
+        Self::with_accelerator(crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 Keccak-256 哈希计算器
+    ///
+    /// # 参数
+    ///
+    /// * `accelerator` - 用于执行置换的 RISC-V 加速器
+    pub fn with_accelerator(accelerator: Box<dyn RiscVCryptoExt>) -> Self {
+        Keccak256Riscv {
+            accelerator,
+            state: [0u8; KECCAK_STATE_SIZE],
+            offset: 0,
+        }
+    }
+
+    /// 向哈希计算追加数据
+    ///
+    /// 数据直接异或进状态的速率部分，每满 136 字节执行一次置换。
+    ///
+    /// # 参数
+    ///
+    /// * `data` - 要追加的数据，可为任意长度
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state[self.offset] ^= byte;
+            self.offset += 1;
+            if self.offset == KECCAK256_RATE {
+                self.permute();
+                self.offset = 0;
+            }
+        }
+    }
+
+    /// 执行一次置换
+    ///
+    /// 优先调用加速器；加速器返回错误时使用软件实现。
+    fn permute(&mut self) {
+        self.state = match self.accelerator.copr_keccak_f1600(&self.state) {
+            Ok(state) => state,
+            Err(_) => keccak_f1600_bytes(&self.state),
+        };
+    }
+
+    /// 完成哈希计算并返回结果
+    ///
+    /// # 填充格式
+    ///
+    /// 原始 Keccak 的 pad10*1：在当前位置异或 0x01，在速率末字节异或 0x80
+    /// （两者重合时该字节为 0x81）。
+    ///
+    /// # 返回
+    ///
+    /// 32 字节哈希值
+    pub fn finalize(mut self) -> [u8; 32] {
+        self.state[self.offset] ^= 0x01;
+        self.state[KECCAK256_RATE - 1] ^= 0x80;
+        self.permute();
+
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&self.state[..32]);
+        digest
+    }
+
+    /// 对数据直接计算 Keccak-256 哈希
+    ///
+    /// # 参数
+    ///
+    /// * `data` - 要计算哈希的数据
+    ///
+    /// # 返回
+    ///
+    /// 32 字节哈希值
+    pub fn hash(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl Default for Keccak256Riscv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 以太坊 Keccak-256，可用于 `crypto::hash` 中的 Merkle 树
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Keccak256Hasher;
+
+impl Hasher for Keccak256Hasher {
+    const NAME: &'static str = "keccak256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Keccak256Riscv::hash(data)
+    }
+
+    fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Keccak256Riscv::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    }
+}
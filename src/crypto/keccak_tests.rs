@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod crypto_keccak_tests {
+    use crate::crypto::hash::{Hasher, MerkleLayout, calculate_merkle_root, merkle_leaf};
+    use crate::crypto::keccak::{Keccak256Hasher, Keccak256Riscv, keccak_f1600};
+    use crate::crypto::mock::{MockAccelerator, MockOperation};
+    use crate::crypto::riscv_ext::{HardwareAccelerator, RiscVCryptoExt, SoftwareBackend};
+    use crate::crypto::zkn::{ZknBackend, ZknEmulator};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_keccak_f1600_zero_state() {
+        // Keccak 团队发布的 KeccakF-1600 中间值：全零状态置换一次
+        let mut lanes = [0u64; 25];
+        keccak_f1600(&mut lanes);
+        assert_eq!(lanes[0], 0xf1258f7940e1dde7);
+        assert_eq!(lanes[1], 0x84d5ccf933c0478a);
+        assert_eq!(lanes[2], 0xd598261ea65aa9ee);
+        assert_eq!(lanes[24], 0xeaf1ff7b5ceca249);
+    }
+
+    #[test]
+    fn test_keccak256_vectors() {
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        let long: Vec<u8> = (0..=255).chain(0..=255).collect();
+        let rate_minus_one = [b'a'; 135];
+        let rate = [b'a'; 136];
+        let vectors: [(&[u8], &str); 6] = [
+            (
+                b"",
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            (
+                b"abc",
+                "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+            ),
+            (
+                fox,
+                "4d741b6f1eb29cb2a9b9911c82f56fa8d73b04959d3d9d222895df6c0b28aa15",
+            ),
+            // 填充字节 0x01 与 0x80 重合为 0x81
+            (
+                &rate_minus_one,
+                "34367dc248bbd832f4e3e69dfaac2f92638bd0bbd18f2912ba4ef454919cf446",
+            ),
+            // 恰好一个速率块，填充单独占一块
+            (
+                &rate,
+                "a6c4d403279fe3e0af03729caada8374b5ca54d8065329a3ebcaeb4b60aa386e",
+            ),
+            (
+                &long,
+                "f55ba327291604f0e5be6651752398b7be2331aad65f5763ce067df95cc13be1",
+            ),
+        ];
+
+        for (input, expected) in vectors {
+            assert_eq!(hex(&Keccak256Riscv::hash(input)), expected);
+        }
+    }
+
+    #[test]
+    fn test_keccak256_incremental_update() {
+        let data: Vec<u8> = (0..=255).chain(0..=255).collect();
+        for split in [0, 1, 135, 136, 137, 272, 511] {
+            let mut hasher = Keccak256Riscv::new();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.finalize(), Keccak256Riscv::hash(&data));
+        }
+    }
+
+    #[test]
+    fn test_keccak_backends_agree() {
+        let mut state = [0u8; 200];
+        for (i, byte) in state.iter_mut().enumerate() {
+            *byte = (i * 7) as u8;
+        }
+        let expected = SoftwareBackend.copr_keccak_f1600(&state).unwrap();
+        assert_ne!(expected, state);
+
+        let backends: [Box<dyn RiscVCryptoExt>; 3] = [
+            Box::new(HardwareAccelerator::new()),
+            Box::new(ZknBackend::new(ZknEmulator)),
+            Box::new(MockAccelerator::new()),
+        ];
+        for backend in backends.iter() {
+            assert_eq!(backend.copr_keccak_f1600(&state).unwrap(), expected);
+        }
+
+        let mut disabled = HardwareAccelerator::new();
+        disabled.set_enabled(false);
+        assert!(disabled.copr_keccak_f1600(&state).is_err());
+    }
+
+    #[test]
+    fn test_keccak256_uses_accelerator_and_falls_back() {
+        let expected = Keccak256Riscv::hash(&[0x5a; 300]);
+
+        let mock = MockAccelerator::new();
+        let mut hasher = Keccak256Riscv::with_accelerator(Box::new(mock.clone()));
+        hasher.update(&[0x5a; 300]);
+        assert_eq!(hasher.finalize(), expected);
+        assert_eq!(mock.call_count(MockOperation::KeccakF1600), 3);
+
+        let failing = MockAccelerator::new().fail_operation(MockOperation::KeccakF1600);
+        let mut hasher = Keccak256Riscv::with_accelerator(Box::new(failing.clone()));
+        hasher.update(&[0x5a; 300]);
+        assert_eq!(hasher.finalize(), expected);
+        assert!(failing.calls().iter().all(|call| call.failed));
+    }
+
+    #[test]
+    fn test_keccak256_hasher() {
+        assert_eq!(Keccak256Hasher::NAME, "keccak256");
+        assert_eq!(
+            Keccak256Hasher::hash_parts(&[b"a", b"bc"]),
+            Keccak256Hasher::hash(b"abc")
+        );
+
+        let leaves = [
+            merkle_leaf::<Keccak256Hasher>(b"deposit-0"),
+            merkle_leaf::<Keccak256Hasher>(b"deposit-1"),
+        ];
+        let root = calculate_merkle_root::<Keccak256Hasher>(
+            &[b"deposit-0", b"deposit-1"],
+            MerkleLayout::Rfc6962,
+        );
+        assert_eq!(
+            root,
+            Keccak256Hasher::hash_parts(&[&[0x01], &leaves[0], &leaves[1]])
+        );
+    }
+}
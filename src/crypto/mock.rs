@@ -0,0 +1,467 @@
+//! 故障注入与调用记录的 Mock 加速器
+//!
+//! `MockAccelerator` 把实际运算委托给另一个后端（默认 `SoftwareBackend`），
+//! 并可按配置注入故障，用于测试上层代码对加速器错误的处理：
+//!
+//! - 让第 N 次调用（全局计数，从 1 开始）或某类操作的全部调用返回错误
+//! - 篡改某类操作的输出
+//! - 为每次调用增加固定延迟
+//! - 记录所有调用
+//!
+//! 克隆的实例共享配置和调用记录，因此可以把一个克隆交给 `Aes256Riscv` 等类型，
+//! 再通过另一个克隆检查调用记录。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::aes::Aes256Riscv;
+//! use crypto::mock::{MockAccelerator, MockOperation};
+//! use crypto::secret::Aes256Key;
+//!
+//! let mock = MockAccelerator::new().fail_operation(MockOperation::EncryptAes256);
+//! let aes = Aes256Riscv::with_accelerator(Box::new(mock.clone()));
+//! assert!(aes.encrypt_aes256(b"data", &Aes256Key::new([0u8; 32])).is_err());
+//! assert_eq!(mock.calls()[0].operation, MockOperation::EncryptAes256);
+//! ```
+
+use crate::crypto::riscv_ext::{Aes256KeySchedule, RiscVCryptoExt, SoftwareBackend};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Mock 加速器注入的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct MockError {
+    message: String,
+}
+
+impl fmt::Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mock Accelerator Error: {}", self.message)
+    }
+}
+
+impl Error for MockError {}
+
+/// `RiscVCryptoExt` 的操作类型，与 trait 方法一一对应
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MockOperation {
+    /// `copr_encrypt_aes256`
+    EncryptAes256,
+    /// `copr_decrypt_aes256`
+    DecryptAes256,
+    /// `copr_aes256_encrypt_block`
+    Aes256EncryptBlock,
+    /// `copr_aes256_decrypt_block`
+    Aes256DecryptBlock,
+    /// `copr_aes256_key_schedule`
+    ///
+    /// 返回的分组变换的每次调用分别记为 `Aes256EncryptBlock` / `Aes256DecryptBlock`。
+    Aes256KeySchedule,
+    /// `copr_hash_sha256`
+    HashSha256,
+    /// `copr_sha256_compress`
+    Sha256Compress,
+    /// `copr_keccak_f1600`
+    KeccakF1600,
+    /// `copr_ec_mul`
+    EcMul,
+    /// `copr_ec_add`
+    EcAdd,
+    /// `copr_sign_ed25519`
+    SignEd25519,
+    /// `copr_verify_ed25519`
+    VerifyEd25519,
+    /// `copr_verify_ed25519_batch`
+    VerifyEd25519Batch,
+    /// `copr_zkp_prove`
+    ZkpProve,
+    /// `copr_zkp_verify`
+    ZkpVerify,
+    /// `copr_msm_accelerate`
+    Msm,
+}
+
+/// 一次调用的记录
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockCall {
+    /// 全局调用序号，从 1 开始
+    pub index: usize,
+    /// 调用的操作
+    pub operation: MockOperation,
+    /// 是否被注入了错误
+    pub failed: bool,
+    /// 输出是否被篡改
+    pub corrupted: bool,
+}
+
+/// 故障配置与调用记录
+#[derive(Debug, Default)]
+struct MockState {
+    fail_calls: BTreeSet<usize>,
+    fail_operations: BTreeSet<MockOperation>,
+    corrupt_operations: BTreeSet<MockOperation>,
+    latency: Duration,
+    calls: Vec<MockCall>,
+}
+
+struct MockShared {
+    delegate: Box<dyn RiscVCryptoExt>,
+    state: Mutex<MockState>,
+}
+
+/// 可注入故障并记录调用的加速器
+///
+/// 未配置任何故障时行为与委托后端完全一致。
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct MockAccelerator {
+    shared: Arc<MockShared>,
+}
+
+impl fmt::Debug for MockAccelerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockAccelerator")
+            .field("state", &*self.state())
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(dead_code)]
+impl MockAccelerator {
+    /// 创建委托给 `SoftwareBackend` 的 Mock 加速器
+    ///
+    /// # 返回
+    ///
+    /// 未配置故障的 `MockAccelerator`
+    pub fn new() -> Self {
+        Self::wrapping(Box::new(SoftwareBackend))
+    }
+
+    /// 创建委托给指定后端的 Mock 加速器
+    ///
+    /// # 参数
+    ///
+    /// * `delegate` - 实际执行运算的后端
+    ///
+    /// # 返回
+    ///
+    /// 未配置故障的 `MockAccelerator`
+    pub fn wrapping(delegate: Box<dyn RiscVCryptoExt>) -> Self {
+        MockAccelerator {
+            shared: Arc::new(MockShared {
+                delegate,
+                state: Mutex::new(MockState::default()),
+            }),
+        }
+    }
+
+    /// 让第 `n` 次调用（全局计数，从 1 开始）返回错误
+    ///
+    /// 可多次调用以配置多个失败点。
+    pub fn fail_on_call(self, n: usize) -> Self {
+        self.state().fail_calls.insert(n);
+        self
+    }
+
+    /// 让某类操作的所有调用返回错误
+    pub fn fail_operation(self, operation: MockOperation) -> Self {
+        self.state().fail_operations.insert(operation);
+        self
+    }
+
+    /// 篡改某类操作的输出
+    ///
+    /// 字节输出翻转首字节的最低位（GCM 加密翻转标签首字节），
+    /// 布尔输出取反，批量验证结果取反第一项。
+    pub fn corrupt_operation(self, operation: MockOperation) -> Self {
+        self.state().corrupt_operations.insert(operation);
+        self
+    }
+
+    /// 每次调用前等待 `latency`
+    pub fn with_latency(self, latency: Duration) -> Self {
+        self.state().latency = latency;
+        self
+    }
+
+    /// 按调用顺序返回调用记录
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state().calls.clone()
+    }
+
+    /// 某类操作的调用次数
+    pub fn call_count(&self, operation: MockOperation) -> usize {
+        self.state()
+            .calls
+            .iter()
+            .filter(|call| call.operation == operation)
+            .count()
+    }
+
+    /// 清空调用记录，故障配置保持不变
+    ///
+    /// 调用序号重新从 1 开始计数。
+    pub fn clear_calls(&self) {
+        self.state().calls.clear();
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.shared.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 记录调用并按配置执行、注入故障
+    fn run<T: Corrupt>(
+        &self,
+        operation: MockOperation,
+        f: impl FnOnce(&dyn RiscVCryptoExt) -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let (call, latency) = {
+            let mut state = self.state();
+            let index = state.calls.len() + 1;
+            let call = MockCall {
+                index,
+                operation,
+                failed: state.fail_calls.contains(&index)
+                    || state.fail_operations.contains(&operation),
+                corrupted: state.corrupt_operations.contains(&operation),
+            };
+            state.calls.push(call.clone());
+            (call, state.latency)
+        };
+
+        if !latency.is_zero() {
+            thread::sleep(latency);
+        }
+
+        if call.failed {
+            return Err(Box::new(MockError {
+                message: format!("Injected failure in {:?} (call #{})", operation, call.index),
+            }));
+        }
+
+        let mut output = f(self.shared.delegate.as_ref())?;
+        if call.corrupted {
+            output.corrupt();
+        }
+        Ok(output)
+    }
+}
+
+impl Default for MockAccelerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 可被篡改的操作输出
+trait Corrupt {
+    fn corrupt(&mut self);
+}
+
+impl Corrupt for bool {
+    fn corrupt(&mut self) {
+        *self = !*self;
+    }
+}
+
+impl Corrupt for Vec<u8> {
+    fn corrupt(&mut self) {
+        if let Some(first) = self.first_mut() {
+            *first ^= 1;
+        }
+    }
+}
+
+impl Corrupt for Vec<bool> {
+    fn corrupt(&mut self) {
+        if let Some(first) = self.first_mut() {
+            *first = !*first;
+        }
+    }
+}
+
+impl<const N: usize> Corrupt for [u8; N] {
+    fn corrupt(&mut self) {
+        if let Some(first) = self.first_mut() {
+            *first ^= 1;
+        }
+    }
+}
+
+impl Corrupt for (Vec<u8>, [u8; 16]) {
+    fn corrupt(&mut self) {
+        self.1.corrupt();
+    }
+}
+
+/// 密钥调度本身没有可篡改的字节；篡改作用于之后记录的分组运算
+impl Corrupt for Box<dyn Aes256KeySchedule> {
+    fn corrupt(&mut self) {}
+}
+
+/// 记录并按配置干预每次分组运算的密钥调度
+struct MockKeySchedule {
+    mock: MockAccelerator,
+    inner: Box<dyn Aes256KeySchedule>,
+}
+
+impl Aes256KeySchedule for MockKeySchedule {
+    fn encrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        self.mock.run(MockOperation::Aes256EncryptBlock, |_| {
+            self.inner.encrypt_block(block)
+        })
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        self.mock.run(MockOperation::Aes256DecryptBlock, |_| {
+            self.inner.decrypt_block(block)
+        })
+    }
+}
+
+impl RiscVCryptoExt for MockAccelerator {
+    fn copr_encrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        self.run(MockOperation::EncryptAes256, |d| {
+            d.copr_encrypt_aes256(key, nonce, aad, plaintext)
+        })
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.run(MockOperation::DecryptAes256, |d| {
+            d.copr_decrypt_aes256(key, nonce, aad, ciphertext, tag)
+        })
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        self.run(MockOperation::Aes256EncryptBlock, |d| {
+            d.copr_aes256_encrypt_block(key, block)
+        })
+    }
+
+    fn copr_aes256_decrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        self.run(MockOperation::Aes256DecryptBlock, |d| {
+            d.copr_aes256_decrypt_block(key, block)
+        })
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        let inner = self.run(MockOperation::Aes256KeySchedule, |d| {
+            d.copr_aes256_key_schedule(key)
+        })?;
+        Ok(Box::new(MockKeySchedule {
+            mock: self.clone(),
+            inner,
+        }))
+    }
+
+    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        self.run(MockOperation::HashSha256, |d| d.copr_hash_sha256(input))
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        self.run(MockOperation::Sha256Compress, |d| {
+            d.copr_sha256_compress(state, block)
+        })
+    }
+
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        self.run(MockOperation::KeccakF1600, |d| d.copr_keccak_f1600(state))
+    }
+
+    fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>> {
+        self.run(MockOperation::EcMul, |d| d.copr_ec_mul(scalar, point))
+    }
+
+    fn copr_ec_add(
+        &self,
+        point1: &[u8; 32],
+        point2: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        self.run(MockOperation::EcAdd, |d| d.copr_ec_add(point1, point2))
+    }
+
+    fn copr_sign_ed25519(
+        &self,
+        secret: &[u8; 32],
+        message: &[u8],
+    ) -> Result<[u8; 64], Box<dyn Error>> {
+        self.run(MockOperation::SignEd25519, |d| {
+            d.copr_sign_ed25519(secret, message)
+        })
+    }
+
+    fn copr_verify_ed25519(
+        &self,
+        public: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, Box<dyn Error>> {
+        self.run(MockOperation::VerifyEd25519, |d| {
+            d.copr_verify_ed25519(public, message, signature)
+        })
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        self.run(MockOperation::VerifyEd25519Batch, |d| {
+            d.copr_verify_ed25519_batch(items)
+        })
+    }
+
+    fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.run(MockOperation::ZkpProve, |d| d.copr_zkp_prove(witness))
+    }
+
+    fn copr_zkp_verify(&self, proof: &[u8], public_input: &[u8]) -> Result<bool, Box<dyn Error>> {
+        self.run(MockOperation::ZkpVerify, |d| {
+            d.copr_zkp_verify(proof, public_input)
+        })
+    }
+
+    fn copr_msm_accelerate(
+        &self,
+        points: &[Vec<u8>],
+        scalars: &[Vec<u8>],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.run(MockOperation::Msm, |d| {
+            d.copr_msm_accelerate(points, scalars)
+        })
+    }
+}
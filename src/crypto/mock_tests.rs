@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod crypto_mock_tests {
+    use crate::crypto::aes::Aes256Riscv;
+    use crate::crypto::ec::Ed25519Riscv;
+    use crate::crypto::mock::{MockAccelerator, MockOperation};
+    use crate::crypto::riscv_ext::{RiscVCryptoExt, SoftwareBackend};
+    use crate::crypto::secret::Aes256Key;
+    use crate::crypto::sha256::Sha256Riscv;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_mock_without_faults_matches_software_backend() {
+        let mock = MockAccelerator::new();
+        let key = [7u8; 32];
+        let nonce = [3u8; 12];
+
+        assert_eq!(
+            mock.copr_encrypt_aes256(&key, &nonce, b"aad", b"plaintext")
+                .unwrap(),
+            SoftwareBackend
+                .copr_encrypt_aes256(&key, &nonce, b"aad", b"plaintext")
+                .unwrap()
+        );
+        assert_eq!(
+            mock.copr_hash_sha256(b"abc").unwrap(),
+            SoftwareBackend.copr_hash_sha256(b"abc").unwrap()
+        );
+        assert!(
+            mock.calls()
+                .iter()
+                .all(|call| !call.failed && !call.corrupted)
+        );
+    }
+
+    #[test]
+    fn test_mock_fails_nth_call_only() {
+        let mock = MockAccelerator::new().fail_on_call(2);
+
+        assert!(mock.copr_hash_sha256(b"first").is_ok());
+        let err = mock.copr_hash_sha256(b"second").unwrap_err();
+        assert!(err.to_string().contains("call #2"));
+        assert!(mock.copr_hash_sha256(b"third").is_ok());
+
+        let failed: Vec<bool> = mock.calls().iter().map(|call| call.failed).collect();
+        assert_eq!(failed, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_mock_fail_operation_leaves_other_operations_working() {
+        let mock = MockAccelerator::new().fail_operation(MockOperation::SignEd25519);
+        let ed25519 = Ed25519Riscv::with_accelerator(Box::new(mock.clone()));
+        let (secret, public) = ed25519.keygen_from_seed(&[1u8; 32]);
+
+        assert!(ed25519.sign(&secret, b"msg").is_err());
+        let signature = SoftwareBackend
+            .copr_sign_ed25519(secret.expose_secret(), b"msg")
+            .unwrap();
+        assert!(ed25519.verify(&public, b"msg", &signature).unwrap());
+
+        assert_eq!(mock.call_count(MockOperation::SignEd25519), 1);
+        assert_eq!(mock.call_count(MockOperation::VerifyEd25519), 1);
+    }
+
+    #[test]
+    fn test_mock_corrupted_gcm_tag_is_rejected() {
+        let mock = MockAccelerator::new().corrupt_operation(MockOperation::EncryptAes256);
+        let aes = Aes256Riscv::with_accelerator(Box::new(mock.clone()));
+        let key = Aes256Key::new([5u8; 32]);
+
+        let sealed = aes.encrypt_gcm(b"payload", &key, b"").unwrap();
+        assert!(aes.decrypt_gcm(&sealed, &key, b"").is_err());
+        assert!(mock.calls()[0].corrupted);
+    }
+
+    #[test]
+    fn test_mock_corrupted_verify_flips_result() {
+        let mock = MockAccelerator::new().corrupt_operation(MockOperation::VerifyEd25519);
+        let ed25519 = Ed25519Riscv::with_accelerator(Box::new(mock));
+        let (secret, public) = ed25519.keygen_from_seed(&[2u8; 32]);
+
+        let signature = ed25519.sign(&secret, b"msg").unwrap();
+        assert!(!ed25519.verify(&public, b"msg", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sha256_falls_back_to_software_on_compress_failure() {
+        let mock = MockAccelerator::new().fail_operation(MockOperation::Sha256Compress);
+        let mut sha = Sha256Riscv::with_accelerator(Box::new(mock.clone()));
+        sha.update(b"abc");
+
+        assert_eq!(sha.finalize(), Sha256Riscv::hash(b"abc"));
+        assert!(mock.call_count(MockOperation::Sha256Compress) > 0);
+        assert!(mock.calls().iter().all(|call| call.failed));
+    }
+
+    #[test]
+    fn test_mock_latency_and_call_log() {
+        let mock = MockAccelerator::new().with_latency(Duration::from_millis(20));
+
+        let start = Instant::now();
+        mock.copr_hash_sha256(b"abc").unwrap();
+        mock.copr_ec_add(&[0u8; 32], &[0u8; 32]).ok();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        let calls = mock.calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].index, 1);
+        assert_eq!(calls[0].operation, MockOperation::HashSha256);
+        assert_eq!(calls[1].index, 2);
+        assert_eq!(calls[1].operation, MockOperation::EcAdd);
+
+        mock.clear_calls();
+        assert!(mock.calls().is_empty());
+    }
+}
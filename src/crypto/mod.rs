@@ -3,17 +3,37 @@
 //! 提供完整的加密功能支持，包括：
 //! - AES-256 对称加密
 //! - SHA-256 哈希计算
+//! - Keccak-256 哈希（以太坊 L1 兼容）
+//! - HMAC / HKDF 密钥派生
 //! - Ed25519 椭圆曲线签名
+//! - X25519 密钥交换与 Noise 认证会话通道
 //! - Merkle 树操作
+//! - Poseidon 零知识友好哈希
 //! - RISC-V 硬件加速抽象
 //!
 //! # 模块结构
 //!
 //! - `aes`: AES-256 加密/解密
+//! - `aes_modes`: AES-256 CTR / CBC / XTS 分组模式
+//! - `backend`: 加速器后端注册与选择
+//! - `conformance`: NIST CAVP / RFC 8032 测试向量对各后端的一致性检验
+//! - `device`: 协处理器设备协议、客户端后端与用户态替身守护进程
 //! - `sha256`: SHA-256 哈希
 //! - `ec`: Ed25519 椭圆曲线
+//! - `field`: BN254 标量域（证明系统的标量域）运算
 //! - `hash`: 高级哈希操作
+//! - `keccak`: Keccak-f[1600] 置换与以太坊 Keccak-256 哈希
+//! - `kdf`: HMAC-SHA256、HKDF-SHA256 与分层密钥派生
+//! - `job_queue`: 提交/完成分离的异步加密作业队列与线程池实现
+//! - `mock`: 故障注入与调用记录的 Mock 加速器
+//! - `poseidon`: BN254 标量域上的 Poseidon 置换、海绵与字节哈希
 //! - `riscv_ext`: RISC-V 加速器抽象
+//! - `secret`: 释放时清零、常数时间比较的秘密密钥类型
+//! - `session`: Noise XX / NN 握手与 AES-256-GCM 会话通道
+//! - `timing`: dudect 风格的常数时间统计检验
+//! - `x25519`: X25519 Diffie-Hellman 密钥交换
+//! - `zkn`: Zkn 标量加密指令模拟后端
+//! - `zkn_hw`: riscv64 Zkn 硬件指令后端（需启用 `riscv-zkn` 特性）
 //!
 //! # 使用示例
 //!
@@ -21,27 +41,79 @@
 //! use crypto::aes::Aes256Riscv;
 //! use crypto::sha256::Sha256Riscv;
 //! use crypto::ec::Ed25519Riscv;
+//! use crypto::secret::Aes256Key;
 //!
 //! // AES-256 加密
 //! let aes = Aes256Riscv::new();
-//! let encrypted = aes.encrypt_aes256(b"data", &[0u8; 32]).unwrap();
+//! let encrypted = aes.encrypt_aes256(b"data", &Aes256Key::new([0u8; 32])).unwrap();
 //!
 //! // SHA-256 哈希
 //! let hash = Sha256Riscv::hash(b"data");
 //!
 //! // Ed25519 签名
 //! let ec = Ed25519Riscv::new();
-//! let (sk, pk) = ec.keygen();
+//! let (sk, pk) = ec.keygen().unwrap();
 //! let signature = ec.sign(&sk, b"message").unwrap();
 //! ```
 
 pub mod aes;
+pub mod aes_modes;
+pub mod backend;
+pub mod conformance;
+pub mod device;
 pub mod ec;
+pub mod field;
 pub mod hash;
+pub mod job_queue;
+pub mod kdf;
+pub mod keccak;
+pub mod mock;
+pub mod poseidon;
 pub mod riscv_ext;
+pub mod secret;
+pub mod session;
 pub mod sha256;
+pub mod timing;
+pub mod x25519;
+pub mod zkn;
+#[cfg(feature = "riscv-zkn")]
+pub mod zkn_hw;
 
 #[cfg(test)]
-mod riscv_tests;
+mod aes_modes_tests;
+#[cfg(test)]
+mod aes_tests;
+#[cfg(test)]
+mod backend_tests;
+#[cfg(test)]
+mod conformance_tests;
+#[cfg(test)]
+mod device_tests;
 #[cfg(test)]
 mod hash_tests;
+#[cfg(test)]
+mod job_queue_tests;
+#[cfg(test)]
+mod kdf_tests;
+#[cfg(test)]
+mod keccak_tests;
+#[cfg(test)]
+mod mock_tests;
+#[cfg(test)]
+mod poseidon_tests;
+#[cfg(test)]
+mod riscv_tests;
+#[cfg(test)]
+mod secret_tests;
+#[cfg(test)]
+mod session_tests;
+#[cfg(test)]
+mod test_util;
+#[cfg(test)]
+mod timing_tests;
+#[cfg(test)]
+mod x25519_tests;
+#[cfg(all(test, feature = "riscv-zkn"))]
+mod zkn_hw_tests;
+#[cfg(test)]
+mod zkn_tests;
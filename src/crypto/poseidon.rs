@@ -0,0 +1,508 @@
+//! Poseidon 哈希
+//!
+//! 面向零知识证明的代数哈希（Grassi 等，USENIX Security 2021），
+//! 在 BN254 标量域 `Fr` 上计算。一次 2 输入哈希在电路中约 240 个 R1CS 约束，
+//! 而 SHA-256 需要约 2.7 万个，适合作为需要在电路内证明的状态承诺。
+//!
+//! # 参数
+//!
+//! 使用 HADES 结构：S-box 为 x^5，`R_F = 8` 个全轮（前后各 4 轮）夹 `R_P` 个部分轮。
+//! 轮常数与 MDS 矩阵按参考实现 `generate_parameters_grain.sage` 的方式由 Grain LFSR 生成：
+//!
+//! - 80 位初始状态依次编码 域类型(2) | S-box 类型(4) | 域位数(12) | 宽度 t(12) | R_F(10) | R_P(10) | 30 个 1，
+//!   丢弃前 160 位输出，之后每输出一位前按自收缩规则丢弃一位
+//! - 轮常数：每次取 254 位，不小于模数时重取，共 `(R_F + R_P) * t` 个
+//! - MDS：继续取 `2t` 个元素 `x_i, y_j`，`M[i][j] = 1 / (x_i + y_j)`（Cauchy 矩阵）
+//!
+//! 宽度 2 到 5 的标准参数（`R_P` = 56、57、56、60）与 circomlib 的 Poseidon 完全一致，
+//! 因此 `poseidon_hash(&[a, b])` 与 circom 电路中的 `Poseidon(2)` 输出相同。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::field::Fr;
+//! use crypto::poseidon::{PoseidonSponge, poseidon_hash};
+//!
+//! let digest = poseidon_hash(&[Fr::from(1), Fr::from(2)]).unwrap();
+//!
+//! let mut sponge = PoseidonSponge::new(3, Fr::ZERO).unwrap();
+//! sponge.absorb(&[Fr::from(1), Fr::from(2), Fr::from(3)]);
+//! let output = sponge.squeeze();
+//! ```
+
+use crate::crypto::field::Fr;
+use crate::crypto::hash::Hasher;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+/// Poseidon 运算中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct PoseidonError {
+    message: String,
+}
+
+impl fmt::Display for PoseidonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Poseidon Error: {}", self.message)
+    }
+}
+
+impl Error for PoseidonError {}
+
+fn poseidon_error(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(PoseidonError {
+        message: message.into(),
+    })
+}
+
+/// 标准参数的全轮数
+pub const FULL_ROUNDS: usize = 8;
+/// 标准参数支持的最小宽度（1 个输入）
+pub const MIN_WIDTH: usize = 2;
+/// 标准参数支持的最大宽度（4 个输入）
+pub const MAX_WIDTH: usize = 5;
+/// 宽度 2 到 5 对应的部分轮数（128 位安全，与 circomlib 相同）
+const PARTIAL_ROUNDS: [usize; MAX_WIDTH - MIN_WIDTH + 1] = [56, 57, 56, 60];
+/// 域元素位数
+const FIELD_BITS: usize = 254;
+
+/// 参考实现的 Grain LFSR
+///
+/// 状态第 i 位存于 `state` 的第 i 位，第 0 位最旧。
+struct GrainLfsr {
+    state: u128,
+}
+
+impl GrainLfsr {
+    fn new(width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut lfsr = GrainLfsr { state: 0 };
+        let mut position = 0;
+        // 域类型 1 = 素域，S-box 类型 0 = x^alpha
+        for (value, bits) in [
+            (1, 2),
+            (0, 4),
+            (FIELD_BITS, 12),
+            (width, 12),
+            (full_rounds, 10),
+            (partial_rounds, 10),
+            ((1 << 30) - 1, 30),
+        ] {
+            for bit in (0..bits).rev() {
+                lfsr.state |= (((value >> bit) & 1) as u128) << position;
+                position += 1;
+            }
+        }
+
+        for _ in 0..160 {
+            lfsr.step();
+        }
+        lfsr
+    }
+
+    /// 移入一位新状态并返回该位
+    fn step(&mut self) -> u8 {
+        let bit = |i: u32| ((self.state >> i) & 1) as u8;
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state = (self.state >> 1) | ((new_bit as u128) << 79);
+        new_bit
+    }
+
+    /// 自收缩输出：成对取位，第一位为 1 时输出第二位
+    fn next_bit(&mut self) -> u8 {
+        loop {
+            if self.step() == 1 {
+                return self.step();
+            }
+            self.step();
+        }
+    }
+
+    /// 取 254 位，按大端序拼成 32 字节整数
+    fn next_bytes(&mut self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for i in 256 - FIELD_BITS..256 {
+            bytes[i / 8] |= self.next_bit() << (7 - i % 8);
+        }
+        bytes
+    }
+
+    /// 取小于模数的域元素（拒绝采样）
+    fn next_field_element(&mut self) -> Fr {
+        loop {
+            if let Ok(element) = Fr::from_bytes_be(&self.next_bytes()) {
+                return element;
+            }
+        }
+    }
+}
+
+/// Poseidon 置换参数
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoseidonParams {
+    width: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+    round_constants: Vec<Fr>,
+    mds: Vec<Vec<Fr>>,
+}
+
+#[allow(dead_code)]
+impl PoseidonParams {
+    /// 用 Grain LFSR 生成参数
+    ///
+    /// # 参数
+    ///
+    /// * `width` - 状态宽度 t，至少为 2
+    /// * `full_rounds` - 全轮数 R_F，必须为偶数
+    /// * `partial_rounds` - 部分轮数 R_P
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(PoseidonParams)`: 生成的参数
+    /// - `Err(Box<dyn Error>)`: 参数不合法
+    pub fn generate(
+        width: usize,
+        full_rounds: usize,
+        partial_rounds: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        if !(MIN_WIDTH..1 << 12).contains(&width) {
+            return Err(poseidon_error(format!("Unsupported width {}", width)));
+        }
+        if !full_rounds.is_multiple_of(2) || full_rounds >= 1 << 10 || partial_rounds >= 1 << 10 {
+            return Err(poseidon_error(format!(
+                "Unsupported round numbers R_F = {}, R_P = {}",
+                full_rounds, partial_rounds
+            )));
+        }
+
+        let mut lfsr = GrainLfsr::new(width, full_rounds, partial_rounds);
+        let round_constants = (0..(full_rounds + partial_rounds) * width)
+            .map(|_| lfsr.next_field_element())
+            .collect();
+
+        let mds = loop {
+            let elements: Vec<Fr> = (0..2 * width)
+                .map(|_| Fr::from_bytes_be_mod_order(&lfsr.next_bytes()))
+                .collect();
+            let distinct = (0..elements.len())
+                .all(|i| (i + 1..elements.len()).all(|j| elements[i] != elements[j]));
+            if !distinct {
+                continue;
+            }
+
+            let (xs, ys) = elements.split_at(width);
+            let matrix: Option<Vec<Vec<Fr>>> = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| (*x + *y).inverse()).collect())
+                .collect();
+            if let Some(matrix) = matrix {
+                break matrix;
+            }
+        };
+
+        Ok(PoseidonParams {
+            width,
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        })
+    }
+
+    /// 取宽度为 `width` 的标准参数
+    ///
+    /// 参数只在首次使用时生成，之后共享同一份。
+    ///
+    /// # 参数
+    ///
+    /// * `width` - 状态宽度，2 到 5
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Arc<PoseidonParams>)`: 标准参数
+    /// - `Err(Box<dyn Error>)`: 宽度不在支持范围内
+    pub fn standard(width: usize) -> Result<Arc<Self>, Box<dyn Error>> {
+        static CACHE: [OnceLock<Arc<PoseidonParams>>; MAX_WIDTH - MIN_WIDTH + 1] =
+            [const { OnceLock::new() }; MAX_WIDTH - MIN_WIDTH + 1];
+
+        if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) {
+            return Err(poseidon_error(format!(
+                "Standard parameters exist for width {} to {}, got {}",
+                MIN_WIDTH, MAX_WIDTH, width
+            )));
+        }
+        let index = width - MIN_WIDTH;
+        let params = CACHE[index].get_or_init(|| {
+            Arc::new(
+                Self::generate(width, FULL_ROUNDS, PARTIAL_ROUNDS[index])
+                    .expect("standard Poseidon parameters are valid"),
+            )
+        });
+        Ok(params.clone())
+    }
+
+    /// 状态宽度 t
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// 全轮数 R_F
+    pub fn full_rounds(&self) -> usize {
+        self.full_rounds
+    }
+
+    /// 部分轮数 R_P
+    pub fn partial_rounds(&self) -> usize {
+        self.partial_rounds
+    }
+
+    /// 第 `round` 轮的 t 个轮常数
+    pub fn round_constants(&self, round: usize) -> &[Fr] {
+        &self.round_constants[round * self.width..(round + 1) * self.width]
+    }
+
+    /// MDS 矩阵
+    pub fn mds(&self) -> &[Vec<Fr>] {
+        &self.mds
+    }
+
+    /// 第 `round` 轮是否为全轮
+    pub fn is_full_round(&self, round: usize) -> bool {
+        let half = self.full_rounds / 2;
+        round < half || round >= half + self.partial_rounds
+    }
+
+    /// 总轮数
+    pub fn rounds(&self) -> usize {
+        self.full_rounds + self.partial_rounds
+    }
+}
+
+/// S-box：x^5
+pub fn sbox(x: Fr) -> Fr {
+    let x2 = x.square();
+    x2.square() * x
+}
+
+/// Poseidon 置换
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct Poseidon {
+    params: Arc<PoseidonParams>,
+}
+
+#[allow(dead_code)]
+impl Poseidon {
+    /// 使用宽度为 `width` 的标准参数
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Poseidon)`: 置换实例
+    /// - `Err(Box<dyn Error>)`: 宽度不在 2 到 5 之间
+    pub fn new(width: usize) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::with_params(PoseidonParams::standard(width)?))
+    }
+
+    /// 使用自定义参数
+    pub fn with_params(params: Arc<PoseidonParams>) -> Self {
+        Poseidon { params }
+    }
+
+    /// 置换参数
+    pub fn params(&self) -> &PoseidonParams {
+        &self.params
+    }
+
+    /// 原地执行置换
+    ///
+    /// 每轮依次为：加轮常数、S-box（全轮作用于全部元素，部分轮只作用于第 0 个）、乘 MDS 矩阵。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(())`: 置换完成
+    /// - `Err(Box<dyn Error>)`: 状态长度与宽度不符
+    pub fn permute(&self, state: &mut [Fr]) -> Result<(), Box<dyn Error>> {
+        let params = &self.params;
+        if state.len() != params.width {
+            return Err(poseidon_error(format!(
+                "State has {} elements, expected {}",
+                state.len(),
+                params.width
+            )));
+        }
+
+        for round in 0..params.rounds() {
+            for (element, constant) in state.iter_mut().zip(params.round_constants(round)) {
+                *element += *constant;
+            }
+            if params.is_full_round(round) {
+                for element in state.iter_mut() {
+                    *element = sbox(*element);
+                }
+            } else {
+                state[0] = sbox(state[0]);
+            }
+
+            let mixed: Vec<Fr> = params
+                .mds
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(state.iter())
+                        .fold(Fr::ZERO, |acc, (m, s)| acc + *m * *s)
+                })
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+        Ok(())
+    }
+
+    /// 定长哈希：状态初始化为 `[0, inputs...]`，置换后输出第 0 个元素
+    ///
+    /// 与 circomlib `Poseidon(n)` 一致。
+    ///
+    /// # 参数
+    ///
+    /// * `inputs` - 恰好 `width - 1` 个域元素
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Fr)`: 哈希值
+    /// - `Err(Box<dyn Error>)`: 输入个数不符
+    pub fn hash(&self, inputs: &[Fr]) -> Result<Fr, Box<dyn Error>> {
+        if inputs.len() + 1 != self.params.width {
+            return Err(poseidon_error(format!(
+                "Expected {} inputs, got {}",
+                self.params.width - 1,
+                inputs.len()
+            )));
+        }
+        let mut state = vec![Fr::ZERO; self.params.width];
+        state[1..].copy_from_slice(inputs);
+        self.permute(&mut state)?;
+        Ok(state[0])
+    }
+}
+
+/// 对 1 到 4 个域元素做定长 Poseidon 哈希
+///
+/// 按输入个数选择宽度为 `inputs.len() + 1` 的标准参数。
+///
+/// # 返回
+///
+/// - `Ok(Fr)`: 哈希值
+/// - `Err(Box<dyn Error>)`: 输入个数不在 1 到 4 之间
+#[allow(dead_code)]
+pub fn poseidon_hash(inputs: &[Fr]) -> Result<Fr, Box<dyn Error>> {
+    Poseidon::new(inputs.len() + 1)?.hash(inputs)
+}
+
+/// Poseidon 海绵
+///
+/// 容量为 1 个元素（状态第 0 个），速率为 `width - 1`。
+/// 容量元素初始化为调用方给定的域分隔标签；第一次挤出前追加填充元素 1 再置换，
+/// 因此不同长度的输入不会产生相同的吸收状态。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct PoseidonSponge {
+    poseidon: Poseidon,
+    state: Vec<Fr>,
+    /// 当前速率部分的写入/读出位置（1..width）
+    position: usize,
+    squeezing: bool,
+}
+
+#[allow(dead_code)]
+impl PoseidonSponge {
+    /// 创建海绵
+    ///
+    /// # 参数
+    ///
+    /// * `width` - 状态宽度，2 到 5
+    /// * `domain` - 域分隔标签，不同用途应使用不同的值
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(PoseidonSponge)`: 海绵实例
+    /// - `Err(Box<dyn Error>)`: 宽度不在支持范围内
+    pub fn new(width: usize, domain: Fr) -> Result<Self, Box<dyn Error>> {
+        let poseidon = Poseidon::new(width)?;
+        let mut state = vec![Fr::ZERO; width];
+        state[0] = domain;
+        Ok(PoseidonSponge {
+            poseidon,
+            state,
+            position: 1,
+            squeezing: false,
+        })
+    }
+
+    fn permute(&mut self) {
+        self.poseidon
+            .permute(&mut self.state)
+            .expect("sponge state matches permutation width");
+        self.position = 1;
+    }
+
+    /// 吸收单个元素
+    fn absorb_one(&mut self, element: Fr) {
+        if self.position == self.state.len() {
+            self.permute();
+        }
+        self.state[self.position] += element;
+        self.position += 1;
+    }
+
+    /// 吸收域元素
+    ///
+    /// # Panics
+    ///
+    /// 已经开始挤出后再吸收会 panic。
+    pub fn absorb(&mut self, elements: &[Fr]) {
+        assert!(!self.squeezing, "cannot absorb after squeezing");
+        for element in elements {
+            self.absorb_one(*element);
+        }
+    }
+
+    /// 挤出一个域元素
+    pub fn squeeze(&mut self) -> Fr {
+        if !self.squeezing {
+            self.absorb_one(Fr::ONE);
+            self.squeezing = true;
+            self.permute();
+        } else if self.position == self.state.len() {
+            self.permute();
+        }
+        let output = self.state[self.position];
+        self.position += 1;
+        output
+    }
+}
+
+/// 以 Poseidon 海绵实现的字节哈希
+///
+/// 输入按 31 字节分块（保证小于模数）转为域元素，
+/// 容量元素以输入字节长度作域分隔，输出为挤出元素的 32 字节大端序编码。
+/// 可作为 `crypto::hash` 中 Merkle 树的哈希算法。
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoseidonHasher;
+
+impl Hasher for PoseidonHasher {
+    const NAME: &'static str = "poseidon-bn254";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        let mut sponge = PoseidonSponge::new(3, Fr::from(data.len() as u64))
+            .expect("width 3 has standard parameters");
+        for chunk in data.chunks(31) {
+            let mut bytes = [0u8; 32];
+            bytes[32 - chunk.len()..].copy_from_slice(chunk);
+            sponge.absorb(&[Fr::from_bytes_be_mod_order(&bytes)]);
+        }
+        sponge.squeeze().to_bytes_be()
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod crypto_poseidon_tests {
+    use crate::crypto::field::Fr;
+    use crate::crypto::hash::{
+        Hasher, MerkleLayout, calculate_merkle_root, merkle_branch, merkle_leaf,
+    };
+    use crate::crypto::poseidon::{
+        Poseidon, PoseidonHasher, PoseidonParams, PoseidonSponge, poseidon_hash,
+    };
+    use crate::crypto::test_util::hex_array;
+
+    /// 解析大端序十六进制常数，可带 `0x` 前缀，不足 64 位时左侧补零
+    fn fr(hex: &str) -> Fr {
+        let hex = format!("{:0>64}", hex.trim_start_matches("0x"));
+        Fr::from_bytes_be(&hex_array::<32>(&hex)).unwrap()
+    }
+
+    #[test]
+    fn test_field_arithmetic() {
+        let a = Fr::from(7);
+        let b = Fr::from(5);
+        assert_eq!(a + b, Fr::from(12));
+        assert_eq!(a - b, Fr::from(2));
+        assert_eq!(b - a + Fr::from(2), Fr::ZERO);
+        assert_eq!(a * b, Fr::from(35));
+        assert_eq!(-Fr::ONE + Fr::ONE, Fr::ZERO);
+        assert_eq!(Fr::from(u64::MAX) * Fr::from(u64::MAX), {
+            let m = Fr::from(u64::MAX);
+            m.square()
+        });
+
+        let inverse = a.inverse().unwrap();
+        assert_eq!(a * inverse, Fr::ONE);
+        assert_eq!(
+            inverse,
+            fr("06e9c21069503b73ac9dc0d0edede80d4ee2d80a5a8834a709b290cbfdb6db6e")
+        );
+        assert!(Fr::ZERO.inverse().is_none());
+    }
+
+    #[test]
+    fn test_field_encoding() {
+        let value = fr("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a");
+        assert_eq!(Fr::from_bytes_be(&value.to_bytes_be()).unwrap(), value);
+        assert_eq!(Fr::from(258).to_bytes_be()[30..], [1, 2]);
+
+        // r - 1 是最大的规范编码，r 本身被拒绝
+        let r_minus_one = fr("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000");
+        assert_eq!(r_minus_one + Fr::ONE, Fr::ZERO);
+        let mut modulus = r_minus_one.to_bytes_be();
+        modulus[31] = 1;
+        assert!(Fr::from_bytes_be(&modulus).is_err());
+        assert_eq!(Fr::from_bytes_be_mod_order(&modulus), Fr::ZERO);
+
+        assert_eq!(
+            Fr::from_bytes_be_mod_order(&[0xff; 32]),
+            fr("0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffa")
+        );
+    }
+
+    #[test]
+    fn test_grain_parameters_match_reference() {
+        let params = PoseidonParams::standard(3).unwrap();
+        assert_eq!(params.full_rounds(), 8);
+        assert_eq!(params.partial_rounds(), 57);
+        assert_eq!(
+            params.round_constants(0)[0],
+            fr("0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e")
+        );
+        assert_eq!(
+            params.mds()[0],
+            vec![
+                fr("109b7f411ba0e4c9b2b70caf5c36a7b194be7c11ad24378bfedb68592ba8118b"),
+                fr("16ed41e13bb9c0c66ae119424fddbcbc9314dc9fdbdeea55d6c64543dc4903e0"),
+                fr("2b90bba00fca0589f617e7dcbfe82e0df706ab640ceb247b791a93b74e36736d"),
+            ]
+        );
+        // 生成结果可复现，缓存返回同一份参数
+        assert_eq!(*params, PoseidonParams::generate(3, 8, 57).unwrap());
+        assert!(PoseidonParams::standard(6).is_err());
+        assert!(PoseidonParams::generate(3, 7, 57).is_err());
+    }
+
+    #[test]
+    fn test_permutation_reference_vector() {
+        // 参考实现 poseidonperm_x5_254_3 的测试向量
+        let mut state = [Fr::from(0), Fr::from(1), Fr::from(2)];
+        Poseidon::new(3).unwrap().permute(&mut state).unwrap();
+        assert_eq!(
+            state,
+            [
+                fr("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"),
+                fr("0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29"),
+                fr("0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c"),
+            ]
+        );
+        assert!(
+            Poseidon::new(3)
+                .unwrap()
+                .permute(&mut [Fr::ZERO; 2])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_hash_matches_circomlib() {
+        let inputs: Vec<Fr> = (1..=4).map(Fr::from).collect();
+        let expected = [
+            "29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133",
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+            "0e7732d89e6939c0ff03d5e58dab6302f3230e269dc5b968f725df34ab36d732",
+            "299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465",
+        ];
+        for (arity, digest) in expected.iter().enumerate() {
+            assert_eq!(poseidon_hash(&inputs[..arity + 1]).unwrap(), fr(digest));
+        }
+        assert!(poseidon_hash(&[]).is_err());
+        assert!(poseidon_hash(&[Fr::ONE; 5]).is_err());
+        assert!(Poseidon::new(3).unwrap().hash(&[Fr::ONE]).is_err());
+    }
+
+    #[test]
+    fn test_sponge_absorb_and_squeeze() {
+        let squeeze = |domain: u64, inputs: &[Fr]| {
+            let mut sponge = PoseidonSponge::new(3, Fr::from(domain)).unwrap();
+            sponge.absorb(inputs);
+            sponge.squeeze()
+        };
+        let inputs: Vec<Fr> = (1..=5).map(Fr::from).collect();
+
+        // 分批吸收与一次吸收结果相同
+        let mut sponge = PoseidonSponge::new(3, Fr::ZERO).unwrap();
+        sponge.absorb(&inputs[..1]);
+        sponge.absorb(&inputs[1..]);
+        let first = sponge.squeeze();
+        assert_eq!(first, squeeze(0, &inputs));
+        assert_ne!(sponge.squeeze(), first);
+
+        // 填充区分末尾的 0，容量元素区分用途
+        assert_ne!(
+            squeeze(0, &inputs[..2]),
+            squeeze(0, &[inputs[0], inputs[1], Fr::ZERO])
+        );
+        assert_ne!(squeeze(0, &inputs), squeeze(1, &inputs));
+        assert_ne!(squeeze(0, &[]), squeeze(0, &[Fr::ZERO]));
+    }
+
+    #[test]
+    fn test_poseidon_hasher_for_merkle_trees() {
+        assert_eq!(PoseidonHasher::hash(b"abc"), PoseidonHasher::hash(b"abc"));
+        assert_ne!(PoseidonHasher::hash(b"abc"), PoseidonHasher::hash(b"abd"));
+        assert_ne!(PoseidonHasher::hash(b""), PoseidonHasher::hash(&[0]));
+        assert_ne!(
+            PoseidonHasher::hash(&[1u8; 62]),
+            PoseidonHasher::hash(&[1u8; 63])
+        );
+        // 输出是规范的域元素编码
+        assert!(Fr::from_bytes_be(&PoseidonHasher::hash(&[0xffu8; 100])).is_ok());
+
+        let root = calculate_merkle_root::<PoseidonHasher>(&[b"a", b"b"], MerkleLayout::ZeroPad);
+        assert_eq!(
+            root,
+            merkle_branch::<PoseidonHasher>(
+                &merkle_leaf::<PoseidonHasher>(b"a"),
+                &merkle_leaf::<PoseidonHasher>(b"b")
+            )
+        );
+    }
+}
@@ -1,11 +1,21 @@
-/// RISC-V 硬件加速器抽象模块
-///
-/// 该模块定义了 RISC-V 加密扩展的硬件加速接口。
+//! RISC-V 硬件加速器抽象模块
+//!
+//! 该模块定义了 RISC-V 加密扩展的硬件加速接口。
 
+use crate::crypto::aes::Aes256Riscv;
+use crate::crypto::ec::software as ed25519;
+use crate::crypto::keccak::keccak_f1600_bytes;
+use crate::crypto::secret::SecretVec;
+use crate::crypto::sha256::Sha256Riscv;
+use aes_gcm::aes::Aes256;
+use aes_gcm::aes::cipher::{BlockDecrypt, BlockEncrypt};
+use aes_gcm::{AeadInPlace, Aes256Gcm, KeyInit};
+use sha2::{Digest, Sha256};
 #[allow(dead_code)]
 use std::error::Error;
 #[allow(dead_code)]
 use std::fmt;
+use std::sync::Arc;
 
 /// RISC-V 加密操作中发生的错误
 #[allow(dead_code)]
@@ -29,21 +39,74 @@ impl Error for RiscVCryptoError {}
 ///
 /// # 实现者
 ///
+/// - `SoftwareBackend`: 纯软件实现
 /// - `HardwareAccelerator`: 模拟硬件加速器
-/// - Mock 实现: 用于单元测试
+/// - `DeviceClient`: 通过设备协议卸载到协处理器
+/// - `ZknBackend`: 由 Zkn 标量加密指令（模型 `ZknEmulator` 或硬件 `ZknHardware`）组合
+/// - `MockAccelerator`: 故障注入与调用记录，用于测试错误处理路径
+///
+/// 运行时按名称选择实现见 `crate::crypto::backend`。
+///
+/// 实现必须是 `Send + Sync`，以便同一个后端实例在线程间共享（见 `default_backend()`）。
 #[allow(dead_code)]
-pub trait RiscVCryptoExt {
-    /// AES-256 加密
-    fn copr_encrypt_aes256(&self, input: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, Box<dyn Error>>;
-    /// AES-256 解密
-    fn copr_decrypt_aes256(&self, input: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, Box<dyn Error>>;
+pub trait RiscVCryptoExt: Send + Sync {
+    /// AES-256-GCM 加密，返回 `(ciphertext, tag)`
+    fn copr_encrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>>;
+    /// AES-256-GCM 解密，认证标签不匹配时返回错误
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>>;
+    /// AES-256 单分组加密
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>>;
+    /// AES-256 单分组解密
+    fn copr_aes256_decrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>>;
+    /// AES-256 密钥调度
+    ///
+    /// 返回绑定 `key` 的分组变换，之后的分组运算不再重复展开密钥。
+    /// 用于分组密码模式等需要同一密钥处理多个分组的场景。
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>>;
     /// SHA-256 哈希
     fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>>;
-    /// 椭圆曲线标量乘法
+    /// SHA-256 单块压缩函数
+    ///
+    /// `state` 为 8 个 32 位状态字的大端序编码，返回处理 64 字节消息块后的新状态。
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>>;
+    /// Keccak-f[1600] 置换
+    ///
+    /// `state` 为 25 个 64 位 lane 的小端序编码，lane `(x, y)` 位于第 `x + 5y` 个，
+    /// 返回置换后的状态。
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>>;
+    /// Edwards25519 标量乘法，点使用 RFC 8032 压缩编码，标量对群阶取模
     fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>>;
-    /// 椭圆曲线点加法
+    /// Edwards25519 点加法，点使用 RFC 8032 压缩编码
     fn copr_ec_add(&self, point1: &[u8; 32], point2: &[u8; 32])
-        -> Result<[u8; 32], Box<dyn Error>>;
+    -> Result<[u8; 32], Box<dyn Error>>;
     /// Ed25519 签名
     fn copr_sign_ed25519(
         &self,
@@ -57,6 +120,20 @@ pub trait RiscVCryptoExt {
         message: &[u8],
         signature: &[u8; 64],
     ) -> Result<bool, Box<dyn Error>>;
+    /// Ed25519 批量验证，返回与 `items` 一一对应的结果
+    ///
+    /// 默认逐个调用 `copr_verify_ed25519`；支持多标量乘法的后端应覆盖此方法。
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        items
+            .iter()
+            .map(|(public, message, signature)| {
+                self.copr_verify_ed25519(public, message, signature)
+            })
+            .collect()
+    }
     /// ZK Proof 生成
     fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>>;
     /// ZK Proof 验证
@@ -69,6 +146,132 @@ pub trait RiscVCryptoExt {
     ) -> Result<Vec<u8>, Box<dyn Error>>;
 }
 
+/// 绑定密钥的 AES-256 分组变换，由 `RiscVCryptoExt::copr_aes256_key_schedule` 返回
+///
+/// 结果与以同一密钥调用 `copr_aes256_encrypt_block` / `copr_aes256_decrypt_block` 一致。
+#[allow(dead_code)]
+pub trait Aes256KeySchedule: Send + Sync {
+    /// 加密单个分组
+    fn encrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>>;
+    /// 解密单个分组
+    fn decrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>>;
+}
+
+/// 共享后端：`Arc` 的所有克隆使用同一个后端实例（如同一条设备连接）
+impl<T: RiscVCryptoExt + ?Sized> RiscVCryptoExt for Arc<T> {
+    fn copr_encrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        (**self).copr_encrypt_aes256(key, nonce, aad, plaintext)
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        (**self).copr_decrypt_aes256(key, nonce, aad, ciphertext, tag)
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        (**self).copr_aes256_encrypt_block(key, block)
+    }
+
+    fn copr_aes256_decrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        (**self).copr_aes256_decrypt_block(key, block)
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        (**self).copr_aes256_key_schedule(key)
+    }
+
+    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        (**self).copr_hash_sha256(input)
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        (**self).copr_sha256_compress(state, block)
+    }
+
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        (**self).copr_keccak_f1600(state)
+    }
+
+    fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>> {
+        (**self).copr_ec_mul(scalar, point)
+    }
+
+    fn copr_ec_add(
+        &self,
+        point1: &[u8; 32],
+        point2: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        (**self).copr_ec_add(point1, point2)
+    }
+
+    fn copr_sign_ed25519(
+        &self,
+        secret: &[u8; 32],
+        message: &[u8],
+    ) -> Result<[u8; 64], Box<dyn Error>> {
+        (**self).copr_sign_ed25519(secret, message)
+    }
+
+    fn copr_verify_ed25519(
+        &self,
+        public: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, Box<dyn Error>> {
+        (**self).copr_verify_ed25519(public, message, signature)
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        (**self).copr_verify_ed25519_batch(items)
+    }
+
+    fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        (**self).copr_zkp_prove(witness)
+    }
+
+    fn copr_zkp_verify(&self, proof: &[u8], public_input: &[u8]) -> Result<bool, Box<dyn Error>> {
+        (**self).copr_zkp_verify(proof, public_input)
+    }
+
+    fn copr_msm_accelerate(
+        &self,
+        points: &[Vec<u8>],
+        scalars: &[Vec<u8>],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        (**self).copr_msm_accelerate(points, scalars)
+    }
+}
+
 /// RISC-V 硬件加速器模拟实现
 ///
 /// 该结构体模拟 RISC-V 加密扩展硬件加速器的行为。
@@ -77,7 +280,8 @@ pub trait RiscVCryptoExt {
 /// # 特性
 ///
 /// - 模拟模式：即使没有真实加速器也能工作
-/// - 设备路径：模拟 `/dev/crypto0` 设备
+/// - 设备路径：默认 `/dev/crypto0`，`open_device()` 返回通过设备协议访问它的后端
+///   （见 `crate::crypto::device`）
 /// - 可禁用：可以关闭加速器模拟错误情况
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
@@ -87,9 +291,9 @@ pub struct HardwareAccelerator {
     /// 为 `false` 时，所有操作返回错误。
     /// 用于测试错误处理路径。
     enabled: bool,
-    /// 模拟设备路径
+    /// 设备路径
     ///
-    /// 模拟 RISC-V 加密设备的文件路径。
+    /// RISC-V 加密协处理器的字符设备或 Unix socket 路径。
     device_path: String,
 }
 
@@ -107,69 +311,222 @@ impl HardwareAccelerator {
             device_path: "/dev/crypto0".to_string(),
         }
     }
+
+    /// 启用或禁用加速器
+    ///
+    /// 禁用后所有操作返回错误，调用方应回退到软件实现。
+    #[allow(dead_code)]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// 设置设备路径
+    #[allow(dead_code)]
+    pub fn set_device_path(&mut self, device_path: impl Into<String>) {
+        self.device_path = device_path.into();
+    }
+
+    /// 设备路径
+    #[allow(dead_code)]
+    pub fn device_path(&self) -> &str {
+        &self.device_path
+    }
+
+    /// 创建通过设备协议访问 `device_path` 的后端
+    ///
+    /// 设备在首次调用时才打开。
+    ///
+    /// # 返回
+    ///
+    /// 指向本加速器设备路径的 `DeviceClient`
+    #[allow(dead_code)]
+    pub fn open_device(&self) -> crate::crypto::device::DeviceClient {
+        crate::crypto::device::DeviceClient::new(&self.device_path)
+    }
+}
+
+/// `HardwareAccelerator` 的密钥调度：创建时展开一次密钥
+struct HardwareKeySchedule(Aes256);
+
+impl Aes256KeySchedule for HardwareKeySchedule {
+    fn encrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        let mut output = (*block).into();
+        self.0.encrypt_block(&mut output);
+        Ok(output.into())
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        let mut output = (*block).into();
+        self.0.decrypt_block(&mut output);
+        Ok(output.into())
+    }
+}
+
+/// 加速器被禁用时返回的错误
+fn disabled_error() -> Box<dyn Error> {
+    Box::new(RiscVCryptoError {
+        message: "Accelerator disabled".to_string(),
+    })
+}
+
+/// AES-256-GCM 加密，返回分离的密文和标签
+fn gcm_seal(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+    let cipher = Aes256Gcm::new(key.into());
+    let mut buffer = plaintext.to_vec();
+    let tag = cipher
+        .encrypt_in_place_detached(nonce.into(), aad, &mut buffer)
+        .map_err(|_| {
+            Box::new(RiscVCryptoError {
+                message: "AES-GCM encryption failed".to_string(),
+            })
+        })?;
+
+    Ok((buffer, tag.into()))
+}
+
+/// AES-256-GCM 解密并校验标签
+fn gcm_open(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8; 16],
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let cipher = Aes256Gcm::new(key.into());
+    let mut buffer = ciphertext.to_vec();
+    cipher
+        .decrypt_in_place_detached(nonce.into(), aad, &mut buffer, tag.into())
+        .map_err(|_| {
+            Box::new(RiscVCryptoError {
+                message: "AES-GCM authentication failed".to_string(),
+            })
+        })?;
+
+    Ok(buffer)
 }
 
 impl RiscVCryptoExt for HardwareAccelerator {
     fn copr_encrypt_aes256(
         &self,
-        input: &[u8],
-        _key: &[u8; 32],
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
+        }
+
+        gcm_seal(key, nonce, aad, plaintext)
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
     ) -> Result<Vec<u8>, Box<dyn Error>> {
         if !self.enabled {
-            return Err(Box::new(RiscVCryptoError {
-                message: "Accelerator disabled".to_string(),
-            }));
+            return Err(disabled_error());
         }
 
-        let mut output = vec![0u8; 16.max(input.len())];
-        for (i, byte) in input.iter().enumerate() {
-            if i < output.len() {
-                output[i] = *byte ^ 0x42;
-            }
+        gcm_open(key, nonce, aad, ciphertext, tag)
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
         }
 
-        Ok(output)
+        let cipher = Aes256::new(key.into());
+        let mut output = (*block).into();
+        cipher.encrypt_block(&mut output);
+        Ok(output.into())
     }
 
-    fn copr_decrypt_aes256(
+    fn copr_aes256_decrypt_block(
         &self,
-        input: &[u8],
-        _key: &[u8; 32],
-    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
         if !self.enabled {
-            return Err(Box::new(RiscVCryptoError {
-                message: "Accelerator disabled".to_string(),
-            }));
+            return Err(disabled_error());
         }
 
-        if input.is_empty() {
-            return Err(Box::new(RiscVCryptoError {
-                message: "Invalid input".to_string(),
-            }));
+        let cipher = Aes256::new(key.into());
+        let mut output = (*block).into();
+        cipher.decrypt_block(&mut output);
+        Ok(output.into())
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
         }
 
-        let mut output = input.to_vec();
-        for byte in output.iter_mut() {
-            *byte ^= 0x42;
+        Ok(Box::new(HardwareKeySchedule(Aes256::new(key.into()))))
+    }
+
+    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
+        }
+
+        Ok(Sha256::digest(input).into())
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
         }
 
+        let mut words = [0u32; 8];
+        for (word, bytes) in words.iter_mut().zip(state.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        sha2::compress256(&mut words, &[(*block).into()]);
+
+        let mut output = [0u8; 32];
+        for (bytes, word) in output.chunks_exact_mut(4).zip(words.iter()) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
         Ok(output)
     }
 
-    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
-        let mut hash = [0u8; 32];
-        for (i, byte) in input.iter().enumerate().take(32) {
-            hash[i] = *byte;
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
         }
-        Ok(hash)
+
+        Ok(keccak_f1600_bytes(state))
     }
 
+    // 以下运算模拟设备没有专用单元，启用时委托给软件后端
+
     fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>> {
-        let mut result = [0u8; 32];
-        for i in 0..32 {
-            result[i] = scalar[i] ^ point[i];
+        if !self.enabled {
+            return Err(disabled_error());
         }
-        Ok(result)
+
+        SoftwareBackend.copr_ec_mul(scalar, point)
     }
 
     fn copr_ec_add(
@@ -177,11 +534,11 @@ impl RiscVCryptoExt for HardwareAccelerator {
         point1: &[u8; 32],
         point2: &[u8; 32],
     ) -> Result<[u8; 32], Box<dyn Error>> {
-        let mut result = [0u8; 32];
-        for i in 0..32 {
-            result[i] = point1[i] ^ point2[i];
+        if !self.enabled {
+            return Err(disabled_error());
         }
-        Ok(result)
+
+        SoftwareBackend.copr_ec_add(point1, point2)
     }
 
     fn copr_sign_ed25519(
@@ -189,31 +546,192 @@ impl RiscVCryptoExt for HardwareAccelerator {
         secret: &[u8; 32],
         message: &[u8],
     ) -> Result<[u8; 64], Box<dyn Error>> {
-        let mut signature = [0u8; 64];
-        for i in 0..32 {
-            signature[i] = secret[i];
-        }
-        for (i, byte) in message.iter().enumerate().take(32) {
-            signature[32 + i] = *byte;
+        if !self.enabled {
+            return Err(disabled_error());
         }
-        Ok(signature)
+
+        SoftwareBackend.copr_sign_ed25519(secret, message)
     }
 
     fn copr_verify_ed25519(
         &self,
-        _public: &[u8; 32],
+        public: &[u8; 32],
         message: &[u8],
         signature: &[u8; 64],
     ) -> Result<bool, Box<dyn Error>> {
-        let mut check = true;
-        for i in 0..32 {
-            let msg_byte = if i < message.len() { message[i] } else { 0 };
-            let expected_part2 = msg_byte;
-            if signature[32 + i] != expected_part2 {
-                check = false;
-            }
+        if !self.enabled {
+            return Err(disabled_error());
+        }
+
+        SoftwareBackend.copr_verify_ed25519(public, message, signature)
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
+        }
+
+        SoftwareBackend.copr_verify_ed25519_batch(items)
+    }
+
+    fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
+        }
+
+        SoftwareBackend.copr_zkp_prove(witness)
+    }
+
+    fn copr_zkp_verify(&self, proof: &[u8], public_input: &[u8]) -> Result<bool, Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
+        }
+
+        SoftwareBackend.copr_zkp_verify(proof, public_input)
+    }
+
+    fn copr_msm_accelerate(
+        &self,
+        points: &[Vec<u8>],
+        scalars: &[Vec<u8>],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        if !self.enabled {
+            return Err(disabled_error());
         }
-        Ok(check)
+
+        SoftwareBackend.copr_msm_accelerate(points, scalars)
+    }
+}
+
+/// 纯软件后端
+///
+/// 不依赖任何加速器，所有运算都使用本 crate 的软件实现：
+/// AES 分组运算使用 `Aes256Riscv` 的无查表实现，SHA-256 使用 `Sha256Riscv`
+/// 的软件压缩函数，Keccak-f[1600] 使用 `keccak::keccak_f1600`，Ed25519 使用 `ec::software`。
+/// 用作没有 RISC-V 加密扩展的平台上的默认回退，也是其他后端的参考实现。
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SoftwareBackend;
+
+/// `SoftwareBackend` 的密钥调度：持有展开后的轮密钥（释放时清零）
+struct SoftwareKeySchedule(SecretVec);
+
+impl Aes256KeySchedule for SoftwareKeySchedule {
+    fn encrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(Aes256Riscv::cipher(block, self.0.expose_secret()))
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(Aes256Riscv::inv_cipher(block, self.0.expose_secret()))
+    }
+}
+
+impl RiscVCryptoExt for SoftwareBackend {
+    fn copr_encrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        gcm_seal(key, nonce, aad, plaintext)
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        gcm_open(key, nonce, aad, ciphertext, tag)
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(Aes256Riscv::cipher(
+            block,
+            Aes256Riscv::expand_key(key).expose_secret(),
+        ))
+    }
+
+    fn copr_aes256_decrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(Aes256Riscv::inv_cipher(
+            block,
+            Aes256Riscv::expand_key(key).expose_secret(),
+        ))
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        Ok(Box::new(SoftwareKeySchedule(Aes256Riscv::expand_key(key))))
+    }
+
+    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        let mut hasher = Sha256Riscv::with_accelerator(Box::new(SoftwareBackend));
+        hasher.update(input);
+        Ok(hasher.finalize())
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        Ok(Sha256Riscv::compress_software(state, block))
+    }
+
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        Ok(keccak_f1600_bytes(state))
+    }
+
+    fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>> {
+        ed25519::scalar_mul(scalar, point)
+    }
+
+    fn copr_ec_add(
+        &self,
+        point1: &[u8; 32],
+        point2: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        ed25519::point_add(point1, point2)
+    }
+
+    fn copr_sign_ed25519(
+        &self,
+        secret: &[u8; 32],
+        message: &[u8],
+    ) -> Result<[u8; 64], Box<dyn Error>> {
+        Ok(ed25519::sign(secret, message))
+    }
+
+    fn copr_verify_ed25519(
+        &self,
+        public: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, Box<dyn Error>> {
+        Ok(ed25519::verify(public, message, signature))
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        Ok(ed25519::verify_batch_each(items))
     }
 
     fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
@@ -1,14 +1,16 @@
 #[cfg(test)]
 mod riscv_crypto_tests {
     use crate::crypto::aes::Aes256Riscv;
-    use crate::crypto::ec::Ed25519Riscv;
+    use crate::crypto::ec::{Ed25519Riscv, software};
     use crate::crypto::riscv_ext::RiscVCryptoExt;
+    use crate::crypto::secret::{Aes256Key, Ed25519SecretKey};
     use crate::crypto::sha256::Sha256Riscv;
+    use crate::crypto::test_util::hex;
 
     #[test]
     fn test_aes256_riscv_encrypt_decrypt() {
         let crypto = Aes256Riscv::new();
-        let key = [0u8; 32];
+        let key = Aes256Key::new([0u8; 32]);
         let plaintext = b"Hello, ZK Rollup with RISC-V!";
 
         let encrypted = crypto.encrypt_aes256(plaintext, &key).unwrap();
@@ -20,7 +22,7 @@ mod riscv_crypto_tests {
     #[test]
     fn test_aes256_riscv_key_expansion() {
         let mut crypto = Aes256Riscv::new();
-        let key = [0u8; 32];
+        let key = Aes256Key::new([0u8; 32]);
         let expanded = crypto.key_expansion(&key).unwrap();
 
         assert_eq!(expanded.len(), 240); // 15 rounds * 16 bytes + 16
@@ -46,19 +48,101 @@ mod riscv_crypto_tests {
         assert_eq!(final_hash.len(), 32);
     }
 
+    #[test]
+    fn test_sha256_riscv_fips180_vectors() {
+        let vectors: [(&[u8], &str); 3] = [
+            (
+                b"",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                b"abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ),
+        ];
+
+        for (message, expected) in vectors {
+            assert_eq!(Sha256Riscv::hash(message).to_vec(), hex(expected));
+        }
+    }
+
+    #[test]
+    fn test_sha256_accelerated_and_software_paths_match_sha2() {
+        use crate::crypto::riscv_ext::HardwareAccelerator;
+        use sha2::{Digest, Sha256};
+
+        let mut disabled = HardwareAccelerator::new();
+        disabled.set_enabled(false);
+
+        for len in (0..300).step_by(7) {
+            let data: Vec<u8> = (0..len).map(|_| rand::random()).collect();
+            let expected: [u8; 32] = Sha256::digest(&data).into();
+
+            let mut accelerated =
+                Sha256Riscv::with_accelerator(Box::new(HardwareAccelerator::new()));
+            let mut software = Sha256Riscv::with_accelerator(Box::new(disabled.clone()));
+            let split = len / 3;
+            accelerated.update(&data[..split]);
+            accelerated.update(&data[split..]);
+            software.update(&data[..split]);
+            software.update(&data[split..]);
+
+            assert_eq!(accelerated.finalize(), expected);
+            assert_eq!(software.finalize(), expected);
+            assert_eq!(
+                HardwareAccelerator::new().copr_hash_sha256(&data).unwrap(),
+                expected
+            );
+        }
+    }
+
     #[test]
     fn test_ed25519_riscv_keygen() {
         let ec = Ed25519Riscv::new();
-        let (sk, pk) = ec.keygen();
+        let (sk, pk) = ec.keygen().unwrap();
 
-        assert_eq!(sk.len(), 32);
+        assert_eq!(sk.expose_secret().len(), 32);
         assert_eq!(pk.len(), 32);
     }
 
+    #[test]
+    fn test_ed25519_riscv_keygen_is_random_and_consistent() {
+        let ec = Ed25519Riscv::new();
+        let (sk1, pk1) = ec.keygen().unwrap();
+        let (sk2, pk2) = ec.keygen().unwrap();
+
+        assert_ne!(sk1, sk2);
+        assert_ne!(pk1, pk2);
+        assert_eq!(software::public_key(sk1.expose_secret()), pk1);
+        assert_eq!(software::public_key(sk2.expose_secret()), pk2);
+    }
+
+    #[test]
+    fn test_ed25519_riscv_keygen_from_seed() {
+        let ec = Ed25519Riscv::new();
+        let seed: [u8; 32] =
+            hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .try_into()
+                .unwrap();
+
+        let (sk, pk) = ec.keygen_from_seed(&seed);
+        assert_eq!(sk.expose_secret(), &seed);
+        assert_eq!(
+            pk.to_vec(),
+            hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+        assert_eq!(ec.keygen_from_seed(&seed), (sk, pk));
+        assert_ne!(ec.keygen_from_seed(&[1u8; 32]).1, pk);
+    }
+
     #[test]
     fn test_ed25519_riscv_sign_verify() {
         let ec = Ed25519Riscv::new();
-        let (sk, pk) = ec.keygen();
+        let (sk, pk) = ec.keygen().unwrap();
         let message = b"Test message for signature";
 
         let signature = ec.sign(&sk, message).unwrap();
@@ -67,12 +151,372 @@ mod riscv_crypto_tests {
         assert!(valid);
     }
 
+    #[test]
+    fn test_ed25519_rfc8032_vectors() {
+        // RFC 8032 Section 7.1, TEST 1-3
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+
+        let ec = Ed25519Riscv::new();
+        for (sk, pk, msg, sig) in vectors {
+            let sk = Ed25519SecretKey::from_slice(&hex(sk)).unwrap();
+            let pk: [u8; 32] = hex(pk).try_into().unwrap();
+            let sig: [u8; 64] = hex(sig).try_into().unwrap();
+            let msg = hex(msg);
+
+            assert_eq!(software::public_key(sk.expose_secret()), pk);
+            assert_eq!(ec.sign(&sk, &msg).unwrap(), sig);
+            assert!(ec.verify(&pk, &msg, &sig).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_ed25519_riscv_rejects_forgeries() {
+        let ec = Ed25519Riscv::new();
+        let (sk, pk) = ec.keygen().unwrap();
+        let message = b"transfer 100 from 0 to 1";
+        let signature = ec.sign(&sk, message).unwrap();
+
+        // 错误的公钥
+        let other_pk = software::public_key(&[7u8; 32]);
+        assert!(!ec.verify(&other_pk, message, &signature).unwrap());
+
+        // 篡改的消息
+        let altered = b"transfer 900 from 0 to 1";
+        assert!(!ec.verify(&pk, altered, &signature).unwrap());
+
+        // 篡改的签名
+        let mut tampered = signature;
+        tampered[40] ^= 0x01;
+        assert!(!ec.verify(&pk, message, &tampered).unwrap());
+
+        // 非规范 S（S + L）必须被拒绝
+        let mut s_plus_l = signature;
+        let l: [u8; 32] = hex("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010")
+            .try_into()
+            .unwrap();
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let sum = s_plus_l[32 + i] as u16 + l[i] as u16 + carry;
+            s_plus_l[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        assert!(!ec.verify(&pk, message, &s_plus_l).unwrap());
+    }
+
+    #[test]
+    fn test_ed25519_verify_rejects_non_canonical_public_key() {
+        use crate::crypto::ec::point_operation::{ED25519_BASE_POINT, ED25519_IDENTITY};
+        use curve25519_dalek::edwards::CompressedEdwardsY;
+
+        // y = p + 1 解压为单位元 A = O，此时 (R, S) = ([r]B, r) 对任意消息都满足 [S]B = R + [k]A
+        let mut non_canonical = [0xffu8; 32];
+        non_canonical[0] = 0xee;
+        non_canonical[31] = 0x7f;
+        assert!(CompressedEdwardsY(non_canonical).decompress().is_some());
+
+        let r = [3u8; 32];
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&software::scalar_mul(&r, &ED25519_BASE_POINT).unwrap());
+        signature[32..].copy_from_slice(&r);
+        let message = b"transfer 100 from 0 to 1";
+
+        // 同一个点的规范编码满足验证方程，拒绝只来自编码检查
+        let ec = Ed25519Riscv::new();
+        assert!(ec.verify(&ED25519_IDENTITY, message, &signature).unwrap());
+        assert!(!ec.verify(&non_canonical, message, &signature).unwrap());
+        assert_eq!(
+            ec.find_invalid(&[(non_canonical, message.as_slice(), signature)])
+                .unwrap(),
+            vec![0]
+        );
+    }
+
     #[test]
     fn test_riscv_crypto_ext_operations() {
         use crate::crypto::riscv_ext::HardwareAccelerator;
         let ext = HardwareAccelerator::new();
 
-        let result = ext.copr_encrypt_aes256(b"test", &[0u8; 32]).unwrap();
-        assert!(result.len() >= 4);
+        let (ciphertext, tag) = ext
+            .copr_encrypt_aes256(&[0u8; 32], &[0u8; 12], &[], b"test")
+            .unwrap();
+        assert_eq!(ciphertext.len(), 4);
+
+        let plaintext = ext
+            .copr_decrypt_aes256(&[0u8; 32], &[0u8; 12], &[], &ciphertext, &tag)
+            .unwrap();
+        assert_eq!(plaintext, b"test");
+    }
+
+    /// 生成 `count` 个由不同私钥签名的批量验证项
+    fn batch_items(count: usize) -> Vec<([u8; 32], Vec<u8>, [u8; 64])> {
+        (0..count)
+            .map(|i| {
+                let secret = [i as u8 + 1; 32];
+                let message = format!("transfer {} from {} to {}", i * 10, i, i + 1).into_bytes();
+                let signature = software::sign(&secret, &message);
+                (software::public_key(&secret), message, signature)
+            })
+            .collect()
+    }
+
+    fn as_refs(items: &[([u8; 32], Vec<u8>, [u8; 64])]) -> Vec<([u8; 32], &[u8], [u8; 64])> {
+        items
+            .iter()
+            .map(|(public, message, signature)| (*public, message.as_slice(), *signature))
+            .collect()
+    }
+
+    #[test]
+    fn test_ed25519_verify_batch_accepts_valid_batch() {
+        let ec = Ed25519Riscv::new();
+        let items = batch_items(64);
+
+        assert!(ec.verify_batch(&as_refs(&items)).unwrap());
+        assert!(ec.verify_batch(&[]).unwrap());
+        assert!(
+            software::verify_batch_each(&as_refs(&items))
+                .iter()
+                .all(|valid| *valid)
+        );
+    }
+
+    #[test]
+    fn test_ed25519_find_invalid_locates_failures() {
+        let ec = Ed25519Riscv::new();
+        let mut items = batch_items(50);
+
+        // 篡改签名、篡改消息、错误公钥、非规范 S 和无法解码的公钥
+        items[3].2[40] ^= 0x01;
+        items[17].1.push(b'!');
+        items[29].0 = items[30].0;
+        items[41].2[63] = 0xff;
+        items[48].0 = [0xff; 32];
+        items[48].0[31] = 0x7f;
+
+        let refs = as_refs(&items);
+        assert!(!ec.verify_batch(&refs).unwrap());
+        assert_eq!(ec.find_invalid(&refs).unwrap(), vec![3, 17, 29, 41, 48]);
+
+        // 结果与逐个验证一致
+        let each = software::verify_batch_each(&refs);
+        for (valid, (public, message, signature)) in each.iter().zip(&refs) {
+            assert_eq!(*valid, software::verify(public, message, signature));
+        }
+    }
+
+    #[test]
+    fn test_ed25519_single_and_batch_agree_on_torsion_components() {
+        use curve25519_dalek::constants::EIGHT_TORSION;
+        use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+        use curve25519_dalek::scalar::Scalar;
+        use sha2::{Digest, Sha512};
+
+        let ec = Ed25519Riscv::new();
+        let secret = [11u8; 32];
+        let message = b"transfer 100 from 0 to 1";
+        let public = software::public_key(&secret);
+        let torsion = EIGHT_TORSION[1];
+
+        let mut a_bytes: [u8; 32] = Sha512::digest(secret)[..32].try_into().unwrap();
+        a_bytes[0] &= 248;
+        a_bytes[31] &= 127;
+        a_bytes[31] |= 64;
+        let a = Scalar::from_bytes_mod_order(a_bytes);
+        let nonce = Scalar::from_bytes_mod_order([5u8; 32]);
+        let big_r = EdwardsPoint::mul_base(&nonce);
+        // S = r + H(R || A || M) * a，R 和 A 按给定编码参与哈希
+        let sign = |r_bytes: [u8; 32], public: [u8; 32]| {
+            let mut wide = [0u8; 64];
+            wide.copy_from_slice(&Sha512::digest([&r_bytes[..], &public, message].concat()));
+            let k = Scalar::from_bytes_mod_order_wide(&wide);
+            let mut signature = [0u8; 64];
+            signature[..32].copy_from_slice(&r_bytes);
+            signature[32..].copy_from_slice((nonce + k * a).as_bytes());
+            signature
+        };
+
+        // R' = R + T 和 A' = A + T：满足带余因子的方程，不满足不带余因子的方程
+        let tweaked_r = sign((big_r + torsion).compress().to_bytes(), public);
+        let tweaked_public = (CompressedEdwardsY(public).decompress().unwrap() + torsion)
+            .compress()
+            .to_bytes();
+        let tweaked_a = sign(big_r.compress().to_bytes(), tweaked_public);
+
+        for (public, signature) in [(public, tweaked_r), (tweaked_public, tweaked_a)] {
+            let single = ec.verify(&public, message, &signature).unwrap();
+            let batch = ec
+                .find_invalid(&[(public, message.as_slice(), signature)])
+                .unwrap();
+            assert!(single);
+            assert_eq!(single, batch.is_empty());
+        }
+
+        // 混入有效签名的大批次走合并验证，结果同样不变
+        let mut items = batch_items(16);
+        items[4] = (public, message.to_vec(), tweaked_r);
+        items[9] = (tweaked_public, message.to_vec(), tweaked_a);
+        assert!(ec.find_invalid(&as_refs(&items)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ed25519_batch_goes_through_accelerator() {
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+        use crate::crypto::riscv_ext::SoftwareBackend;
+
+        let mut items = batch_items(8);
+        items[5].2[0] ^= 0x01;
+        let refs = as_refs(&items);
+        let expected = SoftwareBackend.copr_verify_ed25519_batch(&refs).unwrap();
+        assert_eq!(expected.iter().filter(|valid| !**valid).count(), 1);
+
+        let mock = MockAccelerator::new();
+        let ec = Ed25519Riscv::with_accelerator(Box::new(mock.clone()));
+        assert_eq!(ec.find_invalid(&refs).unwrap(), vec![5]);
+        assert_eq!(mock.call_count(MockOperation::VerifyEd25519Batch), 1);
+        assert_eq!(mock.call_count(MockOperation::VerifyEd25519), 0);
+
+        let failing = Ed25519Riscv::with_accelerator(Box::new(
+            MockAccelerator::new().fail_operation(MockOperation::VerifyEd25519Batch),
+        ));
+        assert!(failing.verify_batch(&refs).is_err());
+    }
+
+    #[test]
+    fn test_ed25519_scalar_mul_matches_rfc8032_public_key() {
+        use crate::crypto::ec::point_operation::ED25519_BASE_POINT;
+        use sha2::{Digest, Sha512};
+
+        // RFC 8032 7.1 TEST 1：A = [a]B，a 为 clamp(SHA-512(secret)[..32])
+        let secret: [u8; 32] =
+            hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .try_into()
+                .unwrap();
+        let mut a: [u8; 32] = Sha512::digest(secret)[..32].try_into().unwrap();
+        a[0] &= 248;
+        a[31] &= 127;
+        a[31] |= 64;
+
+        let ec = Ed25519Riscv::new();
+        assert_eq!(
+            ec.scalar_mul(&a, &ED25519_BASE_POINT).unwrap().to_vec(),
+            hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+    }
+
+    #[test]
+    fn test_ed25519_point_arithmetic_group_laws() {
+        use crate::crypto::ec::point_operation::{
+            ED25519_BASE_POINT, ED25519_GROUP_ORDER, ED25519_IDENTITY,
+        };
+
+        let ec = Ed25519Riscv::new();
+        let b = ED25519_BASE_POINT;
+        let scalar = |n: u64| {
+            let mut bytes = [0u8; 32];
+            bytes[..8].copy_from_slice(&n.to_le_bytes());
+            bytes
+        };
+
+        assert_eq!(ec.scalar_mul(&scalar(1), &b).unwrap(), b);
+        assert_eq!(ec.scalar_mul(&scalar(0), &b).unwrap(), ED25519_IDENTITY);
+        assert_eq!(ec.point_add(&b, &ED25519_IDENTITY).unwrap(), b);
+
+        let two_b = ec.point_add(&b, &b).unwrap();
+        assert_eq!(ec.scalar_mul(&scalar(2), &b).unwrap(), two_b);
+
+        // (x + y)P = xP + yP，结果与加速器无关
+        let p = ec.scalar_mul(&scalar(0xdead_beef), &b).unwrap();
+        let lhs = ec.scalar_mul(&scalar(1000 + 2345), &p).unwrap();
+        let rhs = ec
+            .point_add(
+                &ec.scalar_mul(&scalar(1000), &p).unwrap(),
+                &ec.scalar_mul(&scalar(2345), &p).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(lhs, rhs);
+
+        // [L]B = O，[L - 1]B + B = O
+        assert_eq!(
+            ec.scalar_mul(&ED25519_GROUP_ORDER, &b).unwrap(),
+            ED25519_IDENTITY
+        );
+        let mut l_minus_one = ED25519_GROUP_ORDER;
+        l_minus_one[0] -= 1;
+        let minus_b = ec.scalar_mul(&l_minus_one, &b).unwrap();
+        assert_eq!(ec.point_add(&minus_b, &b).unwrap(), ED25519_IDENTITY);
+    }
+
+    #[test]
+    fn test_ed25519_point_validation() {
+        use crate::crypto::ec::point_operation::{ED25519_BASE_POINT, ED25519_IDENTITY};
+        use curve25519_dalek::constants::EIGHT_TORSION;
+        use curve25519_dalek::edwards::CompressedEdwardsY;
+
+        let ec = Ed25519Riscv::new();
+        let one = {
+            let mut bytes = [0u8; 32];
+            bytes[0] = 1;
+            bytes
+        };
+
+        assert!(ec.validate_point(&ED25519_BASE_POINT).is_ok());
+        assert!(software::validate_point(&software::public_key(&[5u8; 32])).is_ok());
+
+        // 单位元可参与运算，但不是合法的公钥
+        assert!(ec.validate_point(&ED25519_IDENTITY).is_err());
+        assert!(ec.scalar_mul(&one, &ED25519_IDENTITY).is_ok());
+
+        // 全部 8 个小阶点
+        for torsion in EIGHT_TORSION.iter() {
+            let encoded = torsion.compress().to_bytes();
+            assert!(ec.validate_point(&encoded).is_err());
+        }
+
+        // 含挠分量的点：B + T
+        let mixed = (EIGHT_TORSION[1] + software::decode_point(&ED25519_BASE_POINT).unwrap())
+            .compress()
+            .to_bytes();
+        assert!(software::decode_point(&mixed).is_ok());
+        assert!(ec.validate_point(&mixed).is_err());
+        assert!(ec.scalar_mul(&one, &mixed).is_err());
+        assert!(ec.point_add(&mixed, &ED25519_BASE_POINT).is_err());
+
+        // 非规范编码：y = p + 1 ≡ 1
+        let mut non_canonical = [0xffu8; 32];
+        non_canonical[0] = 0xee;
+        non_canonical[31] = 0x7f;
+        assert!(CompressedEdwardsY(non_canonical).decompress().is_some());
+        assert!(software::decode_point(&non_canonical).is_err());
+
+        // 不在曲线上的 y
+        let off_curve = (2u8..=255)
+            .map(|y| {
+                let mut bytes = [0u8; 32];
+                bytes[0] = y;
+                bytes
+            })
+            .find(|bytes| CompressedEdwardsY(*bytes).decompress().is_none())
+            .unwrap();
+        assert!(ec.scalar_mul(&one, &off_curve).is_err());
+        assert!(ec.validate_point(&off_curve).is_err());
     }
 }
@@ -0,0 +1,218 @@
+//! 秘密数据类型
+//!
+//! 私钥、对称密钥和扩展轮密钥不应以裸 `[u8; 32]` / `Vec<u8>` 的形式到处传递。
+//! 本模块提供的封装类型：
+//!
+//! - 离开作用域时用 `zeroize` 清零内存，编译器不会把清零优化掉
+//! - `Debug` 输出只显示 `[REDACTED]`，避免通过日志或 panic 信息泄露
+//! - 相等比较使用 `subtle` 的常数时间比较，耗时不依赖首个不同字节的位置
+//! - 只能通过 `expose_secret` 显式取出原始字节，便于审计使用点
+//!
+//! `RiscVCryptoExt` 作为硬件边界仍接受原始字节数组，
+//! 上层类型（`Ed25519Riscv`、`Aes256Riscv`、`TransferTx` 等）只接受秘密类型。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::secret::Aes256Key;
+//!
+//! let key = Aes256Key::random().unwrap();
+//! assert_eq!(format!("{:?}", key), "SecretBytes<32>([REDACTED])");
+//! assert_eq!(key, key.clone());
+//! ```
+
+use rand::TryRng;
+use rand::rngs::SysRng;
+use std::error::Error;
+use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// 构造秘密类型时发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct SecretError {
+    message: String,
+}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret Error: {}", self.message)
+    }
+}
+
+impl Error for SecretError {}
+
+/// 定长秘密字节串，释放时清零
+pub struct SecretBytes<const N: usize>([u8; N]);
+
+/// Ed25519 私钥（RFC 8032 的 32 字节种子）
+pub type Ed25519SecretKey = SecretBytes<32>;
+
+/// AES-256 密钥
+pub type Aes256Key = SecretBytes<32>;
+
+/// X25519 私钥（RFC 7748 的 32 字节标量，使用时钳位）
+pub type X25519SecretKey = SecretBytes<32>;
+
+#[allow(dead_code)]
+impl<const N: usize> SecretBytes<N> {
+    /// 封装已有的秘密字节
+    ///
+    /// 参数按值移入，调用方栈上的原始副本不会被清零；
+    /// 需要避免残留时应使用 `random` 或 `from_slice` 后自行清零来源。
+    pub fn new(bytes: [u8; N]) -> Self {
+        SecretBytes(bytes)
+    }
+
+    /// 从切片复制秘密字节
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecretBytes)`: 封装后的秘密
+    /// - `Err(Box<dyn Error>)`: 切片长度不等于 `N`
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if bytes.len() != N {
+            return Err(Box::new(SecretError {
+                message: format!("Expected {} bytes, got {}", N, bytes.len()),
+            }));
+        }
+        let mut secret = SecretBytes([0u8; N]);
+        secret.0.copy_from_slice(bytes);
+        Ok(secret)
+    }
+
+    /// 从操作系统 CSPRNG 生成随机秘密
+    ///
+    /// 随机字节直接写入封装内部，不经过中间缓冲区。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecretBytes)`: 随机秘密
+    /// - `Err(Box<dyn Error>)`: 操作系统随机源不可用
+    pub fn random() -> Result<Self, Box<dyn Error>> {
+        let mut secret = SecretBytes([0u8; N]);
+        SysRng.try_fill_bytes(&mut secret.0)?;
+        Ok(secret)
+    }
+
+    /// 取出原始秘密字节
+    ///
+    /// 仅应在把秘密交给底层运算（如 `RiscVCryptoExt`）时调用，不要复制或记录返回值。
+    pub fn expose_secret(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for SecretBytes<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<const N: usize> Clone for SecretBytes<N> {
+    fn clone(&self) -> Self {
+        SecretBytes(self.0)
+    }
+}
+
+impl<const N: usize> Zeroize for SecretBytes<N> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<const N: usize> Drop for SecretBytes<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<const N: usize> fmt::Debug for SecretBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretBytes<{}>([REDACTED])", N)
+    }
+}
+
+impl<const N: usize> ConstantTimeEq for SecretBytes<N> {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<const N: usize> PartialEq for SecretBytes<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const N: usize> Eq for SecretBytes<N> {}
+
+/// 变长秘密字节串，释放时清零整个分配
+///
+/// 用于 AES-256 扩展轮密钥等长度在运行时确定的秘密。
+#[derive(Default)]
+pub struct SecretVec(Vec<u8>);
+
+#[allow(dead_code)]
+impl SecretVec {
+    /// 封装已有的秘密字节，接管其分配
+    pub fn new(bytes: Vec<u8>) -> Self {
+        SecretVec(bytes)
+    }
+
+    /// 取出原始秘密字节
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// 秘密长度（字节）
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Clone for SecretVec {
+    fn clone(&self) -> Self {
+        SecretVec(self.0.clone())
+    }
+}
+
+impl Zeroize for SecretVec {
+    fn zeroize(&mut self) {
+        // 清零全部容量并把长度置 0
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretVec {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretVec({} bytes, [REDACTED])", self.0.len())
+    }
+}
+
+impl ConstantTimeEq for SecretVec {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        // 长度不是秘密；长度不同时 subtle 直接返回 0
+        self.0.as_slice().ct_eq(other.0.as_slice())
+    }
+}
+
+impl PartialEq for SecretVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for SecretVec {}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod crypto_secret_tests {
+    use crate::crypto::secret::{Aes256Key, Ed25519SecretKey, SecretBytes, SecretVec};
+    use subtle::ConstantTimeEq;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn test_secret_debug_is_redacted() {
+        let key = Aes256Key::new([0xabu8; 32]);
+        let debug = format!("{:?}", key);
+        assert_eq!(debug, "SecretBytes<32>([REDACTED])");
+        assert!(!debug.contains("171"));
+
+        let round_keys = SecretVec::new(vec![0xcdu8; 240]);
+        assert_eq!(
+            format!("{:?}", round_keys),
+            "SecretVec(240 bytes, [REDACTED])"
+        );
+
+        // 包含秘密的结构体派生 Debug 时同样不会泄露
+        let job = crate::crypto::job_queue::CryptoJob::SignEd25519 {
+            secret: Ed25519SecretKey::new([0x5au8; 32]),
+            message: b"m".to_vec(),
+        };
+        assert!(format!("{:?}", job).contains("[REDACTED]"));
+        assert!(!format!("{:?}", job).contains("90"));
+    }
+
+    #[test]
+    fn test_secret_constant_time_equality() {
+        let a = SecretBytes::new([1u8; 32]);
+        let b = SecretBytes::from([1u8; 32]);
+        let mut c = [1u8; 32];
+        c[31] = 2;
+        let c = SecretBytes::new(c);
+
+        assert!(bool::from(a.ct_eq(&b)));
+        assert!(!bool::from(a.ct_eq(&c)));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.clone(), a);
+
+        assert_eq!(SecretVec::new(vec![1, 2, 3]), SecretVec::new(vec![1, 2, 3]));
+        assert_ne!(SecretVec::new(vec![1, 2, 3]), SecretVec::new(vec![1, 2, 4]));
+        assert_ne!(SecretVec::new(vec![1, 2, 3]), SecretVec::new(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_secret_zeroize_clears_memory() {
+        let mut key = Aes256Key::new([0xffu8; 32]);
+        key.zeroize();
+        assert_eq!(key.expose_secret(), &[0u8; 32]);
+
+        let mut round_keys = SecretVec::new(vec![0xffu8; 240]);
+        round_keys.zeroize();
+        assert!(round_keys.is_empty());
+    }
+
+    #[test]
+    fn test_secret_from_slice_and_random() {
+        assert!(Aes256Key::from_slice(&[0u8; 31]).is_err());
+        assert!(Aes256Key::from_slice(&[0u8; 33]).is_err());
+        assert_eq!(
+            Aes256Key::from_slice(&[7u8; 32]).unwrap(),
+            Aes256Key::new([7u8; 32])
+        );
+
+        let first = Aes256Key::random().unwrap();
+        let second = Aes256Key::random().unwrap();
+        assert_ne!(first, second);
+    }
+}
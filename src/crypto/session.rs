@@ -0,0 +1,717 @@
+//! 认证会话通道模块
+//!
+//! 按 Noise 协议框架（rev 34）实现握手：`Noise_XX_25519_AESGCM_SHA256`（双方交换并认证静态密钥）
+//! 和 `Noise_NN_25519_AESGCM_SHA256`（只有临时密钥，无身份认证）。握手结束后得到每个方向
+//! 各自独立的 AES-256-GCM 会话密钥，nonce 为 64 位计数器，不需要在报文中传输。
+//!
+//! - DH：`crypto::x25519`，全零共享秘密视为握手失败
+//! - 哈希与 HKDF：`Sha256Riscv` / `crypto::kdf::Hkdf`
+//! - 加密：`Aes256Riscv`，经所选加速器后端执行
+//!
+//! XX 握手共三条消息：
+//!
+//! ```text
+//! -> e
+//! <- e, ee, s, es
+//! -> s, se
+//! ```
+//!
+//! 握手只证明对端持有某个静态私钥；对端是谁由上层判断，例如把 `remote_static()`
+//! 与认证报告或预置白名单中的公钥比较。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::session::{Handshake, HandshakePattern, HandshakeRole};
+//! use crypto::x25519::X25519KeyPair;
+//!
+//! let mut client = Handshake::new(
+//!     HandshakeRole::Initiator,
+//!     HandshakePattern::Xx,
+//!     Some(X25519KeyPair::generate().unwrap()),
+//!     b"prologue",
+//! ).unwrap();
+//! let mut server = Handshake::new(
+//!     HandshakeRole::Responder,
+//!     HandshakePattern::Xx,
+//!     Some(X25519KeyPair::generate().unwrap()),
+//!     b"prologue",
+//! ).unwrap();
+//!
+//! server.read_message(&client.write_message(b"").unwrap()).unwrap();
+//! client.read_message(&server.write_message(b"").unwrap()).unwrap();
+//! server.read_message(&client.write_message(b"").unwrap()).unwrap();
+//!
+//! let mut client = client.into_session().unwrap();
+//! let mut server = server.into_session().unwrap();
+//! let message = client.encrypt(b"batch #42").unwrap();
+//! assert_eq!(server.decrypt(&message).unwrap(), b"batch #42");
+//! ```
+
+use crate::crypto::aes::{Aes256Riscv, AesGcmCiphertext, GCM_NONCE_SIZE, GCM_TAG_SIZE};
+use crate::crypto::kdf::Hkdf;
+use crate::crypto::riscv_ext::RiscVCryptoExt;
+use crate::crypto::secret::{Aes256Key, SecretBytes};
+use crate::crypto::sha256::Sha256Riscv;
+use crate::crypto::x25519::{X25519_KEY_SIZE, X25519KeyPair};
+use std::error::Error;
+use std::fmt;
+
+/// Noise 报文（握手消息和传输消息）的最大长度
+pub const NOISE_MAX_MESSAGE_LEN: usize = 65535;
+
+/// 哈希输出长度
+const HASH_LEN: usize = 32;
+
+/// 会话建立或传输过程中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct SessionError {
+    message: String,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Session Error: {}", self.message)
+    }
+}
+
+impl Error for SessionError {}
+
+fn session_error(message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(SessionError {
+        message: message.into(),
+    })
+}
+
+/// 握手模式
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakePattern {
+    /// 无静态密钥，只提供前向安全的加密，不认证任何一方
+    Nn,
+    /// 双方在握手中交换静态公钥并相互认证，静态公钥本身也被加密
+    Xx,
+}
+
+/// 握手消息中的令牌
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    E,
+    S,
+    Ee,
+    Es,
+    Se,
+}
+
+#[allow(dead_code)]
+impl HandshakePattern {
+    /// 完整的 Noise 协议名，握手开始时作为初始哈希
+    pub fn protocol_name(&self) -> &'static str {
+        match self {
+            HandshakePattern::Nn => "Noise_NN_25519_AESGCM_SHA256",
+            HandshakePattern::Xx => "Noise_XX_25519_AESGCM_SHA256",
+        }
+    }
+
+    /// 是否需要双方提供静态密钥
+    pub fn requires_static(&self) -> bool {
+        matches!(self, HandshakePattern::Xx)
+    }
+
+    fn messages(&self) -> &'static [&'static [Token]] {
+        match self {
+            HandshakePattern::Nn => &[&[Token::E], &[Token::E, Token::Ee]],
+            HandshakePattern::Xx => &[
+                &[Token::E],
+                &[Token::E, Token::Ee, Token::S, Token::Es],
+                &[Token::S, Token::Se],
+            ],
+        }
+    }
+}
+
+/// 从消息头部取出 `len` 字节
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8], Box<dyn Error>> {
+    if rest.len() < len {
+        return Err(session_error("Handshake message is truncated"));
+    }
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+    Ok(head)
+}
+
+/// 握手中的角色：发起方写第 1、3 条消息，响应方写第 2 条
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakeRole {
+    Initiator,
+    Responder,
+}
+
+/// 单方向的 AES-256-GCM 密钥与计数器 nonce
+struct CipherState {
+    key: Option<Aes256Key>,
+    nonce: u64,
+}
+
+impl CipherState {
+    fn new(key: Option<Aes256Key>) -> Self {
+        CipherState { key, nonce: 0 }
+    }
+
+    /// Noise 的 AESGCM nonce：4 个零字节后接大端序 64 位计数器
+    fn next_nonce(&self) -> Result<[u8; GCM_NONCE_SIZE], Box<dyn Error>> {
+        // 2^64 - 1 保留，计数器到达时必须重新握手
+        if self.nonce == u64::MAX {
+            return Err(session_error(
+                "Nonce exhausted, a new handshake is required",
+            ));
+        }
+        let mut nonce = [0u8; GCM_NONCE_SIZE];
+        nonce[4..].copy_from_slice(&self.nonce.to_be_bytes());
+        Ok(nonce)
+    }
+
+    /// 有密钥时加密为 `密文 || tag`，否则原样返回
+    fn encrypt_with_ad(
+        &mut self,
+        cipher: &Aes256Riscv,
+        ad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let Some(key) = &self.key else {
+            return Ok(plaintext.to_vec());
+        };
+        let sealed = cipher.encrypt_gcm_with_nonce(plaintext, key, &self.next_nonce()?, ad)?;
+        self.nonce += 1;
+
+        let mut output = sealed.ciphertext;
+        output.extend_from_slice(&sealed.tag);
+        Ok(output)
+    }
+
+    /// 有密钥时认证并解密 `密文 || tag`，否则原样返回；认证失败时计数器不变
+    fn decrypt_with_ad(
+        &mut self,
+        cipher: &Aes256Riscv,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let Some(key) = &self.key else {
+            return Ok(ciphertext.to_vec());
+        };
+        if ciphertext.len() < GCM_TAG_SIZE {
+            return Err(session_error(format!(
+                "Ciphertext of {} bytes is shorter than the tag",
+                ciphertext.len()
+            )));
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - GCM_TAG_SIZE);
+        let sealed = AesGcmCiphertext {
+            nonce: self.next_nonce()?,
+            ciphertext: body.to_vec(),
+            tag: tag.try_into()?,
+        };
+        let plaintext = cipher.decrypt_gcm(&sealed, key, ad)?;
+        self.nonce += 1;
+        Ok(plaintext)
+    }
+}
+
+/// 握手过程中的链式密钥 `ck`、握手哈希 `h` 与当前加密状态
+struct SymmetricState {
+    chaining_key: SecretBytes<HASH_LEN>,
+    hash: [u8; HASH_LEN],
+    cipher_state: CipherState,
+}
+
+impl SymmetricState {
+    fn new(protocol_name: &str) -> Self {
+        let name = protocol_name.as_bytes();
+        let hash = if name.len() <= HASH_LEN {
+            let mut padded = [0u8; HASH_LEN];
+            padded[..name.len()].copy_from_slice(name);
+            padded
+        } else {
+            Sha256Riscv::hash(name)
+        };
+
+        SymmetricState {
+            chaining_key: SecretBytes::new(hash),
+            hash,
+            cipher_state: CipherState::new(None),
+        }
+    }
+
+    /// `h = SHA-256(h || data)`
+    fn mix_hash(&mut self, data: &[u8]) {
+        let mut hasher = Sha256Riscv::new();
+        hasher.update(&self.hash);
+        hasher.update(data);
+        self.hash = hasher.finalize();
+    }
+
+    /// Noise 的 `HKDF(ck, ikm, 2)`，即盐为 `ck`、`info` 为空的 RFC 5869 HKDF 输出 64 字节
+    fn hkdf(&self, ikm: &[u8]) -> Result<(SecretBytes<HASH_LEN>, Aes256Key), Box<dyn Error>> {
+        let okm = Hkdf::new(self.chaining_key.expose_secret(), ikm).expand(&[], 2 * HASH_LEN)?;
+        let (first, second) = okm.expose_secret().split_at(HASH_LEN);
+        Ok((
+            SecretBytes::from_slice(first)?,
+            Aes256Key::from_slice(second)?,
+        ))
+    }
+
+    fn mix_key(&mut self, ikm: &[u8]) -> Result<(), Box<dyn Error>> {
+        let (chaining_key, key) = self.hkdf(ikm)?;
+        self.chaining_key = chaining_key;
+        self.cipher_state = CipherState::new(Some(key));
+        Ok(())
+    }
+
+    fn has_key(&self) -> bool {
+        self.cipher_state.key.is_some()
+    }
+
+    fn encrypt_and_hash(
+        &mut self,
+        cipher: &Aes256Riscv,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let ciphertext = self
+            .cipher_state
+            .encrypt_with_ad(cipher, &self.hash, plaintext)?;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_and_hash(
+        &mut self,
+        cipher: &Aes256Riscv,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let plaintext = self
+            .cipher_state
+            .decrypt_with_ad(cipher, &self.hash, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    /// 握手结束：派生发起方→响应方、响应方→发起方两个方向的密钥
+    fn split(&self) -> Result<(CipherState, CipherState), Box<dyn Error>> {
+        let (first, second) = self.hkdf(&[])?;
+        Ok((
+            CipherState::new(Some(Aes256Key::new(*first.expose_secret()))),
+            CipherState::new(Some(second)),
+        ))
+    }
+}
+
+/// 进行中的 Noise 握手
+///
+/// 双方按模式轮流调用 `write_message` / `read_message`，`is_finished()` 为真后
+/// 调用 `into_session` 得到传输通道。任一步返回错误后握手即作废，必须重新开始。
+#[allow(dead_code)]
+pub struct Handshake {
+    role: HandshakeRole,
+    pattern: HandshakePattern,
+    symmetric: SymmetricState,
+    local_static: Option<X25519KeyPair>,
+    local_ephemeral: Option<X25519KeyPair>,
+    remote_static: Option<[u8; X25519_KEY_SIZE]>,
+    remote_ephemeral: Option<[u8; X25519_KEY_SIZE]>,
+    message_index: usize,
+    failed: bool,
+    cipher: Aes256Riscv,
+}
+
+impl fmt::Debug for Handshake {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Handshake")
+            .field("role", &self.role)
+            .field("pattern", &self.pattern)
+            .field("message_index", &self.message_index)
+            .field("failed", &self.failed)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(dead_code)]
+impl Handshake {
+    /// 开始握手
+    ///
+    /// 初始化时通过 `crate::crypto::backend::default_backend()` 选择加速器后端。
+    ///
+    /// # 参数
+    ///
+    /// * `role` - 发起方或响应方
+    /// * `pattern` - 握手模式
+    /// * `local_static` - 本方静态密钥；XX 模式必须提供，NN 模式必须为 `None`
+    /// * `prologue` - 双方事先约定的上下文（如协议版本），不一致时握手失败
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Handshake)`: 初始状态
+    /// - `Err(Box<dyn Error>)`: 静态密钥与模式不匹配
+    pub fn new(
+        role: HandshakeRole,
+        pattern: HandshakePattern,
+        local_static: Option<X25519KeyPair>,
+        prologue: &[u8],
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::with_accelerator(
+            role,
+            pattern,
+            local_static,
+            prologue,
+            crate::crypto::backend::default_backend(),
+        )
+    }
+
+    /// 使用指定的加速器开始握手
+    ///
+    /// # 参数
+    ///
+    /// * `role` - 发起方或响应方
+    /// * `pattern` - 握手模式
+    /// * `local_static` - 本方静态密钥
+    /// * `prologue` - 双方事先约定的上下文
+    /// * `accelerator` - 执行握手和会话中 AES-GCM 运算的后端
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Handshake)`: 初始状态
+    /// - `Err(Box<dyn Error>)`: 静态密钥与模式不匹配
+    pub fn with_accelerator(
+        role: HandshakeRole,
+        pattern: HandshakePattern,
+        local_static: Option<X25519KeyPair>,
+        prologue: &[u8],
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if pattern.requires_static() != local_static.is_some() {
+            return Err(session_error(format!(
+                "{} {} a local static key",
+                pattern.protocol_name(),
+                if pattern.requires_static() {
+                    "requires"
+                } else {
+                    "does not use"
+                }
+            )));
+        }
+
+        let mut symmetric = SymmetricState::new(pattern.protocol_name());
+        symmetric.mix_hash(prologue);
+
+        Ok(Handshake {
+            role,
+            pattern,
+            symmetric,
+            local_static,
+            local_ephemeral: None,
+            remote_static: None,
+            remote_ephemeral: None,
+            message_index: 0,
+            failed: false,
+            cipher: Aes256Riscv::with_accelerator(accelerator),
+        })
+    }
+
+    /// 指定本方临时密钥，代替写 `e` 令牌时随机生成的密钥
+    ///
+    /// 只用于复现测试向量；重复使用临时密钥会破坏前向安全性。
+    pub(crate) fn with_ephemeral(mut self, ephemeral: X25519KeyPair) -> Self {
+        self.local_ephemeral = Some(ephemeral);
+        self
+    }
+
+    /// 握手消息是否已全部处理
+    pub fn is_finished(&self) -> bool {
+        self.message_index == self.pattern.messages().len()
+    }
+
+    /// 下一条消息是否由本方写出
+    pub fn is_write_turn(&self) -> bool {
+        let initiator_turn = self.message_index.is_multiple_of(2);
+        initiator_turn == (self.role == HandshakeRole::Initiator)
+    }
+
+    /// 对端静态公钥，XX 模式下在收到对端的 `s` 令牌后可用
+    pub fn remote_static(&self) -> Option<[u8; X25519_KEY_SIZE]> {
+        self.remote_static
+    }
+
+    /// 当前握手哈希
+    pub fn handshake_hash(&self) -> [u8; HASH_LEN] {
+        self.symmetric.hash
+    }
+
+    fn check_turn(&self, writing: bool) -> Result<(), Box<dyn Error>> {
+        if self.failed {
+            return Err(session_error("Handshake already failed"));
+        }
+        if self.is_finished() {
+            return Err(session_error("Handshake already finished"));
+        }
+        if self.is_write_turn() != writing {
+            return Err(session_error(format!(
+                "Message {} must be {} by the {}",
+                self.message_index + 1,
+                if writing { "read" } else { "written" },
+                if self.role == HandshakeRole::Initiator {
+                    "initiator"
+                } else {
+                    "responder"
+                }
+            )));
+        }
+        Ok(())
+    }
+
+    /// 执行 `ee` / `es` / `se` 令牌：`MixKey(DH(...))`
+    fn mix_dh(&mut self, token: Token) -> Result<(), Box<dyn Error>> {
+        let initiator = self.role == HandshakeRole::Initiator;
+        let (local, remote) = match token {
+            Token::Ee => (&self.local_ephemeral, &self.remote_ephemeral),
+            Token::Es if initiator => (&self.local_ephemeral, &self.remote_static),
+            Token::Es => (&self.local_static, &self.remote_ephemeral),
+            Token::Se if initiator => (&self.local_static, &self.remote_ephemeral),
+            Token::Se => (&self.local_ephemeral, &self.remote_static),
+            Token::E | Token::S => unreachable!("not a DH token"),
+        };
+        let (Some(local), Some(remote)) = (local, remote) else {
+            return Err(session_error(format!("Missing key for {:?} token", token)));
+        };
+
+        let shared = local.diffie_hellman(remote)?;
+        self.symmetric.mix_key(shared.expose_secret())
+    }
+
+    /// 写出下一条握手消息
+    ///
+    /// # 参数
+    ///
+    /// * `payload` - 随消息发送的应用数据；得到密钥之前（如 XX 第 1 条消息）以明文发送
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 发给对端的消息
+    /// - `Err(Box<dyn Error>)`: 不是本方写消息的轮次、消息超长或加速器错误
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.check_turn(true)?;
+        let result = self.write_tokens(payload);
+        self.finish_step(result)
+    }
+
+    fn write_tokens(&mut self, payload: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut message = Vec::new();
+        for &token in self.pattern.messages()[self.message_index] {
+            match token {
+                Token::E => {
+                    let ephemeral = match self.local_ephemeral.take() {
+                        Some(ephemeral) => ephemeral,
+                        None => X25519KeyPair::generate()?,
+                    };
+                    let public = ephemeral.public_key();
+                    message.extend_from_slice(&public);
+                    self.symmetric.mix_hash(&public);
+                    self.local_ephemeral = Some(ephemeral);
+                }
+                Token::S => {
+                    let public = self
+                        .local_static
+                        .as_ref()
+                        .ok_or_else(|| session_error("Missing local static key"))?
+                        .public_key();
+                    let ciphertext = self.symmetric.encrypt_and_hash(&self.cipher, &public)?;
+                    message.extend_from_slice(&ciphertext);
+                }
+                _ => self.mix_dh(token)?,
+            }
+        }
+        message.extend(self.symmetric.encrypt_and_hash(&self.cipher, payload)?);
+
+        if message.len() > NOISE_MAX_MESSAGE_LEN {
+            return Err(session_error(format!(
+                "Handshake message of {} bytes exceeds {}",
+                message.len(),
+                NOISE_MAX_MESSAGE_LEN
+            )));
+        }
+        Ok(message)
+    }
+
+    /// 读取对端的下一条握手消息
+    ///
+    /// # 参数
+    ///
+    /// * `message` - 对端 `write_message` 的输出
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 消息携带的应用数据
+    /// - `Err(Box<dyn Error>)`: 不是本方读消息的轮次、消息被截断或篡改、
+    ///   prologue 不一致、对端公钥是小阶点
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.check_turn(false)?;
+        let result = self.read_tokens(message);
+        self.finish_step(result)
+    }
+
+    fn read_tokens(&mut self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        if message.len() > NOISE_MAX_MESSAGE_LEN {
+            return Err(session_error(format!(
+                "Handshake message of {} bytes exceeds {}",
+                message.len(),
+                NOISE_MAX_MESSAGE_LEN
+            )));
+        }
+
+        let mut rest = message;
+        for &token in self.pattern.messages()[self.message_index] {
+            match token {
+                Token::E => {
+                    let public: [u8; X25519_KEY_SIZE] =
+                        take(&mut rest, X25519_KEY_SIZE)?.try_into()?;
+                    self.symmetric.mix_hash(&public);
+                    self.remote_ephemeral = Some(public);
+                }
+                Token::S => {
+                    let len = if self.symmetric.has_key() {
+                        X25519_KEY_SIZE + GCM_TAG_SIZE
+                    } else {
+                        X25519_KEY_SIZE
+                    };
+                    let public = self
+                        .symmetric
+                        .decrypt_and_hash(&self.cipher, take(&mut rest, len)?)?;
+                    self.remote_static = Some(public.as_slice().try_into()?);
+                }
+                _ => self.mix_dh(token)?,
+            }
+        }
+        self.symmetric.decrypt_and_hash(&self.cipher, rest)
+    }
+
+    fn finish_step<T>(&mut self, result: Result<T, Box<dyn Error>>) -> Result<T, Box<dyn Error>> {
+        match result {
+            Ok(value) => {
+                self.message_index += 1;
+                Ok(value)
+            }
+            Err(error) => {
+                self.failed = true;
+                Err(error)
+            }
+        }
+    }
+
+    /// 结束握手，派生双向会话密钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecureSession)`: 传输通道
+    /// - `Err(Box<dyn Error>)`: 握手尚未完成或已失败
+    pub fn into_session(self) -> Result<SecureSession, Box<dyn Error>> {
+        if self.failed || !self.is_finished() {
+            return Err(session_error(format!(
+                "Handshake is not complete ({} of {} messages)",
+                self.message_index,
+                self.pattern.messages().len()
+            )));
+        }
+
+        let (initiator_to_responder, responder_to_initiator) = self.symmetric.split()?;
+        let (send, receive) = match self.role {
+            HandshakeRole::Initiator => (initiator_to_responder, responder_to_initiator),
+            HandshakeRole::Responder => (responder_to_initiator, initiator_to_responder),
+        };
+
+        Ok(SecureSession {
+            send,
+            receive,
+            handshake_hash: self.symmetric.hash,
+            remote_static: self.remote_static,
+            cipher: self.cipher,
+        })
+    }
+}
+
+/// 握手完成后的双向加密通道
+///
+/// 每条消息编码为 `密文 || tag`，nonce 由双方各自的计数器隐式确定，
+/// 因此消息必须按发送顺序、不丢失地交给 `decrypt`（如经 TCP 或 Unix socket）。
+#[allow(dead_code)]
+pub struct SecureSession {
+    send: CipherState,
+    receive: CipherState,
+    handshake_hash: [u8; HASH_LEN],
+    remote_static: Option<[u8; X25519_KEY_SIZE]>,
+    cipher: Aes256Riscv,
+}
+
+impl fmt::Debug for SecureSession {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SecureSession")
+            .field("sent", &self.send.nonce)
+            .field("received", &self.receive.nonce)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(dead_code)]
+impl SecureSession {
+    /// 加密一条发往对端的消息
+    ///
+    /// # 参数
+    ///
+    /// * `plaintext` - 明文，不超过 65519 字节
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: `密文 || tag`
+    /// - `Err(Box<dyn Error>)`: 消息超长、计数器耗尽或加速器错误
+    pub fn encrypt(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        if plaintext.len() + GCM_TAG_SIZE > NOISE_MAX_MESSAGE_LEN {
+            return Err(session_error(format!(
+                "Plaintext of {} bytes exceeds {}",
+                plaintext.len(),
+                NOISE_MAX_MESSAGE_LEN - GCM_TAG_SIZE
+            )));
+        }
+        self.send.encrypt_with_ad(&self.cipher, &[], plaintext)
+    }
+
+    /// 认证并解密对端发来的下一条消息
+    ///
+    /// # 参数
+    ///
+    /// * `ciphertext` - 对端 `encrypt` 的输出
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<u8>)`: 明文
+    /// - `Err(Box<dyn Error>)`: 消息被篡改、重放或乱序；失败不消耗计数器，可继续接收正确的消息
+    pub fn decrypt(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        if ciphertext.len() > NOISE_MAX_MESSAGE_LEN {
+            return Err(session_error(format!(
+                "Ciphertext of {} bytes exceeds {}",
+                ciphertext.len(),
+                NOISE_MAX_MESSAGE_LEN
+            )));
+        }
+        self.receive.decrypt_with_ad(&self.cipher, &[], ciphertext)
+    }
+
+    /// 握手哈希，双方相同，可用于通道绑定（如签入认证报告）
+    pub fn handshake_hash(&self) -> [u8; HASH_LEN] {
+        self.handshake_hash
+    }
+
+    /// 对端静态公钥；NN 模式下为 `None`
+    pub fn remote_static(&self) -> Option<[u8; X25519_KEY_SIZE]> {
+        self.remote_static
+    }
+}
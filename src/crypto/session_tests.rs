@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod crypto_session_tests {
+    use crate::crypto::riscv_ext::SoftwareBackend;
+    use crate::crypto::secret::X25519SecretKey;
+    use crate::crypto::session::{
+        Handshake, HandshakePattern, HandshakeRole, NOISE_MAX_MESSAGE_LEN, SecureSession,
+    };
+    use crate::crypto::test_util::hex;
+    use crate::crypto::x25519::X25519KeyPair;
+
+    fn fixed_key(start: u8) -> X25519KeyPair {
+        X25519KeyPair::from_secret(X25519SecretKey::new(std::array::from_fn(|i| {
+            start + i as u8
+        })))
+    }
+
+    /// 依次交换全部握手消息，返回每条消息的线上字节
+    fn run_handshake(
+        initiator: &mut Handshake,
+        responder: &mut Handshake,
+        payloads: &[&[u8]],
+    ) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+        for (index, payload) in payloads.iter().enumerate() {
+            let (writer, reader) = if index.is_multiple_of(2) {
+                (&mut *initiator, &mut *responder)
+            } else {
+                (&mut *responder, &mut *initiator)
+            };
+            assert!(writer.is_write_turn() && !reader.is_write_turn());
+            let message = writer.write_message(payload).unwrap();
+            assert_eq!(reader.read_message(&message).unwrap(), *payload);
+            messages.push(message);
+        }
+        assert!(initiator.is_finished() && responder.is_finished());
+        messages
+    }
+
+    fn fixed_pair(pattern: HandshakePattern) -> (Handshake, Handshake) {
+        let static_key = |start| pattern.requires_static().then(|| fixed_key(start));
+        let initiator = Handshake::new(
+            HandshakeRole::Initiator,
+            pattern,
+            static_key(0),
+            b"zk-rollup test",
+        )
+        .unwrap()
+        .with_ephemeral(fixed_key(32));
+        let responder = Handshake::new(
+            HandshakeRole::Responder,
+            pattern,
+            static_key(64),
+            b"zk-rollup test",
+        )
+        .unwrap()
+        .with_ephemeral(fixed_key(96));
+        (initiator, responder)
+    }
+
+    fn random_pair(pattern: HandshakePattern) -> (Handshake, Handshake) {
+        let static_key = || {
+            pattern
+                .requires_static()
+                .then(|| X25519KeyPair::generate().unwrap())
+        };
+        (
+            Handshake::new(HandshakeRole::Initiator, pattern, static_key(), b"").unwrap(),
+            Handshake::with_accelerator(
+                HandshakeRole::Responder,
+                pattern,
+                static_key(),
+                b"",
+                Box::new(SoftwareBackend),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn established(pattern: HandshakePattern) -> (SecureSession, SecureSession) {
+        let (mut initiator, mut responder) = random_pair(pattern);
+        let payloads: &[&[u8]] = match pattern {
+            HandshakePattern::Nn => &[b"", b""],
+            HandshakePattern::Xx => &[b"", b"", b""],
+        };
+        run_handshake(&mut initiator, &mut responder, payloads);
+        (
+            initiator.into_session().unwrap(),
+            responder.into_session().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_xx_reference_transcript() {
+        // 期望值由独立的 Noise 参考实现（Python cryptography 的 X25519 / AESGCM / HMAC）按相同密钥生成
+        let (mut initiator, mut responder) = fixed_pair(HandshakePattern::Xx);
+        let messages = run_handshake(
+            &mut initiator,
+            &mut responder,
+            &[b"hello", b"world", b"done"],
+        );
+        assert_eq!(
+            messages[0],
+            hex("358072d6365880d1aeea329adf9121383851ed21a28e3b75e965d0d2cd16625468656c6c6f")
+        );
+        assert_eq!(
+            messages[1],
+            hex(concat!(
+                "675dd574ed7789310b3d2e7681f3790b466c773b1521fecf36577958371ea52f",
+                "612b92700b6794bc8668390588f9b69aaa554b7ccdbe3ec346313aca7e5165aa",
+                "04ae938f75c05e98aae7b285868a7ec3276b633599112992312f87db58b2dadd",
+                "883d5f71eb"
+            ))
+        );
+        assert_eq!(
+            messages[2],
+            hex(concat!(
+                "c84510cdb41da99f2d84ead62432d2e3c5d5999ac4f4831b8ed64a162324fa72",
+                "b836a8fbc64ae7cfa13775a57d614867907aea64fa522e98cc853e1dd7e7b725",
+                "87bf0b6e"
+            ))
+        );
+        assert_eq!(initiator.remote_static(), Some(fixed_key(64).public_key()));
+        assert_eq!(responder.remote_static(), Some(fixed_key(0).public_key()));
+
+        let mut initiator = initiator.into_session().unwrap();
+        let mut responder = responder.into_session().unwrap();
+        let expected_hash = hex("70bf7ee66f1ef9af4ae01ce1c92baa36ac92c19550e9f378d085b3605f0bddbd");
+        assert_eq!(initiator.handshake_hash().to_vec(), expected_hash);
+        assert_eq!(responder.handshake_hash().to_vec(), expected_hash);
+
+        let ping = initiator.encrypt(b"ping").unwrap();
+        assert_eq!(ping, hex("1b249c34a2271852356538ba59b306e8d91405ec"));
+        assert_eq!(responder.decrypt(&ping).unwrap(), b"ping");
+        let pong = responder.encrypt(b"pong").unwrap();
+        assert_eq!(pong, hex("1dfb49e6b2a3784a551739fc37f7fe8968f9f983"));
+        assert_eq!(initiator.decrypt(&pong).unwrap(), b"pong");
+    }
+
+    #[test]
+    fn test_nn_reference_transcript() {
+        let (mut initiator, mut responder) = fixed_pair(HandshakePattern::Nn);
+        let messages = run_handshake(&mut initiator, &mut responder, &[b"hello", b"world"]);
+        assert_eq!(
+            messages[1],
+            hex(concat!(
+                "675dd574ed7789310b3d2e7681f3790b466c773b1521fecf36577958371ea52f",
+                "499dbcacdeb98d23b7a592dea2d1ac0b8f2aeccd56"
+            ))
+        );
+        assert_eq!(initiator.remote_static(), None);
+
+        let mut initiator = initiator.into_session().unwrap();
+        let mut responder = responder.into_session().unwrap();
+        assert_eq!(
+            initiator.handshake_hash().to_vec(),
+            hex("8e9d6b8abdccdd89bda3d683cec0b045a5e8fdb8be2869525e16b8d092b9045d")
+        );
+        let ping = initiator.encrypt(b"ping").unwrap();
+        assert_eq!(ping, hex("c35aa7d147e7301d23a23610251ee2cf53ac5b2e"));
+        assert_eq!(responder.decrypt(&ping).unwrap(), b"ping");
+    }
+
+    #[test]
+    fn test_session_traffic_both_directions() {
+        for pattern in [HandshakePattern::Nn, HandshakePattern::Xx] {
+            let (mut initiator, mut responder) = established(pattern);
+            assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+
+            for round in 0..10u8 {
+                let request = vec![round; round as usize * 100];
+                let sealed = initiator.encrypt(&request).unwrap();
+                assert_eq!(sealed.len(), request.len() + 16);
+                assert_eq!(responder.decrypt(&sealed).unwrap(), request);
+
+                let reply = responder.encrypt(&[round]).unwrap();
+                assert_eq!(initiator.decrypt(&reply).unwrap(), [round]);
+            }
+
+            // 两个方向使用不同密钥：同一明文、同一计数器得到不同密文
+            let (mut a, mut b) = established(pattern);
+            assert_ne!(a.encrypt(b"same").unwrap(), b.encrypt(b"same").unwrap());
+        }
+    }
+
+    #[test]
+    fn test_session_rejects_tampering_and_replay() {
+        let (mut initiator, mut responder) = established(HandshakePattern::Xx);
+
+        let first = initiator.encrypt(b"first").unwrap();
+        let second = initiator.encrypt(b"second").unwrap();
+
+        // 乱序、篡改和截断都失败，且不消耗接收计数器
+        assert!(responder.decrypt(&second).is_err());
+        let mut tampered = first.clone();
+        tampered[0] ^= 1;
+        assert!(responder.decrypt(&tampered).is_err());
+        assert!(responder.decrypt(&first[..15]).is_err());
+
+        assert_eq!(responder.decrypt(&first).unwrap(), b"first");
+        assert!(responder.decrypt(&first).is_err());
+        assert_eq!(responder.decrypt(&second).unwrap(), b"second");
+
+        // 反射攻击：自己发出的消息不能被自己接收
+        let own = responder.encrypt(b"echo").unwrap();
+        assert!(responder.decrypt(&own).is_err());
+
+        let limit = NOISE_MAX_MESSAGE_LEN - 16;
+        assert!(initiator.encrypt(&vec![0u8; limit]).is_ok());
+        assert!(initiator.encrypt(&vec![0u8; limit + 1]).is_err());
+    }
+
+    #[test]
+    fn test_handshake_failures() {
+        // 第 2 条消息中对端静态公钥被篡改
+        let (mut initiator, mut responder) = random_pair(HandshakePattern::Xx);
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        let mut message = responder.write_message(b"").unwrap();
+        message[40] ^= 0x80;
+        assert!(initiator.read_message(&message).is_err());
+        // 失败后握手作废，即使收到正确消息也不能继续
+        message[40] ^= 0x80;
+        assert!(initiator.read_message(&message).is_err());
+        assert!(initiator.into_session().is_err());
+
+        // prologue 不一致在第一条带密钥的消息处发现
+        let mut initiator =
+            Handshake::new(HandshakeRole::Initiator, HandshakePattern::Nn, None, b"v1").unwrap();
+        let mut responder =
+            Handshake::new(HandshakeRole::Responder, HandshakePattern::Nn, None, b"v2").unwrap();
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        assert!(
+            initiator
+                .read_message(&responder.write_message(b"").unwrap())
+                .is_err()
+        );
+
+        // 小阶临时公钥
+        let (_, mut responder) = random_pair(HandshakePattern::Nn);
+        responder.read_message(&[0u8; 32]).unwrap();
+        assert!(responder.write_message(b"").is_err());
+
+        // 截断的消息
+        let (mut initiator, mut responder) = random_pair(HandshakePattern::Xx);
+        let message = initiator.write_message(b"").unwrap();
+        assert!(responder.read_message(&message[..31]).is_err());
+    }
+
+    #[test]
+    fn test_handshake_state_checks() {
+        assert!(Handshake::new(HandshakeRole::Initiator, HandshakePattern::Xx, None, b"").is_err());
+        assert!(
+            Handshake::new(
+                HandshakeRole::Initiator,
+                HandshakePattern::Nn,
+                Some(X25519KeyPair::generate().unwrap()),
+                b"",
+            )
+            .is_err()
+        );
+
+        let (mut initiator, mut responder) = random_pair(HandshakePattern::Nn);
+        assert!(initiator.read_message(&[0u8; 32]).is_err());
+        assert!(responder.write_message(b"").is_err());
+
+        let (mut initiator, mut responder) = random_pair(HandshakePattern::Nn);
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        assert!(responder.handshake_hash() == initiator.handshake_hash());
+        assert!(!responder.is_finished());
+        let message = responder.write_message(b"").unwrap();
+        // 响应方已完成但发起方尚未读取
+        assert!(responder.is_finished());
+        assert!(initiator.into_session().is_err());
+        assert!(responder.write_message(b"").is_err());
+        assert_eq!(
+            HandshakePattern::Xx.protocol_name(),
+            "Noise_XX_25519_AESGCM_SHA256"
+        );
+        assert!(!message.is_empty());
+    }
+}
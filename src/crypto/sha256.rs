@@ -1,7 +1,7 @@
-/// SHA-256 哈希模块，支持 RISC-V 硬件加速
-///
-/// 该模块实现 NIST FIPS 180-4 标准的 SHA-256 哈希算法。
-/// SHA-256 生成固定 256 位（32 字节）哈希值，广泛用于数据完整性验证。
+//! SHA-256 哈希模块，支持 RISC-V 硬件加速
+//!
+//! 该模块实现 NIST FIPS 180-4 标准的 SHA-256 哈希算法。
+//! SHA-256 生成固定 256 位（32 字节）哈希值，广泛用于数据完整性验证。
 
 #[allow(dead_code)]
 use crate::crypto::riscv_ext::RiscVCryptoExt;
@@ -9,7 +9,28 @@ use crate::crypto::riscv_ext::RiscVCryptoExt;
 /// SHA-256 处理的块大小（字节）
 const SHA256_CHUNK_SIZE: usize = 64;
 
+/// SHA-256 初始哈希值 H0-H7 的大端序编码
+pub(crate) const SHA256_INITIAL_STATE: [u8; 32] = [
+    0x6a, 0x09, 0xe6, 0x67, 0xbb, 0x67, 0xae, 0x85, 0x3c, 0x6e, 0xf3, 0x72, 0xa5, 0x4f, 0xf5, 0x3a,
+    0x51, 0x0e, 0x52, 0x7f, 0x9b, 0x05, 0x68, 0x8c, 0x1f, 0x83, 0xd9, 0xab, 0x5b, 0xe0, 0xcd, 0x19,
+];
+
+/// SHA-256 轮常数（前 64 个素数立方根小数部分的前 32 位）
+pub(crate) const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
 /// SHA-256 哈希计算器，支持 RISC-V 硬件加速
+///
+/// 每个 64 字节消息块优先交给加速器的 `copr_sha256_compress` 处理；
+/// 加速器不可用或返回错误时，回退到本地软件压缩函数 `process_chunk`。
 #[allow(dead_code)]
 pub struct Sha256Riscv {
     accelerator: Box<dyn RiscVCryptoExt>,
@@ -33,14 +54,25 @@ impl Sha256Riscv {
     /// # 返回
     ///
     /// 新的 `Sha256Riscv` 实例，可用于增量计算哈希
+    ///
+    /// 加速器由 `crate::crypto::backend::default_backend()` 按环境变量选择。
     pub fn new() -> Self {
+        Self::with_accelerator(crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 SHA-256 哈希计算器
+    ///
+    /// # 参数
+    ///
+    /// * `accelerator` - 用于块压缩的 RISC-V 加速器
+    ///
+    /// # 返回
+    ///
+    /// 新的 `Sha256Riscv` 实例，初始状态与 `new()` 相同
+    pub fn with_accelerator(accelerator: Box<dyn RiscVCryptoExt>) -> Self {
         Sha256Riscv {
-            accelerator: Box::new(crate::crypto::riscv_ext::HardwareAccelerator::new()),
-            state: [
-                0x6a, 0x09, 0xe6, 0x67, 0xbb, 0x67, 0xae, 0x85, 0x3c, 0x6e, 0xf3, 0x72, 0xa5, 0x4f,
-                0xf5, 0x3b, 0x51, 0x0e, 0x52, 0x7f, 0x9b, 0x05, 0x68, 0x8c, 0x1f, 0x83, 0xd9, 0xab,
-                0x5b, 0xe0, 0xcd, 0x19,
-            ],
+            accelerator,
+            state: SHA256_INITIAL_STATE,
             buffer: Vec::new(),
             total_len: 0,
         }
@@ -67,11 +99,28 @@ impl Sha256Riscv {
         while self.buffer.len() >= SHA256_CHUNK_SIZE {
             let chunk = self.buffer[..SHA256_CHUNK_SIZE].to_vec();
             self.buffer.drain(..SHA256_CHUNK_SIZE);
-            self.process_chunk(&chunk);
+            self.compress(&chunk);
         }
     }
 
-    /// 处理单个 64 字节消息块
+    /// 压缩单个 64 字节消息块
+    ///
+    /// 优先调用加速器；加速器返回错误时使用软件实现，两条路径结果一致。
+    ///
+    /// # 参数
+    ///
+    /// * `chunk` - 64 字节消息块
+    fn compress(&mut self, chunk: &[u8]) {
+        let mut block = [0u8; SHA256_CHUNK_SIZE];
+        block.copy_from_slice(chunk);
+
+        match self.accelerator.copr_sha256_compress(&self.state, &block) {
+            Ok(state) => self.state = state,
+            Err(_) => self.process_chunk(chunk),
+        }
+    }
+
+    /// 处理单个 64 字节消息块（软件实现）
     ///
     /// 执行 SHA-256 压缩函数，将消息块与当前状态混合。
     /// 包括消息调度（扩展）和 64 轮压缩操作。
@@ -80,6 +129,22 @@ impl Sha256Riscv {
     ///
     /// * `chunk` - 64 字节消息块
     fn process_chunk(&mut self, chunk: &[u8]) {
+        self.state = Self::compress_software(&self.state, chunk);
+    }
+
+    /// SHA-256 软件压缩函数
+    ///
+    /// 不依赖实例状态的纯函数形式，供软件后端直接复用。
+    ///
+    /// # 参数
+    ///
+    /// * `state` - 当前 8 个状态字的大端序编码
+    /// * `chunk` - 64 字节消息块
+    ///
+    /// # 返回
+    ///
+    /// 压缩后的新状态
+    pub(crate) fn compress_software(state: &[u8; 32], chunk: &[u8]) -> [u8; 32] {
         let mut w = [0u32; 64];
 
         for i in 0..16 {
@@ -94,59 +159,18 @@ impl Sha256Riscv {
             let s1 = Self::rotr(w[i - 2], 17) ^ Self::rotr(w[i - 2], 19) ^ (w[i - 2] >> 10);
             w[i] = w[i - 16]
                 .wrapping_add(s0)
-                .wrapping_add(w[i - 9])
+                .wrapping_add(w[i - 7])
                 .wrapping_add(s1);
         }
 
-        let mut a =
-            u32::from_be_bytes([self.state[0], self.state[1], self.state[2], self.state[3]]);
-        let mut b =
-            u32::from_be_bytes([self.state[4], self.state[5], self.state[6], self.state[7]]);
-        let mut c =
-            u32::from_be_bytes([self.state[8], self.state[9], self.state[10], self.state[11]]);
-        let mut d = u32::from_be_bytes([
-            self.state[12],
-            self.state[13],
-            self.state[14],
-            self.state[15],
-        ]);
-        let mut e = u32::from_be_bytes([
-            self.state[16],
-            self.state[17],
-            self.state[18],
-            self.state[19],
-        ]);
-        let mut f = u32::from_be_bytes([
-            self.state[20],
-            self.state[21],
-            self.state[22],
-            self.state[23],
-        ]);
-        let mut g = u32::from_be_bytes([
-            self.state[24],
-            self.state[25],
-            self.state[26],
-            self.state[27],
-        ]);
-        let mut h = u32::from_be_bytes([
-            self.state[28],
-            self.state[29],
-            self.state[30],
-            self.state[31],
-        ]);
-
-        let k = [
-            0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
-            0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
-            0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
-            0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
-            0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
-            0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
-            0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
-            0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
-            0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
-            0xc67178f2,
-        ];
+        let mut a = u32::from_be_bytes([state[0], state[1], state[2], state[3]]);
+        let mut b = u32::from_be_bytes([state[4], state[5], state[6], state[7]]);
+        let mut c = u32::from_be_bytes([state[8], state[9], state[10], state[11]]);
+        let mut d = u32::from_be_bytes([state[12], state[13], state[14], state[15]]);
+        let mut e = u32::from_be_bytes([state[16], state[17], state[18], state[19]]);
+        let mut f = u32::from_be_bytes([state[20], state[21], state[22], state[23]]);
+        let mut g = u32::from_be_bytes([state[24], state[25], state[26], state[27]]);
+        let mut h = u32::from_be_bytes([state[28], state[29], state[30], state[31]]);
 
         for i in 0..64 {
             let s1 = Self::rotr(e, 6) ^ Self::rotr(e, 11) ^ Self::rotr(e, 25);
@@ -154,7 +178,7 @@ impl Sha256Riscv {
             let temp1 = h
                 .wrapping_add(s1)
                 .wrapping_add(ch)
-                .wrapping_add(k[i])
+                .wrapping_add(SHA256_K[i])
                 .wrapping_add(w[i]);
 
             let s0 = Self::rotr(a, 2) ^ Self::rotr(a, 13) ^ Self::rotr(a, 22);
@@ -172,7 +196,7 @@ impl Sha256Riscv {
             a = temp1.wrapping_add(temp2);
         }
 
-        let state_bytes = self.state;
+        let state_bytes = *state;
         let mut new_state = [0u8; 32];
         for i in 0..8 {
             let val = match i {
@@ -230,7 +254,7 @@ impl Sha256Riscv {
             new_state[i * 4..i * 4 + 4].copy_from_slice(&bytes);
         }
 
-        self.state = new_state;
+        new_state
     }
 
     /// 右旋转操作
@@ -247,7 +271,7 @@ impl Sha256Riscv {
     ///
     /// 旋转后的 32 位值
     fn rotr(n: u32, r: usize) -> u32 {
-        (n >> r) | (n << (32 - r))
+        n.rotate_right(r as u32)
     }
 
     /// 完成哈希计算并返回结果
@@ -280,7 +304,7 @@ impl Sha256Riscv {
         while !self.buffer.is_empty() {
             let chunk = self.buffer[..SHA256_CHUNK_SIZE].to_vec();
             self.buffer.drain(..SHA256_CHUNK_SIZE);
-            self.process_chunk(&chunk);
+            self.compress(&chunk);
         }
 
         self.state
@@ -0,0 +1,35 @@
+//! 测试共用的辅助函数
+
+use crate::crypto::conformance::decode_hex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 解码测试向量中的十六进制字符串，格式错误时 panic
+pub(crate) fn hex(text: &str) -> Vec<u8> {
+    decode_hex(text).unwrap()
+}
+
+/// 解码定长的十六进制测试向量，长度不符时 panic
+pub(crate) fn hex_array<const N: usize>(text: &str) -> [u8; N] {
+    hex(text).try_into().unwrap()
+}
+
+/// 进程内唯一的临时文件路径，离开作用域时删除该文件（如测试用的 Unix socket）
+pub(crate) struct TempPath(PathBuf);
+
+impl TempPath {
+    /// 在系统临时目录下生成 `zk-rollup-<pid>-<name>`，不创建文件
+    pub(crate) fn new(name: &str) -> Self {
+        TempPath(std::env::temp_dir().join(format!("zk-rollup-{}-{}", std::process::id(), name)))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,248 @@
+//! 常数时间统计检验模块
+//!
+//! dudect 风格的计时泄露检测（Reparaz、Balasch、Verbauwhede，"Dude, is my code constant time?"）：
+//! 把输入分为"固定"和"随机"两类，随机交错地测量被测操作的耗时，
+//! 再用 Welch t 检验比较两类的耗时分布。|t| 超过阈值说明耗时与输入数据相关。
+//!
+//! 只依赖 `std::time::Instant`，不需要周期计数器或专用硬件，可在开发机上直接运行。
+//! 为压低测量噪声：
+//!
+//! - 每个样本连续执行 `batch` 次操作，摊薄计时器的分辨率
+//! - 先丢弃 `warmup` 个样本，避开缓存和频率爬升
+//! - 除全部样本外，还在多个百分位处截掉长尾（中断、调度造成的离群值）分别检验，取最大的 |t|
+//!
+//! 统计检验只能发现泄露，不能证明没有泄露；通过检验的实现仍应在代码层面避免
+//! 依赖秘密数据的分支和下标。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::timing::{TimingConfig, dudect};
+//! use subtle::ConstantTimeEq;
+//!
+//! let secret = [0x5a; 32];
+//! let report = dudect(
+//!     "tag comparison",
+//!     &TimingConfig::default(),
+//!     secret,
+//!     rand::random::<[u8; 32]>,
+//!     |candidate| bool::from(candidate.ct_eq(&secret)),
+//! );
+//! println!("{}", report);
+//! ```
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// dudect 判定泄露的 |t| 阈值
+pub const DEFAULT_T_THRESHOLD: f64 = 4.5;
+
+/// 截尾检验使用的百分位
+const CROP_PERCENTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+/// 计时检验的参数
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingConfig {
+    /// 计入统计的样本数（两类合计）
+    pub samples: usize,
+    /// 每个样本内连续执行操作的次数
+    pub batch: usize,
+    /// 开始统计前丢弃的样本数
+    pub warmup: usize,
+    /// 判定泄露的 |t| 阈值
+    pub threshold: f64,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        TimingConfig {
+            samples: 20_000,
+            batch: 8,
+            warmup: 1_000,
+            threshold: DEFAULT_T_THRESHOLD,
+        }
+    }
+}
+
+/// 一次计时检验的结果
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingReport {
+    /// 被测操作的名称
+    pub name: String,
+    /// 固定类与随机类的样本数
+    pub class_sizes: [usize; 2],
+    /// 固定类与随机类的平均耗时（纳秒/样本）
+    pub means: [f64; 2],
+    /// 全部样本及各截尾集合中最大的 |t|
+    pub max_t: f64,
+    /// 判定泄露的阈值
+    pub threshold: f64,
+}
+
+#[allow(dead_code)]
+impl TimingReport {
+    /// |t| 是否超过阈值，即耗时是否与输入类别相关
+    pub fn leaky(&self) -> bool {
+        self.max_t > self.threshold
+    }
+}
+
+impl fmt::Display for TimingReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: max |t| = {:.2} ({}), fixed {:.1} ns over {} samples, random {:.1} ns over {} samples",
+            self.name,
+            self.max_t,
+            if self.leaky() {
+                "LEAKY"
+            } else {
+                "no leak detected"
+            },
+            self.means[0],
+            self.class_sizes[0],
+            self.means[1],
+            self.class_sizes[1]
+        )
+    }
+}
+
+/// 在线计算均值与方差（Welford 算法）
+#[derive(Clone, Copy, Debug, Default)]
+struct Moments {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+}
+
+/// Welch t 统计量
+///
+/// # 参数
+///
+/// * `a`, `b` - 两组样本
+///
+/// # 返回
+///
+/// `(mean(a) - mean(b)) / sqrt(var(a)/n_a + var(b)/n_b)`；
+/// 任一组少于 2 个样本或两组方差都为 0 时返回 0
+#[allow(dead_code)]
+pub fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let moments = |values: &[f64]| {
+        let mut m = Moments::default();
+        values.iter().for_each(|&v| m.push(v));
+        m
+    };
+    t_statistic(&moments(a), &moments(b))
+}
+
+fn t_statistic(a: &Moments, b: &Moments) -> f64 {
+    if a.count < 2 || b.count < 2 {
+        return 0.0;
+    }
+    let error = (a.variance() / a.count as f64 + b.variance() / b.count as f64).sqrt();
+    if error == 0.0 {
+        0.0
+    } else {
+        (a.mean - b.mean) / error
+    }
+}
+
+/// 对一个操作做 dudect 计时检验
+///
+/// 固定类每次使用 `fixed`，随机类每次调用 `random` 生成新输入；所有输入在计时开始前
+/// 生成好，类别按随机位交错，避免两类受到不同的系统状态影响。
+///
+/// # 参数
+///
+/// * `name` - 报告中使用的名称
+/// * `config` - 样本数、批量和阈值
+/// * `fixed` - 固定类输入，通常选能触发特殊路径的值（如与秘密相等的标签）
+/// * `random` - 随机类输入生成器
+/// * `operation` - 被测操作，返回值经 `black_box` 防止被优化掉
+///
+/// # 返回
+///
+/// 检验报告，`leaky()` 为真表示检测到数据相关的耗时
+#[allow(dead_code)]
+pub fn dudect<T, R, F, O>(
+    name: &str,
+    config: &TimingConfig,
+    fixed: T,
+    mut random: R,
+    mut operation: F,
+) -> TimingReport
+where
+    T: Clone,
+    R: FnMut() -> T,
+    F: FnMut(&T) -> O,
+{
+    let total = config.warmup + config.samples;
+    let inputs: Vec<(usize, T)> = (0..total)
+        .map(|_| {
+            if rand::random::<bool>() {
+                (0, fixed.clone())
+            } else {
+                (1, random())
+            }
+        })
+        .collect();
+
+    let mut timings = Vec::with_capacity(config.samples);
+    for (index, (class, input)) in inputs.iter().enumerate() {
+        let start = Instant::now();
+        for _ in 0..config.batch {
+            black_box(operation(black_box(input)));
+        }
+        let elapsed = start.elapsed().as_nanos() as f64;
+        if index >= config.warmup {
+            timings.push((*class, elapsed));
+        }
+    }
+
+    let mut sorted: Vec<f64> = timings.iter().map(|&(_, time)| time).collect();
+    sorted.sort_by(f64::total_cmp);
+    let cutoffs = CROP_PERCENTILES
+        .iter()
+        .filter_map(|p| sorted.get((sorted.len().saturating_sub(1) as f64 * p) as usize))
+        .copied()
+        .chain(std::iter::once(f64::INFINITY));
+
+    let mut max_t = 0.0f64;
+    let mut overall = [Moments::default(); 2];
+    for cutoff in cutoffs {
+        let mut classes = [Moments::default(); 2];
+        for &(class, time) in timings.iter().filter(|&&(_, time)| time <= cutoff) {
+            classes[class].push(time);
+        }
+        max_t = max_t.max(t_statistic(&classes[0], &classes[1]).abs());
+        overall = classes;
+    }
+
+    TimingReport {
+        name: name.to_string(),
+        class_sizes: [overall[0].count, overall[1].count],
+        means: [overall[0].mean, overall[1].mean],
+        max_t,
+        threshold: config.threshold,
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod crypto_timing_tests {
+    use crate::crypto::aes::Aes256Riscv;
+    use crate::crypto::aes_modes::pkcs7_padding_len;
+    use crate::crypto::ec::software;
+    use crate::crypto::kdf::HmacSha256;
+    use crate::crypto::timing::{TimingConfig, dudect, welch_t};
+    use std::thread;
+    use std::time::Duration;
+    use subtle::ConstantTimeEq;
+
+    // 对具体实现的计时测量受机器负载影响，默认忽略，见 README 的运行方式；
+    // 默认测试只保留 Welch t 的计算和明显泄露的对照
+
+    /// 测试用的缩小配置：调试构建下 AES 等操作较慢
+    fn config(samples: usize, batch: usize) -> TimingConfig {
+        TimingConfig {
+            samples,
+            batch,
+            warmup: samples / 10,
+            ..TimingConfig::default()
+        }
+    }
+
+    /// 遇到第一个不同字节就返回的比较，作为已知有泄露的对照
+    fn early_exit_eq(a: &[u8], b: &[u8]) -> bool {
+        for (x, y) in a.iter().zip(b) {
+            if x != y {
+                return false;
+            }
+        }
+        a.len() == b.len()
+    }
+
+    #[test]
+    fn test_welch_t_statistic() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+        let b = [3.0, 4.0, 5.0, 6.0];
+        assert!((welch_t(&a, &b) + 2.190890).abs() < 1e-5);
+        assert!((welch_t(&b, &a) - 2.190890).abs() < 1e-5);
+        assert_eq!(welch_t(&a, &a), 0.0);
+        assert_eq!(welch_t(&[1.0, 1.0], &[1.0, 1.0]), 0.0);
+        assert_eq!(welch_t(&[1.0], &b), 0.0);
+    }
+
+    #[test]
+    fn test_flags_deliberately_leaky_closure() {
+        // 固定类每次多等待 50 微秒，远大于调度噪声，不依赖机器负载
+        let secret = [0x5au8; 16];
+        let report = dudect(
+            "sleep on match",
+            &config(400, 1),
+            secret,
+            rand::random::<[u8; 16]>,
+            |candidate| {
+                if *candidate == secret {
+                    thread::sleep(Duration::from_micros(50));
+                }
+            },
+        );
+        assert!(report.leaky(), "{}", report);
+        assert!(report.means[0] > report.means[1], "{}", report);
+    }
+
+    #[test]
+    #[ignore = "timing measurement; run with cargo test --release timing -- --ignored"]
+    fn test_flags_early_exit_comparison() {
+        let secret = [0x5au8; 512];
+        let report = dudect(
+            "early-exit comparison",
+            &config(4_000, 4),
+            secret,
+            rand::random::<[u8; 512]>,
+            |candidate| early_exit_eq(candidate, &secret),
+        );
+        assert!(report.leaky(), "{}", report);
+    }
+
+    /// 原先的 PKCS#7 检查：遇到第一个不符的填充字节就返回
+    fn early_exit_padding_len(block: &[u8; 16]) -> Option<usize> {
+        let pad = block[15] as usize;
+        if pad == 0 || pad > 16 || block[16 - pad..].iter().any(|&b| b as usize != pad) {
+            None
+        } else {
+            Some(pad)
+        }
+    }
+
+    #[test]
+    #[ignore = "timing measurement; run with cargo test --release timing -- --ignored"]
+    fn test_cbc_padding_check_is_constant_time() {
+        // 固定类是填充有效的分组，随机类的填充在随机位置出错
+        let mut valid = [8u8; 16];
+        valid[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let invalid = || {
+            let mut block: [u8; 16] = rand::random();
+            block[15] = 8;
+            block
+        };
+
+        let before = dudect(
+            "early-exit PKCS#7 check",
+            &config(20_000, 8),
+            valid,
+            invalid,
+            early_exit_padding_len,
+        );
+        assert!(before.leaky(), "{}", before);
+
+        let after = dudect(
+            "pkcs7_padding_len",
+            &config(20_000, 8),
+            valid,
+            invalid,
+            |block| Option::<usize>::from(pkcs7_padding_len(block)),
+        );
+        assert!(!after.leaky(), "{}", after);
+    }
+
+    #[test]
+    #[ignore = "timing measurement; run with cargo test --release timing -- --ignored"]
+    fn test_tag_comparison_is_constant_time() {
+        let tag: [u8; 16] = rand::random();
+        let report = dudect(
+            "GCM tag ct_eq",
+            &config(20_000, 8),
+            tag,
+            rand::random::<[u8; 16]>,
+            |candidate| bool::from(candidate.ct_eq(&tag)),
+        );
+        assert!(!report.leaky(), "{}", report);
+
+        let key = [0x42u8; 32];
+        let mac = HmacSha256::mac(&key, b"sealed record");
+        let report = dudect(
+            "HmacSha256::verify",
+            &config(4_000, 1),
+            mac,
+            rand::random::<[u8; 32]>,
+            |candidate| HmacSha256::verify(&key, b"sealed record", candidate),
+        );
+        assert!(!report.leaky(), "{}", report);
+    }
+
+    #[test]
+    #[ignore = "timing measurement; run with cargo test --release timing -- --ignored"]
+    fn test_aes_block_is_constant_time() {
+        // 固定密钥下比较固定明文与随机明文，以及固定密钥与随机密钥的密钥扩展
+        let round_keys = Aes256Riscv::expand_key(&rand::random());
+        let report = dudect(
+            "AES-256 encrypt block",
+            &config(3_000, 1),
+            [0u8; 16],
+            rand::random::<[u8; 16]>,
+            |block| Aes256Riscv::cipher(block, round_keys.expose_secret()),
+        );
+        assert!(!report.leaky(), "{}", report);
+
+        let report = dudect(
+            "AES-256 decrypt block",
+            &config(3_000, 1),
+            [0u8; 16],
+            rand::random::<[u8; 16]>,
+            |block| Aes256Riscv::inv_cipher(block, round_keys.expose_secret()),
+        );
+        assert!(!report.leaky(), "{}", report);
+
+        let report = dudect(
+            "AES-256 key expansion",
+            &config(3_000, 1),
+            [0u8; 32],
+            rand::random::<[u8; 32]>,
+            Aes256Riscv::expand_key,
+        );
+        assert!(!report.leaky(), "{}", report);
+    }
+
+    #[test]
+    #[ignore = "timing measurement; run with cargo test --release timing -- --ignored"]
+    fn test_ed25519_is_constant_time() {
+        let report = dudect(
+            "Ed25519 sign",
+            &config(2_000, 1),
+            [0u8; 32],
+            rand::random::<[u8; 32]>,
+            |secret| software::sign(secret, b"transfer"),
+        );
+        assert!(!report.leaky(), "{}", report);
+    }
+}
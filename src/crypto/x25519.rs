@@ -0,0 +1,171 @@
+//! X25519 Diffie-Hellman 密钥交换模块（RFC 7748）
+//!
+//! 在 Curve25519 的 Montgomery 形式上做 x 坐标标量乘法，由 `curve25519-dalek`
+//! 的常数时间 Montgomery 阶梯实现。私钥按 RFC 7748 第 5 节钳位（清低 3 位、
+//! 清最高位、置第 254 位），因此任意 32 字节都是合法私钥。
+//!
+//! 共享秘密全零说明对端公钥是小阶点，`diffie_hellman` 会拒绝这种结果，
+//! 防止攻击者把会话密钥强制为已知值。
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crypto::x25519::X25519KeyPair;
+//!
+//! let alice = X25519KeyPair::generate().unwrap();
+//! let bob = X25519KeyPair::generate().unwrap();
+//! let k1 = alice.diffie_hellman(&bob.public_key()).unwrap();
+//! let k2 = bob.diffie_hellman(&alice.public_key()).unwrap();
+//! assert_eq!(k1, k2);
+//! ```
+
+use crate::crypto::secret::{SecretBytes, X25519SecretKey};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use std::error::Error;
+use std::fmt;
+use subtle::ConstantTimeEq;
+
+/// X25519 私钥、公钥和共享秘密的字节长度
+pub const X25519_KEY_SIZE: usize = 32;
+
+/// RFC 7748 的基点 u = 9
+#[allow(dead_code)]
+pub const X25519_BASEPOINT: [u8; X25519_KEY_SIZE] = {
+    let mut u = [0u8; X25519_KEY_SIZE];
+    u[0] = 9;
+    u
+};
+
+/// X25519 密钥交换中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct X25519Error {
+    message: String,
+}
+
+impl fmt::Display for X25519Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "X25519 Error: {}", self.message)
+    }
+}
+
+impl Error for X25519Error {}
+
+/// RFC 7748 的 X25519 函数
+///
+/// 不检查结果是否为全零，供测试向量和需要原始函数的场景使用；
+/// 密钥交换应使用 `diffie_hellman`。
+///
+/// # 参数
+///
+/// * `scalar` - 32 字节私钥，计算前钳位
+/// * `u` - 对端公钥（u 坐标，小端序，最高位被忽略）
+///
+/// # 返回
+///
+/// 32 字节 u 坐标
+#[allow(dead_code)]
+pub fn x25519(scalar: &X25519SecretKey, u: &[u8; X25519_KEY_SIZE]) -> [u8; X25519_KEY_SIZE] {
+    MontgomeryPoint(*u)
+        .mul_clamped(*scalar.expose_secret())
+        .to_bytes()
+}
+
+/// 由私钥计算公钥
+///
+/// # 参数
+///
+/// * `secret` - 32 字节私钥
+///
+/// # 返回
+///
+/// `X25519(secret, 9)`
+#[allow(dead_code)]
+pub fn public_key(secret: &X25519SecretKey) -> [u8; X25519_KEY_SIZE] {
+    MontgomeryPoint::mul_base_clamped(*secret.expose_secret()).to_bytes()
+}
+
+/// X25519 密钥协商
+///
+/// # 参数
+///
+/// * `secret` - 本方私钥
+/// * `peer_public` - 对端公钥
+///
+/// # 返回
+///
+/// - `Ok(SecretBytes<32>)`: 共享秘密，应再经 KDF 派生会话密钥，不要直接用作密钥
+/// - `Err(Box<dyn Error>)`: 共享秘密为全零（对端公钥是小阶点）
+#[allow(dead_code)]
+pub fn diffie_hellman(
+    secret: &X25519SecretKey,
+    peer_public: &[u8; X25519_KEY_SIZE],
+) -> Result<SecretBytes<X25519_KEY_SIZE>, Box<dyn Error>> {
+    let shared = SecretBytes::new(x25519(secret, peer_public));
+    if bool::from(shared.expose_secret().ct_eq(&[0u8; X25519_KEY_SIZE])) {
+        return Err(Box::new(X25519Error {
+            message: "Shared secret is all zero (small-order public key)".to_string(),
+        }));
+    }
+    Ok(shared)
+}
+
+/// X25519 密钥对
+///
+/// 私钥释放时清零；公钥在构造时计算一次并缓存。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct X25519KeyPair {
+    secret: X25519SecretKey,
+    public: [u8; X25519_KEY_SIZE],
+}
+
+#[allow(dead_code)]
+impl X25519KeyPair {
+    /// 从操作系统 CSPRNG 生成密钥对
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(X25519KeyPair)`: 新密钥对
+    /// - `Err(Box<dyn Error>)`: 随机源不可用
+    pub fn generate() -> Result<Self, Box<dyn Error>> {
+        Ok(Self::from_secret(X25519SecretKey::random()?))
+    }
+
+    /// 由已有私钥构造密钥对
+    ///
+    /// # 参数
+    ///
+    /// * `secret` - 32 字节私钥，可由 `ExtendedKey::derive_key` 等派生
+    pub fn from_secret(secret: X25519SecretKey) -> Self {
+        let public = public_key(&secret);
+        X25519KeyPair { secret, public }
+    }
+
+    /// 获取公钥
+    pub fn public_key(&self) -> [u8; X25519_KEY_SIZE] {
+        self.public
+    }
+
+    /// 获取私钥
+    pub fn secret_key(&self) -> &X25519SecretKey {
+        &self.secret
+    }
+
+    /// 与对端公钥协商共享秘密
+    ///
+    /// # 参数
+    ///
+    /// * `peer_public` - 对端公钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(SecretBytes<32>)`: 共享秘密
+    /// - `Err(Box<dyn Error>)`: 对端公钥是小阶点
+    pub fn diffie_hellman(
+        &self,
+        peer_public: &[u8; X25519_KEY_SIZE],
+    ) -> Result<SecretBytes<X25519_KEY_SIZE>, Box<dyn Error>> {
+        diffie_hellman(&self.secret, peer_public)
+    }
+}
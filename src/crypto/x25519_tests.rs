@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod crypto_x25519_tests {
+    use crate::crypto::secret::X25519SecretKey;
+    use crate::crypto::test_util::hex_array;
+    use crate::crypto::x25519::{
+        X25519_BASEPOINT, X25519KeyPair, diffie_hellman, public_key, x25519,
+    };
+
+    #[test]
+    fn test_rfc7748_function_vectors() {
+        let vectors = [
+            (
+                "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+                "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+            ),
+            (
+                "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+                "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+                "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+            ),
+        ];
+        for (scalar, u, expected) in vectors {
+            let scalar = X25519SecretKey::new(hex_array(scalar));
+            assert_eq!(x25519(&scalar, &hex_array(u)), hex_array(expected));
+        }
+    }
+
+    #[test]
+    fn test_rfc7748_iterated() {
+        // k = X25519(k, u), u = 旧 k，起点 k = u = 9
+        let mut k = X25519_BASEPOINT;
+        let mut u = X25519_BASEPOINT;
+        for iteration in 1..=1000 {
+            let next = x25519(&X25519SecretKey::new(k), &u);
+            u = k;
+            k = next;
+            if iteration == 1 {
+                assert_eq!(
+                    k,
+                    hex_array("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+                );
+            }
+        }
+        assert_eq!(
+            k,
+            hex_array("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    #[test]
+    fn test_rfc7748_diffie_hellman() {
+        let alice = X25519KeyPair::from_secret(X25519SecretKey::new(hex_array(
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+        )));
+        let bob = X25519KeyPair::from_secret(X25519SecretKey::new(hex_array(
+            "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+        )));
+        assert_eq!(
+            alice.public_key(),
+            hex_array("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            bob.public_key(),
+            hex_array("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+        assert_eq!(
+            public_key(alice.secret_key()),
+            x25519(alice.secret_key(), &X25519_BASEPOINT)
+        );
+
+        let shared = hex_array("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        let k1 = alice.diffie_hellman(&bob.public_key()).unwrap();
+        let k2 = bob.diffie_hellman(&alice.public_key()).unwrap();
+        assert_eq!(k1.expose_secret(), &shared);
+        assert_eq!(k1, k2);
+        assert_eq!(format!("{:?}", k1), "SecretBytes<32>([REDACTED])");
+    }
+
+    #[test]
+    fn test_small_order_points_rejected() {
+        let key = X25519KeyPair::generate().unwrap();
+        let small_order = [
+            [0u8; 32],
+            hex_array("0100000000000000000000000000000000000000000000000000000000000000"),
+            hex_array("e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800"),
+        ];
+        for point in small_order {
+            // 钳位后的标量是 8 的倍数，小阶点的结果恒为零
+            assert_eq!(x25519(key.secret_key(), &point), [0u8; 32]);
+            assert!(diffie_hellman(key.secret_key(), &point).is_err());
+            assert!(key.diffie_hellman(&point).is_err());
+        }
+
+        let other = X25519KeyPair::generate().unwrap();
+        assert_ne!(key.public_key(), other.public_key());
+        assert!(key.diffie_hellman(&other.public_key()).is_ok());
+    }
+}
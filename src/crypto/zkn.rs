@@ -0,0 +1,723 @@
+//! RISC-V 标量加密扩展（Zkn）指令模拟
+//!
+//! 按 RISC-V Scalar Cryptography 规范 v1.0.1 对 RV64 指令逐位建模，
+//! 每个函数对应一条指令，参数和返回值即源寄存器和目的寄存器的 64 位内容：
+//!
+//! - Zkne: `aes64es`、`aes64esm`、`aes64ks1i`、`aes64ks2`
+//! - Zknd: `aes64ds`、`aes64dsm`、`aes64im`
+//! - Zknh: `sha256sig0`、`sha256sig1`、`sha256sum0`、`sha256sum1`
+//! - Zbkb / Zbkc（Zkn 的组成部分）: `brev8`、`clmul`、`clmulh`，用于 GCM 的 GHASH
+//!
+//! AES-256、AES-256-GCM 和 SHA-256 只用这些指令和基础整数指令（加法、移位、逻辑运算）
+//! 组合而成，并通过 `ZknInstructions` trait 对指令来源参数化：`ZknEmulator` 使用软件模型，
+//! 启用 `riscv-zkn` 特性后 `crate::crypto::zkn_hw` 在 riscv64 上使用真实指令，
+//! 两者执行相同的指令序列，因此可以在没有硬件的环境中验证硬件路径。
+//! 指令来源由 `ZknBackend` 包装成 `RiscVCryptoExt` 后端。
+
+use crate::crypto::aes::Aes256Riscv;
+use crate::crypto::riscv_ext::{Aes256KeySchedule, RiscVCryptoExt, SoftwareBackend};
+use crate::crypto::sha256::{SHA256_K, Sha256Riscv};
+use std::error::Error;
+use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Zkn 模拟后端操作中发生的错误
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct ZknError {
+    message: String,
+}
+
+impl fmt::Display for ZknError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Zkn Emulator Error: {}", self.message)
+    }
+}
+
+impl Error for ZknError {}
+
+/// 取 64 位寄存器的第 `i` 个字节
+fn byte(x: u64, i: usize) -> u8 {
+    (x >> (8 * i)) as u8
+}
+
+/// 由 8 个字节（低字节在前）拼成 64 位寄存器值
+fn pack(bytes: [u8; 8]) -> u64 {
+    u64::from_le_bytes(bytes)
+}
+
+/// RV64 将 32 位结果符号扩展到 64 位
+fn sext32(x: u32) -> u64 {
+    x as i32 as i64 as u64
+}
+
+/// 正向 ShiftRows 后状态的低 64 位
+///
+/// 状态为 `rs2:rs1`（rs1 为第 0、1 列），交换参数即可得到高 64 位。
+fn shiftrows_fwd(rs2: u64, rs1: u64) -> u64 {
+    pack([
+        byte(rs1, 0),
+        byte(rs1, 5),
+        byte(rs2, 2),
+        byte(rs2, 7),
+        byte(rs1, 4),
+        byte(rs2, 1),
+        byte(rs2, 6),
+        byte(rs1, 3),
+    ])
+}
+
+/// 逆向 ShiftRows 后状态的低 64 位
+fn shiftrows_inv(rs2: u64, rs1: u64) -> u64 {
+    pack([
+        byte(rs1, 0),
+        byte(rs2, 5),
+        byte(rs2, 2),
+        byte(rs1, 7),
+        byte(rs1, 4),
+        byte(rs1, 1),
+        byte(rs2, 6),
+        byte(rs2, 3),
+    ])
+}
+
+/// 对每个字节应用正向 S-Box
+fn sbox_fwd(x: u64) -> u64 {
+    let mut bytes = x.to_le_bytes();
+    for b in bytes.iter_mut() {
+        *b = Aes256Riscv::substitute_byte(*b);
+    }
+    pack(bytes)
+}
+
+/// 对每个字节应用逆向 S-Box
+fn sbox_inv(x: u64) -> u64 {
+    let mut bytes = x.to_le_bytes();
+    for b in bytes.iter_mut() {
+        *b = Aes256Riscv::inv_substitute_byte(*b);
+    }
+    pack(bytes)
+}
+
+/// 单列 MixColumns，字节 0 为第 0 行
+fn mixcolumn_fwd(col: u32) -> u32 {
+    let [a0, a1, a2, a3] = col.to_le_bytes();
+    let m = Aes256Riscv::gf_mul;
+    u32::from_le_bytes([
+        m(a0, 2) ^ m(a1, 3) ^ a2 ^ a3,
+        a0 ^ m(a1, 2) ^ m(a2, 3) ^ a3,
+        a0 ^ a1 ^ m(a2, 2) ^ m(a3, 3),
+        m(a0, 3) ^ a1 ^ a2 ^ m(a3, 2),
+    ])
+}
+
+/// 单列 InvMixColumns
+fn mixcolumn_inv(col: u32) -> u32 {
+    let [a0, a1, a2, a3] = col.to_le_bytes();
+    let m = Aes256Riscv::gf_mul;
+    u32::from_le_bytes([
+        m(a0, 14) ^ m(a1, 11) ^ m(a2, 13) ^ m(a3, 9),
+        m(a0, 9) ^ m(a1, 14) ^ m(a2, 11) ^ m(a3, 13),
+        m(a0, 13) ^ m(a1, 9) ^ m(a2, 14) ^ m(a3, 11),
+        m(a0, 11) ^ m(a1, 13) ^ m(a2, 9) ^ m(a3, 14),
+    ])
+}
+
+/// 对两个 32 位列分别应用单列变换
+fn map_columns(x: u64, f: fn(u32) -> u32) -> u64 {
+    (f(x as u32) as u64) | ((f((x >> 32) as u32) as u64) << 32)
+}
+
+/// `aes64es rd, rs1, rs2`：最后一轮加密（ShiftRows + SubBytes）
+pub fn aes64es(rs1: u64, rs2: u64) -> u64 {
+    sbox_fwd(shiftrows_fwd(rs2, rs1))
+}
+
+/// `aes64esm rd, rs1, rs2`：中间轮加密（ShiftRows + SubBytes + MixColumns）
+pub fn aes64esm(rs1: u64, rs2: u64) -> u64 {
+    map_columns(sbox_fwd(shiftrows_fwd(rs2, rs1)), mixcolumn_fwd)
+}
+
+/// `aes64ds rd, rs1, rs2`：最后一轮解密（InvShiftRows + InvSubBytes）
+pub fn aes64ds(rs1: u64, rs2: u64) -> u64 {
+    sbox_inv(shiftrows_inv(rs2, rs1))
+}
+
+/// `aes64dsm rd, rs1, rs2`：中间轮解密（InvShiftRows + InvSubBytes + InvMixColumns）
+pub fn aes64dsm(rs1: u64, rs2: u64) -> u64 {
+    map_columns(sbox_inv(shiftrows_inv(rs2, rs1)), mixcolumn_inv)
+}
+
+/// `aes64im rd, rs1`：对轮密钥做 InvMixColumns，用于等价逆密码
+pub fn aes64im(rs1: u64) -> u64 {
+    map_columns(rs1, mixcolumn_inv)
+}
+
+/// `aes64ks1i rd, rs1, rnum`：密钥扩展第一步
+///
+/// 取 `rs1` 高 32 位，`rnum` 为 0–9 时先循环右移 8 位（RotWord）再 SubWord 并异或轮常数；
+/// `rnum == 0xA` 时只做 SubWord，用于 AES-256 的奇数轮。结果复制到高低两半。
+///
+/// # Panics
+///
+/// `rnum > 0xA` 为保留编码，按非法指令处理。
+pub fn aes64ks1i(rs1: u64, rnum: u8) -> u64 {
+    const RCON: [u32; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+    assert!(rnum <= 0xA, "aes64ks1i: reserved rnum {:#x}", rnum);
+
+    let word = (rs1 >> 32) as u32;
+    let (word, rcon) = if rnum == 0xA {
+        (word, 0)
+    } else {
+        (word.rotate_right(8), RCON[rnum as usize])
+    };
+    let mut bytes = word.to_le_bytes();
+    for b in bytes.iter_mut() {
+        *b = Aes256Riscv::substitute_byte(*b);
+    }
+    let result = u32::from_le_bytes(bytes) ^ rcon;
+    (result as u64) | ((result as u64) << 32)
+}
+
+/// `aes64ks2 rd, rs1, rs2`：密钥扩展第二步
+///
+/// `w0 = rs1[63:32] ^ rs2[31:0]`，`w1 = w0 ^ rs2[63:32]`，结果为 `w1:w0`。
+pub fn aes64ks2(rs1: u64, rs2: u64) -> u64 {
+    let w0 = ((rs1 >> 32) as u32) ^ (rs2 as u32);
+    let w1 = w0 ^ ((rs2 >> 32) as u32);
+    (w0 as u64) | ((w1 as u64) << 32)
+}
+
+/// `sha256sig0 rd, rs1`：σ0(x) = ROTR7 ^ ROTR18 ^ SHR3
+pub fn sha256sig0(rs1: u64) -> u64 {
+    let x = rs1 as u32;
+    sext32(x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3))
+}
+
+/// `sha256sig1 rd, rs1`：σ1(x) = ROTR17 ^ ROTR19 ^ SHR10
+pub fn sha256sig1(rs1: u64) -> u64 {
+    let x = rs1 as u32;
+    sext32(x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10))
+}
+
+/// `sha256sum0 rd, rs1`：Σ0(x) = ROTR2 ^ ROTR13 ^ ROTR22
+pub fn sha256sum0(rs1: u64) -> u64 {
+    let x = rs1 as u32;
+    sext32(x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22))
+}
+
+/// `sha256sum1 rd, rs1`：Σ1(x) = ROTR6 ^ ROTR11 ^ ROTR25
+pub fn sha256sum1(rs1: u64) -> u64 {
+    let x = rs1 as u32;
+    sext32(x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25))
+}
+
+/// `brev8 rd, rs1`：翻转每个字节内的位序
+pub fn brev8(rs1: u64) -> u64 {
+    let mut bytes = rs1.to_le_bytes();
+    for b in bytes.iter_mut() {
+        *b = b.reverse_bits();
+    }
+    pack(bytes)
+}
+
+/// `clmul rd, rs1, rs2`：无进位乘法的低 64 位
+pub fn clmul(rs1: u64, rs2: u64) -> u64 {
+    let mut result = 0u64;
+    for i in 0..64 {
+        result ^= (rs1 << i) & 0u64.wrapping_sub((rs2 >> i) & 1);
+    }
+    result
+}
+
+/// `clmulh rd, rs1, rs2`：无进位乘法的高 64 位
+pub fn clmulh(rs1: u64, rs2: u64) -> u64 {
+    let mut result = 0u64;
+    for i in 1..64 {
+        result ^= (rs1 >> (64 - i)) & 0u64.wrapping_sub((rs2 >> i) & 1);
+    }
+    result
+}
+
+/// Zkn 指令集接口
+///
+/// AES、GCM 和 SHA-256 的组合算法只通过该接口调用指令，
+/// 因此软件模型（`ZknEmulator`）和真实硬件（`riscv-zkn` 特性下的 `ZknHardware`）
+/// 执行完全相同的指令序列。用 `ZknBackend` 包装后即可作为 `RiscVCryptoExt` 后端使用。
+pub trait ZknInstructions: Copy + Send + Sync + 'static {
+    fn aes64es(self, rs1: u64, rs2: u64) -> u64;
+    fn aes64esm(self, rs1: u64, rs2: u64) -> u64;
+    fn aes64ds(self, rs1: u64, rs2: u64) -> u64;
+    fn aes64dsm(self, rs1: u64, rs2: u64) -> u64;
+    fn aes64im(self, rs1: u64) -> u64;
+    fn aes64ks1i(self, rs1: u64, rnum: u8) -> u64;
+    fn aes64ks2(self, rs1: u64, rs2: u64) -> u64;
+    fn sha256sig0(self, rs1: u64) -> u64;
+    fn sha256sig1(self, rs1: u64) -> u64;
+    fn sha256sum0(self, rs1: u64) -> u64;
+    fn sha256sum1(self, rs1: u64) -> u64;
+    fn brev8(self, rs1: u64) -> u64;
+    fn clmul(self, rs1: u64, rs2: u64) -> u64;
+    fn clmulh(self, rs1: u64, rs2: u64) -> u64;
+}
+
+/// AES-256 轮密钥：15 轮 × 2 个 64 位寄存器
+type RoundKeys = [u64; 30];
+
+/// 用 `aes64ks1i` / `aes64ks2` 展开 AES-256 密钥
+fn expand_key<I: ZknInstructions>(isa: I, key: &[u8; 32]) -> RoundKeys {
+    let mut rk = [0u64; 30];
+    for (i, chunk) in key.chunks_exact(8).enumerate() {
+        rk[i] = pack(chunk.try_into().unwrap());
+    }
+
+    for rnum in 0..7u8 {
+        let base = 4 + 4 * rnum as usize;
+        let t = isa.aes64ks1i(rk[base - 1], rnum);
+        rk[base] = isa.aes64ks2(t, rk[base - 4]);
+        rk[base + 1] = isa.aes64ks2(rk[base], rk[base - 3]);
+        if rnum == 6 {
+            break;
+        }
+        let t = isa.aes64ks1i(rk[base + 1], 0xA);
+        rk[base + 2] = isa.aes64ks2(t, rk[base - 2]);
+        rk[base + 3] = isa.aes64ks2(rk[base + 2], rk[base - 1]);
+    }
+    rk
+}
+
+/// 分组加载为两个 64 位寄存器
+fn load_block(block: &[u8; 16]) -> (u64, u64) {
+    (
+        pack(block[..8].try_into().unwrap()),
+        pack(block[8..].try_into().unwrap()),
+    )
+}
+
+/// 两个 64 位寄存器存回分组
+fn store_block(lo: u64, hi: u64) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    block[..8].copy_from_slice(&lo.to_le_bytes());
+    block[8..].copy_from_slice(&hi.to_le_bytes());
+    block
+}
+
+/// AES-256 单分组加密
+fn encrypt_block<I: ZknInstructions>(isa: I, rk: &RoundKeys, block: &[u8; 16]) -> [u8; 16] {
+    let (lo, hi) = load_block(block);
+    let (mut s0, mut s1) = (lo ^ rk[0], hi ^ rk[1]);
+    for round in 1..14 {
+        let n0 = isa.aes64esm(s0, s1);
+        let n1 = isa.aes64esm(s1, s0);
+        s0 = n0 ^ rk[2 * round];
+        s1 = n1 ^ rk[2 * round + 1];
+    }
+    let n0 = isa.aes64es(s0, s1);
+    let n1 = isa.aes64es(s1, s0);
+    store_block(n0 ^ rk[28], n1 ^ rk[29])
+}
+
+/// AES-256 单分组解密（等价逆密码，中间轮密钥经 `aes64im` 变换）
+fn decrypt_block<I: ZknInstructions>(isa: I, rk: &RoundKeys, block: &[u8; 16]) -> [u8; 16] {
+    let (lo, hi) = load_block(block);
+    let (mut s0, mut s1) = (lo ^ rk[28], hi ^ rk[29]);
+    for round in (1..14).rev() {
+        let n0 = isa.aes64dsm(s0, s1);
+        let n1 = isa.aes64dsm(s1, s0);
+        s0 = n0 ^ isa.aes64im(rk[2 * round]);
+        s1 = n1 ^ isa.aes64im(rk[2 * round + 1]);
+    }
+    let n0 = isa.aes64ds(s0, s1);
+    let n1 = isa.aes64ds(s1, s0);
+    store_block(n0 ^ rk[0], n1 ^ rk[1])
+}
+
+/// `ZknBackend` 的密钥调度：持有 `aes64ks1i` / `aes64ks2` 展开的轮密钥（释放时清零）
+struct ZknKeySchedule<I: ZknInstructions> {
+    isa: I,
+    round_keys: RoundKeys,
+}
+
+impl<I: ZknInstructions> Aes256KeySchedule for ZknKeySchedule<I> {
+    fn encrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(encrypt_block(self.isa, &self.round_keys, block))
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(decrypt_block(self.isa, &self.round_keys, block))
+    }
+}
+
+impl<I: ZknInstructions> Drop for ZknKeySchedule<I> {
+    fn drop(&mut self) {
+        self.round_keys.zeroize();
+    }
+}
+
+/// SHA-256 压缩函数，σ/Σ 使用 Zknh 指令
+fn sha256_compress<I: ZknInstructions>(isa: I, state: &[u8; 32], block: &[u8; 64]) -> [u8; 32] {
+    let mut w = [0u32; 64];
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..64 {
+        w[i] = (isa.sha256sig1(w[i - 2] as u64) as u32)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(isa.sha256sig0(w[i - 15] as u64) as u32)
+            .wrapping_add(w[i - 16]);
+    }
+
+    let mut h = [0u32; 8];
+    for (i, chunk) in state.chunks_exact(4).enumerate() {
+        h[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+    for i in 0..64 {
+        let t1 = hh
+            .wrapping_add(isa.sha256sum1(e as u64) as u32)
+            .wrapping_add((e & f) ^ (!e & g))
+            .wrapping_add(SHA256_K[i])
+            .wrapping_add(w[i]);
+        let t2 = (isa.sha256sum0(a as u64) as u32).wrapping_add((a & b) ^ (a & c) ^ (b & c));
+        hh = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    let mut output = [0u8; 32];
+    for (i, v) in [a, b, c, d, e, f, g, hh].iter().enumerate() {
+        output[i * 4..i * 4 + 4].copy_from_slice(&h[i].wrapping_add(*v).to_be_bytes());
+    }
+    output
+}
+
+/// GHASH 域元素：`[x^0..x^63, x^64..x^127]` 的系数，位 i 对应 x^i
+type Gf128 = [u64; 2];
+
+/// GCM 分组转换为域元素
+///
+/// GCM 以每字节最高位为低次系数，小端加载后用 `brev8` 翻转字节内位序即可。
+/// `brev8` 是对合变换，同一函数也用于转换回分组。
+fn gf128_load<I: ZknInstructions>(isa: I, block: &[u8; 16]) -> Gf128 {
+    let (lo, hi) = load_block(block);
+    [isa.brev8(lo), isa.brev8(hi)]
+}
+
+/// 域元素转换回 GCM 分组
+fn gf128_store<I: ZknInstructions>(isa: I, x: Gf128) -> [u8; 16] {
+    store_block(isa.brev8(x[0]), isa.brev8(x[1]))
+}
+
+/// GF(2^128) 乘法，模 x^128 + x^7 + x^2 + x + 1
+fn gf128_mul<I: ZknInstructions>(isa: I, a: Gf128, b: Gf128) -> Gf128 {
+    // 256 位无进位乘积
+    let r0 = isa.clmul(a[0], b[0]);
+    let r1 = isa.clmulh(a[0], b[0]) ^ isa.clmul(a[0], b[1]) ^ isa.clmul(a[1], b[0]);
+    let r2 = isa.clmulh(a[0], b[1]) ^ isa.clmulh(a[1], b[0]) ^ isa.clmul(a[1], b[1]);
+    let r3 = isa.clmulh(a[1], b[1]);
+
+    // x^128 ≡ x^7 + x^2 + x + 1（0x87），高 128 位折叠两次
+    const R: u64 = 0x87;
+    let t0 = isa.clmul(r2, R);
+    let t1 = isa.clmulh(r2, R) ^ isa.clmul(r3, R);
+    let t2 = isa.clmulh(r3, R);
+    [r0 ^ t0 ^ isa.clmul(t2, R), r1 ^ t1]
+}
+
+/// 计算 GHASH(H, A, C)
+fn ghash<I: ZknInstructions>(isa: I, h: Gf128, aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+    let mut y: Gf128 = [0, 0];
+    for data in [aad, ciphertext] {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            let x = gf128_load(isa, &block);
+            y = gf128_mul(isa, [y[0] ^ x[0], y[1] ^ x[1]], h);
+        }
+    }
+
+    let mut lengths = [0u8; 16];
+    lengths[..8].copy_from_slice(&((aad.len() as u64) * 8).to_be_bytes());
+    lengths[8..].copy_from_slice(&((ciphertext.len() as u64) * 8).to_be_bytes());
+    let x = gf128_load(isa, &lengths);
+    gf128_store(isa, gf128_mul(isa, [y[0] ^ x[0], y[1] ^ x[1]], h))
+}
+
+/// GCM 计数器模式，计数器为分组末 32 位（大端）
+fn gctr<I: ZknInstructions>(isa: I, rk: &RoundKeys, initial: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut counter = *initial;
+    let mut output = Vec::with_capacity(data.len());
+    for chunk in data.chunks(16) {
+        let keystream = encrypt_block(isa, rk, &counter);
+        output.extend(chunk.iter().zip(keystream.iter()).map(|(d, k)| d ^ k));
+
+        let ctr = u32::from_be_bytes(counter[12..].try_into().unwrap()).wrapping_add(1);
+        counter[12..].copy_from_slice(&ctr.to_be_bytes());
+    }
+    output
+}
+
+/// 计算 GCM 认证标签
+fn gcm_tag<I: ZknInstructions>(
+    isa: I,
+    rk: &RoundKeys,
+    nonce: &[u8; 12],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> [u8; 16] {
+    let h = gf128_load(isa, &encrypt_block(isa, rk, &[0u8; 16]));
+    let s = ghash(isa, h, aad, ciphertext);
+    let ek_j0 = encrypt_block(isa, rk, &j0(nonce));
+
+    let mut tag = [0u8; 16];
+    for i in 0..16 {
+        tag[i] = s[i] ^ ek_j0[i];
+    }
+    tag
+}
+
+/// 96 位 nonce 的初始计数器块 J0 = nonce || 0^31 || 1
+fn j0(nonce: &[u8; 12]) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    block[..12].copy_from_slice(nonce);
+    block[15] = 1;
+    block
+}
+
+/// inc32(J0)，数据加密从该计数器开始
+fn first_counter(nonce: &[u8; 12]) -> [u8; 16] {
+    let mut block = j0(nonce);
+    block[15] = 2;
+    block
+}
+
+/// Zkn 指令的软件模型
+///
+/// 指令由本模块的软件模型执行；`ZknBackend<ZknEmulator>` 即 `zkn-emulator` 后端。
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ZknEmulator;
+
+impl ZknInstructions for ZknEmulator {
+    fn aes64es(self, rs1: u64, rs2: u64) -> u64 {
+        aes64es(rs1, rs2)
+    }
+
+    fn aes64esm(self, rs1: u64, rs2: u64) -> u64 {
+        aes64esm(rs1, rs2)
+    }
+
+    fn aes64ds(self, rs1: u64, rs2: u64) -> u64 {
+        aes64ds(rs1, rs2)
+    }
+
+    fn aes64dsm(self, rs1: u64, rs2: u64) -> u64 {
+        aes64dsm(rs1, rs2)
+    }
+
+    fn aes64im(self, rs1: u64) -> u64 {
+        aes64im(rs1)
+    }
+
+    fn aes64ks1i(self, rs1: u64, rnum: u8) -> u64 {
+        aes64ks1i(rs1, rnum)
+    }
+
+    fn aes64ks2(self, rs1: u64, rs2: u64) -> u64 {
+        aes64ks2(rs1, rs2)
+    }
+
+    fn sha256sig0(self, rs1: u64) -> u64 {
+        sha256sig0(rs1)
+    }
+
+    fn sha256sig1(self, rs1: u64) -> u64 {
+        sha256sig1(rs1)
+    }
+
+    fn sha256sum0(self, rs1: u64) -> u64 {
+        sha256sum0(rs1)
+    }
+
+    fn sha256sum1(self, rs1: u64) -> u64 {
+        sha256sum1(rs1)
+    }
+
+    fn brev8(self, rs1: u64) -> u64 {
+        brev8(rs1)
+    }
+
+    fn clmul(self, rs1: u64, rs2: u64) -> u64 {
+        clmul(rs1, rs2)
+    }
+
+    fn clmulh(self, rs1: u64, rs2: u64) -> u64 {
+        clmulh(rs1, rs2)
+    }
+}
+
+/// 由 Zkn 指令组合而成的加速器后端
+///
+/// AES-256（分组与 GCM）和 SHA-256 只通过 `isa` 的指令计算，
+/// 其余没有对应标量加密指令的运算委托给 `SoftwareBackend`。
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ZknBackend<I: ZknInstructions> {
+    isa: I,
+}
+
+#[allow(dead_code)]
+impl<I: ZknInstructions> ZknBackend<I> {
+    /// 使用指定的指令来源创建后端
+    ///
+    /// # 参数
+    ///
+    /// * `isa` - 指令来源，如 `ZknEmulator` 或 `ZknHardware`
+    pub fn new(isa: I) -> Self {
+        ZknBackend { isa }
+    }
+
+    /// 指令来源
+    pub fn isa(&self) -> I {
+        self.isa
+    }
+}
+
+impl<I: ZknInstructions> RiscVCryptoExt for ZknBackend<I> {
+    fn copr_encrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, [u8; 16]), Box<dyn Error>> {
+        let rk = expand_key(self.isa, key);
+        let ciphertext = gctr(self.isa, &rk, &first_counter(nonce), plaintext);
+        let tag = gcm_tag(self.isa, &rk, nonce, aad, &ciphertext);
+        Ok((ciphertext, tag))
+    }
+
+    fn copr_decrypt_aes256(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8; 12],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8; 16],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let rk = expand_key(self.isa, key);
+        let expected = gcm_tag(self.isa, &rk, nonce, aad, ciphertext);
+
+        // 常数时间比较，耗时与标签内容无关
+        if !bool::from(expected.ct_eq(tag)) {
+            return Err(Box::new(ZknError {
+                message: "AES-GCM authentication failed".to_string(),
+            }));
+        }
+
+        Ok(gctr(self.isa, &rk, &first_counter(nonce), ciphertext))
+    }
+
+    fn copr_aes256_encrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(encrypt_block(self.isa, &expand_key(self.isa, key), block))
+    }
+
+    fn copr_aes256_decrypt_block(
+        &self,
+        key: &[u8; 32],
+        block: &[u8; 16],
+    ) -> Result<[u8; 16], Box<dyn Error>> {
+        Ok(decrypt_block(self.isa, &expand_key(self.isa, key), block))
+    }
+
+    fn copr_aes256_key_schedule(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Box<dyn Aes256KeySchedule>, Box<dyn Error>> {
+        Ok(Box::new(ZknKeySchedule {
+            isa: self.isa,
+            round_keys: expand_key(self.isa, key),
+        }))
+    }
+
+    fn copr_hash_sha256(&self, input: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        let mut hasher = Sha256Riscv::with_accelerator(Box::new(*self));
+        hasher.update(input);
+        Ok(hasher.finalize())
+    }
+
+    fn copr_sha256_compress(
+        &self,
+        state: &[u8; 32],
+        block: &[u8; 64],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        Ok(sha256_compress(self.isa, state, block))
+    }
+
+    fn copr_keccak_f1600(&self, state: &[u8; 200]) -> Result<[u8; 200], Box<dyn Error>> {
+        SoftwareBackend.copr_keccak_f1600(state)
+    }
+
+    fn copr_ec_mul(&self, scalar: &[u8; 32], point: &[u8; 32]) -> Result<[u8; 32], Box<dyn Error>> {
+        SoftwareBackend.copr_ec_mul(scalar, point)
+    }
+
+    fn copr_ec_add(
+        &self,
+        point1: &[u8; 32],
+        point2: &[u8; 32],
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        SoftwareBackend.copr_ec_add(point1, point2)
+    }
+
+    fn copr_sign_ed25519(
+        &self,
+        secret: &[u8; 32],
+        message: &[u8],
+    ) -> Result<[u8; 64], Box<dyn Error>> {
+        SoftwareBackend.copr_sign_ed25519(secret, message)
+    }
+
+    fn copr_verify_ed25519(
+        &self,
+        public: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<bool, Box<dyn Error>> {
+        SoftwareBackend.copr_verify_ed25519(public, message, signature)
+    }
+
+    fn copr_verify_ed25519_batch(
+        &self,
+        items: &[([u8; 32], &[u8], [u8; 64])],
+    ) -> Result<Vec<bool>, Box<dyn Error>> {
+        SoftwareBackend.copr_verify_ed25519_batch(items)
+    }
+
+    fn copr_zkp_prove(&self, witness: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        SoftwareBackend.copr_zkp_prove(witness)
+    }
+
+    fn copr_zkp_verify(&self, proof: &[u8], public_input: &[u8]) -> Result<bool, Box<dyn Error>> {
+        SoftwareBackend.copr_zkp_verify(proof, public_input)
+    }
+
+    fn copr_msm_accelerate(
+        &self,
+        points: &[Vec<u8>],
+        scalars: &[Vec<u8>],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        SoftwareBackend.copr_msm_accelerate(points, scalars)
+    }
+}
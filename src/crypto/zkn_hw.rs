@@ -0,0 +1,207 @@
+//! RISC-V Zkn 硬件指令后端（`riscv-zkn` 特性）
+//!
+//! 在 riscv64 上用 `core::arch::asm!` 直接执行 Zkne / Zknd / Zknh / Zbkb / Zbkc 指令，
+//! AES-256、AES-256-GCM 和 SHA-256 的组合算法与 `ZknEmulator` 共用（见 `crate::crypto::zkn`），
+//! 两者只在指令来源上不同。
+//!
+//! 编译目标不要求开启这些扩展：指令通过 `.option arch` 局部启用，
+//! 是否可用在运行时检测。CPU 不支持或不是 riscv64 时，`accelerated_backend()`
+//! 自动回退到 `SoftwareBackend`。
+//!
+//! # 交叉编译与测试
+//!
+//! `.cargo/config.toml` 为 `riscv64gc-unknown-linux-gnu` 配置了链接器和 qemu-user 运行器：
+//!
+//! ```text
+//! rustup target add riscv64gc-unknown-linux-gnu
+//! cargo test --features riscv-zkn --target riscv64gc-unknown-linux-gnu
+//! ```
+
+use crate::crypto::riscv_ext::{RiscVCryptoExt, SoftwareBackend};
+#[cfg(target_arch = "riscv64")]
+use crate::crypto::zkn::ZknBackend;
+
+/// 当前 CPU 是否支持本后端需要的全部指令
+///
+/// # 返回
+///
+/// 在 riscv64 上检测到 Zkne、Zknd、Zknh、Zbkb 和 Zbkc 时为 `true`，其他架构恒为 `false`
+#[allow(dead_code)]
+pub fn zkn_available() -> bool {
+    #[cfg(target_arch = "riscv64")]
+    {
+        std::arch::is_riscv_feature_detected!("zkne")
+            && std::arch::is_riscv_feature_detected!("zknd")
+            && std::arch::is_riscv_feature_detected!("zknh")
+            && std::arch::is_riscv_feature_detected!("zbkb")
+            && std::arch::is_riscv_feature_detected!("zbkc")
+    }
+    #[cfg(not(target_arch = "riscv64"))]
+    {
+        false
+    }
+}
+
+/// 选择可用的最快实现
+///
+/// # 返回
+///
+/// 支持 Zkn 时为 `ZknBackend<ZknHardware>`，否则为 `SoftwareBackend`
+pub fn accelerated_backend() -> Box<dyn RiscVCryptoExt> {
+    #[cfg(target_arch = "riscv64")]
+    {
+        if let Some(hardware) = ZknHardware::detect() {
+            return Box::new(ZknBackend::new(hardware));
+        }
+    }
+    Box::new(SoftwareBackend)
+}
+
+#[cfg(target_arch = "riscv64")]
+pub use self::riscv64::ZknHardware;
+
+#[cfg(target_arch = "riscv64")]
+mod riscv64 {
+    use crate::crypto::zkn::ZknInstructions;
+    use core::arch::asm;
+
+    /// 执行单条 R 型或 I 型 Zkn 指令
+    ///
+    /// 指令只读写寄存器，因此标记为 `pure, nomem, nostack`，
+    /// 编译器可以像普通算术一样调度和消除它们。
+    macro_rules! zkn_op {
+        ($insn:literal, $rs1:expr) => {{
+            let rd: u64;
+            unsafe {
+                asm!(
+                    ".option push",
+                    ".option arch, +zkne, +zknd, +zknh, +zbkb, +zbkc",
+                    concat!($insn, " {rd}, {rs1}"),
+                    ".option pop",
+                    rd = lateout(reg) rd,
+                    rs1 = in(reg) $rs1,
+                    options(pure, nomem, nostack),
+                );
+            }
+            rd
+        }};
+        ($insn:literal, $rs1:expr, $rs2:expr) => {{
+            let rd: u64;
+            unsafe {
+                asm!(
+                    ".option push",
+                    ".option arch, +zkne, +zknd, +zknh, +zbkb, +zbkc",
+                    concat!($insn, " {rd}, {rs1}, {rs2}"),
+                    ".option pop",
+                    rd = lateout(reg) rd,
+                    rs1 = in(reg) $rs1,
+                    rs2 = in(reg) $rs2,
+                    options(pure, nomem, nostack),
+                );
+            }
+            rd
+        }};
+    }
+
+    /// `aes64ks1i` 的轮号是立即数，按取值展开
+    macro_rules! aes64ks1i_dispatch {
+        ($rs1:expr, $rnum:expr, [$($n:literal),*]) => {
+            match $rnum {
+                $($n => {
+                    let rd: u64;
+                    unsafe {
+                        asm!(
+                            ".option push",
+                            ".option arch, +zkne",
+                            "aes64ks1i {rd}, {rs1}, {rnum}",
+                            ".option pop",
+                            rd = lateout(reg) rd,
+                            rs1 = in(reg) $rs1,
+                            rnum = const $n,
+                            options(pure, nomem, nostack),
+                        );
+                    }
+                    rd
+                })*
+                other => panic!("aes64ks1i: reserved rnum {:#x}", other),
+            }
+        };
+    }
+
+    /// 使用 CPU 的 Zkn 指令的后端
+    ///
+    /// 只能通过 `detect()` 创建，持有该值即表示指令可用。
+    #[derive(Clone, Copy, Debug)]
+    pub struct ZknHardware {
+        _detected: (),
+    }
+
+    impl ZknHardware {
+        /// 运行时检测 CPU 支持
+        ///
+        /// # 返回
+        ///
+        /// 支持全部所需扩展时返回 `Some(ZknHardware)`
+        pub fn detect() -> Option<Self> {
+            super::zkn_available().then_some(ZknHardware { _detected: () })
+        }
+    }
+
+    impl ZknInstructions for ZknHardware {
+        fn aes64es(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("aes64es", rs1, rs2)
+        }
+
+        fn aes64esm(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("aes64esm", rs1, rs2)
+        }
+
+        fn aes64ds(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("aes64ds", rs1, rs2)
+        }
+
+        fn aes64dsm(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("aes64dsm", rs1, rs2)
+        }
+
+        fn aes64im(self, rs1: u64) -> u64 {
+            zkn_op!("aes64im", rs1)
+        }
+
+        fn aes64ks1i(self, rs1: u64, rnum: u8) -> u64 {
+            aes64ks1i_dispatch!(rs1, rnum, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10])
+        }
+
+        fn aes64ks2(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("aes64ks2", rs1, rs2)
+        }
+
+        fn sha256sig0(self, rs1: u64) -> u64 {
+            zkn_op!("sha256sig0", rs1)
+        }
+
+        fn sha256sig1(self, rs1: u64) -> u64 {
+            zkn_op!("sha256sig1", rs1)
+        }
+
+        fn sha256sum0(self, rs1: u64) -> u64 {
+            zkn_op!("sha256sum0", rs1)
+        }
+
+        fn sha256sum1(self, rs1: u64) -> u64 {
+            zkn_op!("sha256sum1", rs1)
+        }
+
+        fn brev8(self, rs1: u64) -> u64 {
+            zkn_op!("brev8", rs1)
+        }
+
+        fn clmul(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("clmul", rs1, rs2)
+        }
+
+        fn clmulh(self, rs1: u64, rs2: u64) -> u64 {
+            zkn_op!("clmulh", rs1, rs2)
+        }
+    }
+}
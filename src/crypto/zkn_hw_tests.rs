@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod zkn_hardware_tests {
+    use crate::crypto::backend::BackendRegistry;
+    use crate::crypto::riscv_ext::{HardwareAccelerator, RiscVCryptoExt};
+    use crate::crypto::test_util::hex;
+    use crate::crypto::zkn_hw::{accelerated_backend, zkn_available};
+
+    #[test]
+    fn test_detection_matches_target() {
+        if !cfg!(target_arch = "riscv64") {
+            assert!(!zkn_available());
+        }
+    }
+
+    #[test]
+    fn test_accelerated_backend_vectors() {
+        // FIPS-197 C.3 与 FIPS 180-2 "abc"，无论使用硬件还是回退实现都必须通过
+        let key: [u8; 32] = hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+            .try_into()
+            .unwrap();
+        let plaintext: [u8; 16] = hex("00112233445566778899aabbccddeeff").try_into().unwrap();
+
+        for backend in [
+            accelerated_backend(),
+            BackendRegistry::new().create("riscv-zkn").unwrap(),
+        ] {
+            let ciphertext = backend.copr_aes256_encrypt_block(&key, &plaintext).unwrap();
+            assert_eq!(ciphertext.to_vec(), hex("8ea2b7ca516745bfeafc49904b496089"));
+            assert_eq!(
+                backend
+                    .copr_aes256_decrypt_block(&key, &ciphertext)
+                    .unwrap(),
+                plaintext
+            );
+            assert_eq!(
+                backend.copr_hash_sha256(b"abc").unwrap().to_vec(),
+                hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+            );
+        }
+    }
+
+    #[test]
+    fn test_accelerated_backend_matches_reference() {
+        let backend = accelerated_backend();
+        let reference = HardwareAccelerator::new();
+
+        for len in 0..20 {
+            let key: [u8; 32] = rand::random();
+            let nonce: [u8; 12] = rand::random();
+            let data: Vec<u8> = (0..len * 7).map(|_| rand::random()).collect();
+
+            let sealed = backend
+                .copr_encrypt_aes256(&key, &nonce, &data[..len], &data)
+                .unwrap();
+            assert_eq!(
+                sealed,
+                reference
+                    .copr_encrypt_aes256(&key, &nonce, &data[..len], &data)
+                    .unwrap()
+            );
+            assert_eq!(
+                backend
+                    .copr_decrypt_aes256(&key, &nonce, &data[..len], &sealed.0, &sealed.1)
+                    .unwrap(),
+                data
+            );
+            assert_eq!(
+                backend.copr_hash_sha256(&data).unwrap(),
+                reference.copr_hash_sha256(&data).unwrap()
+            );
+        }
+    }
+
+    #[cfg(target_arch = "riscv64")]
+    #[test]
+    fn test_hardware_instructions_match_models() {
+        use crate::crypto::zkn::{ZknEmulator, ZknInstructions};
+        use crate::crypto::zkn_hw::ZknHardware;
+
+        let Some(hw) = ZknHardware::detect() else {
+            eprintln!("Zkn not available on this CPU, skipping instruction comparison");
+            return;
+        };
+        let model = ZknEmulator;
+
+        for _ in 0..256 {
+            let (a, b): (u64, u64) = (rand::random(), rand::random());
+            assert_eq!(hw.aes64es(a, b), model.aes64es(a, b));
+            assert_eq!(hw.aes64esm(a, b), model.aes64esm(a, b));
+            assert_eq!(hw.aes64ds(a, b), model.aes64ds(a, b));
+            assert_eq!(hw.aes64dsm(a, b), model.aes64dsm(a, b));
+            assert_eq!(hw.aes64im(a), model.aes64im(a));
+            assert_eq!(hw.aes64ks2(a, b), model.aes64ks2(a, b));
+            for rnum in 0..=10 {
+                assert_eq!(hw.aes64ks1i(a, rnum), model.aes64ks1i(a, rnum));
+            }
+            assert_eq!(hw.sha256sig0(a), model.sha256sig0(a));
+            assert_eq!(hw.sha256sig1(a), model.sha256sig1(a));
+            assert_eq!(hw.sha256sum0(a), model.sha256sum0(a));
+            assert_eq!(hw.sha256sum1(a), model.sha256sum1(a));
+            assert_eq!(hw.brev8(a), model.brev8(a));
+            assert_eq!(hw.clmul(a, b), model.clmul(a, b));
+            assert_eq!(hw.clmulh(a, b), model.clmulh(a, b));
+        }
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod zkn_emulator_tests {
+    use crate::crypto::riscv_ext::{HardwareAccelerator, RiscVCryptoExt};
+    use crate::crypto::sha256::Sha256Riscv;
+    use crate::crypto::test_util::hex;
+    use crate::crypto::zkn::{
+        ZknBackend, ZknEmulator, aes64ks1i, aes64ks2, brev8, clmul, clmulh, sha256sig0, sha256sum1,
+    };
+
+    #[test]
+    fn test_aes64ks_first_round_matches_fips197() {
+        // FIPS-197 A.3：w[8] = 9ba35411, w[9] = 8e6925af
+        let key = hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+        let rk0 = u64::from_le_bytes(key[0..8].try_into().unwrap());
+        let rk3 = u64::from_le_bytes(key[24..32].try_into().unwrap());
+
+        let rk4 = aes64ks2(aes64ks1i(rk3, 0), rk0);
+        assert_eq!(rk4.to_le_bytes().to_vec(), hex("9ba354118e6925af"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_aes64ks1i_rejects_reserved_rnum() {
+        aes64ks1i(0, 0xB);
+    }
+
+    #[test]
+    fn test_scalar_instruction_models() {
+        // RV64 上 Zknh 指令只读取 rs1 低 32 位，并将结果符号扩展
+        let x = 0xdead_beef_6a09_e667u64;
+        let sig0 = sha256sig0(x);
+        assert_eq!(sig0, sha256sig0(x & 0xffff_ffff));
+        assert_eq!(sig0, sig0 as u32 as i32 as i64 as u64);
+        assert_eq!(sha256sum1(0x510e527f) as u32, {
+            let e = 0x510e527fu32;
+            e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25)
+        });
+
+        assert_eq!(brev8(0x0102_0408_1020_4080), 0x8040_2010_0804_0201);
+        assert_eq!(clmul(0x87, 0x87), 0x4015);
+        assert_eq!(clmulh(u64::MAX, 2), 1);
+        assert_eq!(clmulh(1 << 63, 1 << 63), 1 << 62);
+    }
+
+    #[test]
+    fn test_zkn_aes256_fips197_vector() {
+        // FIPS-197 C.3
+        let key: [u8; 32] = hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+            .try_into()
+            .unwrap();
+        let plaintext: [u8; 16] = hex("00112233445566778899aabbccddeeff").try_into().unwrap();
+        let expected = hex("8ea2b7ca516745bfeafc49904b496089");
+
+        let zkn = ZknBackend::new(ZknEmulator);
+        let ciphertext = zkn.copr_aes256_encrypt_block(&key, &plaintext).unwrap();
+        assert_eq!(ciphertext.to_vec(), expected);
+        assert_eq!(
+            zkn.copr_aes256_decrypt_block(&key, &ciphertext).unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn test_zkn_aes256_gcm_vectors() {
+        // McGrew & Viega Test Case 14 与 16
+        let k = "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308";
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "000000000000000000000000",
+                "",
+                "00000000000000000000000000000000",
+                "cea7403d4d606b6e074ec5d3baf39d18",
+                "d0d1c8a799996bf0265b98b5d48ab919",
+            ),
+            (
+                k,
+                "cafebabefacedbaddecaf888",
+                "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+                "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+                "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+                "76fc6ece0f4e1768cddf8853bb2d551b",
+            ),
+        ];
+
+        let zkn = ZknBackend::new(ZknEmulator);
+        for (key, nonce, aad, pt, ct, tag) in vectors {
+            let key: [u8; 32] = hex(key).try_into().unwrap();
+            let nonce: [u8; 12] = hex(nonce).try_into().unwrap();
+            let (ciphertext, computed_tag) = zkn
+                .copr_encrypt_aes256(&key, &nonce, &hex(aad), &hex(pt))
+                .unwrap();
+            assert_eq!(ciphertext, hex(ct));
+            assert_eq!(computed_tag.to_vec(), hex(tag));
+
+            let plaintext = zkn
+                .copr_decrypt_aes256(&key, &nonce, &hex(aad), &ciphertext, &computed_tag)
+                .unwrap();
+            assert_eq!(plaintext, hex(pt));
+
+            let mut bad_tag = computed_tag;
+            bad_tag[15] ^= 1;
+            assert!(
+                zkn.copr_decrypt_aes256(&key, &nonce, &hex(aad), &ciphertext, &bad_tag)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_zkn_sha256_vectors() {
+        let zkn = ZknBackend::new(ZknEmulator);
+        assert_eq!(
+            zkn.copr_hash_sha256(b"abc").unwrap().to_vec(),
+            hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            zkn.copr_hash_sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")
+                .unwrap()
+                .to_vec(),
+            hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+    }
+
+    #[test]
+    fn test_zkn_matches_reference_backend() {
+        let zkn = ZknBackend::new(ZknEmulator);
+        let reference = HardwareAccelerator::new();
+
+        for len in 0..40 {
+            let key: [u8; 32] = rand::random();
+            let block: [u8; 16] = rand::random();
+            let nonce: [u8; 12] = rand::random();
+            let data: Vec<u8> = (0..len * 5).map(|_| rand::random()).collect();
+
+            assert_eq!(
+                zkn.copr_aes256_encrypt_block(&key, &block).unwrap(),
+                reference.copr_aes256_encrypt_block(&key, &block).unwrap()
+            );
+            assert_eq!(
+                zkn.copr_aes256_decrypt_block(&key, &block).unwrap(),
+                reference.copr_aes256_decrypt_block(&key, &block).unwrap()
+            );
+            assert_eq!(
+                zkn.copr_encrypt_aes256(&key, &nonce, &data[..len], &data)
+                    .unwrap(),
+                reference
+                    .copr_encrypt_aes256(&key, &nonce, &data[..len], &data)
+                    .unwrap()
+            );
+            assert_eq!(
+                zkn.copr_hash_sha256(&data).unwrap(),
+                reference.copr_hash_sha256(&data).unwrap()
+            );
+        }
+
+        let mut hasher = Sha256Riscv::with_accelerator(Box::new(ZknBackend::new(ZknEmulator)));
+        hasher.update(&[0x61u8; 1000]);
+        assert_eq!(hasher.finalize(), Sha256Riscv::hash(&[0x61u8; 1000]));
+    }
+}
@@ -10,6 +10,9 @@
 //!
 //! ```bash
 //! cargo run
+//!
+//! # 启动用户态加密设备替身守护进程（默认 /tmp/zk-crypto0.sock）
+//! cargo run -- crypto-daemon [socket-path]
 //! ```
 //!
 //! # 输出说明
@@ -27,7 +30,21 @@ mod tee;
 /// 2. TEE Enclave 功能测试
 /// 3. ZK Proof 系统测试
 /// 4. Rollup 状态管理测试
+///
+/// 以 `crypto-daemon [socket-path]` 参数运行时改为启动加密设备替身守护进程。
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("crypto-daemon") {
+        run_crypto_daemon(args.get(2).map_or("/tmp/zk-crypto0.sock", String::as_str));
+        return;
+    }
+
+    // 启动时解析一次加速器后端，错误配置在这里报告而不是在首次哈希时
+    if let Err(e) = crypto::backend::try_default_backend() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
     println!("ZK Rollup with RISC-V Optimization and TEE Encryption");
     println!("======================================================");
 
@@ -46,6 +63,26 @@ fn main() {
     println!("\nAll tests passed successfully!");
 }
 
+/// 运行加密设备替身守护进程
+///
+/// 在 `path` 上用软件实现设备协议，配合 `ZK_ROLLUP_CRYPTO_BACKEND=device`
+/// 与 `ZK_ROLLUP_CRYPTO_DEVICE=<path>` 使用。
+fn run_crypto_daemon(path: &str) {
+    let server = crypto::device::DeviceServer::bind(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    println!(
+        "crypto device daemon listening on {}",
+        server.path().display()
+    );
+    let result = server.serve_with(|e| eprintln!("crypto device: {}", e));
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
 /// 测试 RISC-V 加密扩展模块
 ///
 /// 测试以下加密操作：
@@ -55,11 +92,12 @@ fn main() {
 fn test_riscv_crypto() {
     use crypto::aes::Aes256Riscv;
     use crypto::ec::Ed25519Riscv;
+    use crypto::secret::Aes256Key;
     use crypto::sha256::Sha256Riscv;
 
     // 测试 AES-256
     let aes = Aes256Riscv::new();
-    let key = [0u8; 32];
+    let key = Aes256Key::new([0u8; 32]);
     let plaintext = b"Test AES-256 encryption";
     let encrypted = aes.encrypt_aes256(plaintext, &key).unwrap();
     let decrypted = aes.decrypt_aes256(&encrypted, &key).unwrap();
@@ -73,7 +111,7 @@ fn test_riscv_crypto() {
 
     // 测试 Ed25519
     let ed25519 = Ed25519Riscv::new();
-    let (sk, pk) = ed25519.keygen();
+    let (sk, pk) = ed25519.keygen().unwrap();
     let message = b"Test Ed25519 signature";
     let signature = ed25519.sign(&sk, message).unwrap();
     let valid = ed25519.verify(&pk, message, &signature).unwrap();
@@ -183,7 +221,7 @@ fn test_rollup_state() {
 
     // 生成密钥对
     let ed25519 = Ed25519Riscv::new();
-    let (secret_key, public_key) = ed25519.keygen();
+    let (secret_key, public_key) = ed25519.keygen().unwrap();
 
     // 创建账户
     let account1 = Account {
@@ -235,7 +273,7 @@ fn test_rollup_state() {
         nonce: 1,
         signature: vec![0u8; 64],
     };
-    let wrong_secret = [5u8; 32];
+    let wrong_secret = crypto::secret::Ed25519SecretKey::new([5u8; 32]);
     invalid_tx.sign(&wrong_secret).unwrap();
     assert!(state.apply_transfer(&invalid_tx).is_err());
 
@@ -1,7 +1,10 @@
-/// Rollup 状态管理模块
-///
-/// 该模块管理 ZK Rollup 的链上状态，包括账户管理、余额管理、转账处理和 Merkle 根计算。
+//! Rollup 状态管理模块
+//!
+//! 该模块管理 ZK Rollup 的链上状态，包括账户管理、余额管理、转账处理和 Merkle 根计算。
 
+#[allow(dead_code)]
+use crate::crypto::ec::Ed25519Riscv;
+use crate::crypto::hash::{MerkleLayout, MerkleProof, MerkleTree, Sha256Hasher};
 #[allow(dead_code)]
 use std::collections::HashMap;
 #[allow(dead_code)]
@@ -140,6 +143,7 @@ pub struct RollupState {
     next_account_id: u32,
 }
 
+#[allow(dead_code)]
 impl RollupState {
     /// 创建新的 Rollup 状态
     ///
@@ -256,6 +260,27 @@ impl RollupState {
     pub fn apply_transfer(
         &mut self,
         tx: &super::transaction::TransferTx,
+    ) -> Result<(), Box<dyn Error>> {
+        self.apply_transfer_with(tx, &Ed25519Riscv::new())
+    }
+
+    /// 使用指定的签名验证器应用转账交易
+    ///
+    /// 签名验证在修改任何账户之前完成，验证器出错时状态保持不变。
+    ///
+    /// # 参数
+    ///
+    /// * `tx` - 转账交易
+    /// * `verifier` - 验证交易签名的 Ed25519 签名器
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(())`: 转账成功
+    /// - `Err(Box<dyn Error>)`: 验证失败或验证器错误
+    pub fn apply_transfer_with(
+        &mut self,
+        tx: &super::transaction::TransferTx,
+        verifier: &Ed25519Riscv,
     ) -> Result<(), Box<dyn Error>> {
         let from_account = self.accounts.get_mut(&tx.from).ok_or_else(|| {
             Box::new(StateError {
@@ -275,7 +300,7 @@ impl RollupState {
             })
         })?;
 
-        if !tx.verify_signature(&public_key)? {
+        if !tx.verify_signature_with(verifier, &public_key)? {
             return Err(Box::new(StateError {
                 message: "Invalid signature".to_string(),
             }));
@@ -301,59 +326,62 @@ impl RollupState {
     /// 计算整个状态树的根哈希。
     /// 用于 L1 同步和欺诈证明。
     ///
+    /// # 树的构造
+    ///
+    /// 使用 `crypto::hash` 的 `MerkleTree<Sha256Hasher>`，形状为 `MerkleLayout::ZeroPad`：
+    /// 账户按 ID 升序作为叶子，叶子数补全 0 值到 2 的幂。满二叉树使同样账户数的
+    /// 证明长度固定，便于在电路中验证；叶子和分支由树自身加前缀做域分隔。
+    /// 账户的包含证明见 `account_proof`。
+    ///
     /// # 编码格式
     ///
-    /// 叶子节点编码：[账户 ID (4字节)][ETH 余额低32位 (4字节)]...[填充]
+    /// 叶子数据编码：[账户 ID (4字节，小端序)][ETH 余额 (8字节，小端序)]
     ///
     /// # 返回
     ///
     /// - `Ok([u8; 32])`: 32 字节 Merkle 根
     /// - `Err(Box<dyn Error>)`: 计算失败
     pub fn get_merkle_root(&self) -> Result<[u8; 32], Box<dyn Error>> {
-        use crate::crypto::sha256::Sha256Riscv;
-
-        let mut leaves: Vec<[u8; 32]> = self
-            .accounts
-            .iter()
-            .filter(|(_, acc)| acc.id < 4)
-            .map(|(_, acc)| {
-                let mut leaf = [0u8; 32];
-                leaf[..4].copy_from_slice(&acc.id.to_le_bytes());
-                let eth_bytes = acc.balance.eth.to_le_bytes();
-                leaf[4..8].copy_from_slice(&eth_bytes[..4]);
-                leaf
-            })
-            .collect();
-
-        leaves.sort_by_key(|leaf| u32::from_le_bytes(leaf[..4].try_into().unwrap()));
+        Ok(self.state_tree().root())
+    }
 
-        while leaves.len() < 4 {
-            leaves.push([0u8; 32]);
-        }
+    /// 生成账户的状态树包含证明
+    ///
+    /// # 参数
+    ///
+    /// * `id` - 账户 ID
+    ///
+    /// # 返回
+    ///
+    /// - `Ok((Vec<u8>, usize, MerkleProof))`: 叶子数据编码、叶子序号和证明，
+    ///   可用 `verify_proof::<Sha256Hasher>` 对 `get_merkle_root` 的结果验证
+    /// - `Err(Box<dyn Error>)`: 账户不存在
+    pub fn account_proof(&self, id: u32) -> Result<(Vec<u8>, usize, MerkleProof), Box<dyn Error>> {
+        let mut ids: Vec<u32> = self.accounts.keys().copied().collect();
+        ids.sort_unstable();
+        let index = ids.binary_search(&id).map_err(|_| {
+            Box::new(StateError {
+                message: "Account not found".to_string(),
+            })
+        })?;
+        let proof = self.state_tree().proof(index)?;
+        Ok((Self::encode_leaf(&self.accounts[&id]), index, proof))
+    }
 
-        let mut level1 = Vec::new();
-        for i in 0..2 {
-            let mut combined = [0u8; 64];
-            combined[..32].copy_from_slice(&leaves[i * 2]);
-            combined[32..].copy_from_slice(&leaves[i * 2 + 1]);
-            let hash = Sha256Riscv::hash(&combined);
-            level1.push(hash);
-        }
+    /// 账户在状态树中的叶子数据
+    fn encode_leaf(account: &Account) -> Vec<u8> {
+        let mut leaf = Vec::with_capacity(12);
+        leaf.extend_from_slice(&account.id.to_le_bytes());
+        leaf.extend_from_slice(&account.balance.eth.to_le_bytes());
+        leaf
+    }
 
-        let mut root = [0u8; 32];
-        let root_input = if level1.len() >= 2 {
-            let mut root_combined = [0u8; 64];
-            root_combined[..32].copy_from_slice(&level1[0]);
-            root_combined[32..].copy_from_slice(&level1[1]);
-            Sha256Riscv::hash(&root_combined)
-        } else if !level1.is_empty() {
-            level1[0]
-        } else {
-            [0u8; 32]
-        };
-        root.copy_from_slice(&root_input);
-
-        Ok(root)
+    /// 按账户 ID 升序构建状态树
+    fn state_tree(&self) -> MerkleTree<Sha256Hasher> {
+        let mut accounts: Vec<&Account> = self.accounts.values().collect();
+        accounts.sort_by_key(|account| account.id);
+        let leaves: Vec<Vec<u8>> = accounts.into_iter().map(Self::encode_leaf).collect();
+        MerkleTree::with_layout(&leaves, MerkleLayout::ZeroPad)
     }
 
     /// 获取账户数量
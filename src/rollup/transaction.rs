@@ -1,9 +1,11 @@
+//! Rollup 交易模块
+//!
+//! 该模块定义 ZK Rollup 支持的交易类型和交易构建工具。
+
 #[allow(dead_code)]
 use crate::crypto::ec::Ed25519Riscv;
-/// Rollup 交易模块
-///
-/// 该模块定义 ZK Rollup 支持的交易类型和交易构建工具。
-
+#[allow(dead_code)]
+use crate::crypto::secret::Ed25519SecretKey;
 #[allow(dead_code)]
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +20,7 @@ pub struct TransferTx {
     pub signature: Vec<u8>,
 }
 
+#[allow(dead_code)]
 impl TransferTx {
     /// 获取签名的 64 字节数组引用
     ///
@@ -52,16 +55,36 @@ impl TransferTx {
     ///
     /// # 参数
     ///
-    /// * `secret` - 32 字节 Ed25519 私钥
+    /// * `secret` - Ed25519 私钥
     ///
     /// # 返回
     ///
     /// - `Ok(())`: 签名成功
     /// - `Err(Box<dyn Error>)`: 签名失败
-    pub fn sign(&mut self, secret: &[u8; 32]) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn sign(&mut self, secret: &Ed25519SecretKey) -> Result<(), Box<dyn std::error::Error>> {
+        self.sign_with(&Ed25519Riscv::new(), secret)
+    }
+
+    /// 使用指定的签名器对交易签名
+    ///
+    /// 签名失败时签名字段保持不变。
+    ///
+    /// # 参数
+    ///
+    /// * `signer` - 执行签名的 Ed25519 签名器
+    /// * `secret` - Ed25519 私钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(())`: 签名成功
+    /// - `Err(Box<dyn Error>)`: 签名失败（如加速器错误）
+    pub fn sign_with(
+        &mut self,
+        signer: &Ed25519Riscv,
+        secret: &Ed25519SecretKey,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let message = self.to_message();
-        let ed25519 = Ed25519Riscv::new();
-        let signature = ed25519.sign(secret, &message)?;
+        let signature = signer.sign(secret, &message)?;
         self.signature = signature.to_vec();
         Ok(())
     }
@@ -82,16 +105,35 @@ impl TransferTx {
     pub fn verify_signature(
         &self,
         public_key: &[u8; 32],
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        self.verify_signature_with(&Ed25519Riscv::new(), public_key)
+    }
+
+    /// 使用指定的签名器验证交易签名
+    ///
+    /// # 参数
+    ///
+    /// * `verifier` - 执行验证的 Ed25519 签名器
+    /// * `public_key` - 发送方的 32 字节 Ed25519 公钥
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(true)`: 签名有效
+    /// - `Ok(false)`: 签名无效
+    /// - `Err(Box<dyn Error>)`: 签名长度错误或加速器错误
+    pub fn verify_signature_with(
+        &self,
+        verifier: &Ed25519Riscv,
+        public_key: &[u8; 32],
     ) -> Result<bool, Box<dyn std::error::Error>> {
         let message = self.to_message();
-        let sig_array: [u8; 64] = self.signature[..64].try_into().map_err(|_| {
+        let sig_array = self.signature_bytes().ok_or_else(|| {
             Box::new(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                "Invalid signature length",
+                format!("Invalid signature length: {}", self.signature.len()),
             ))
         })?;
-        let ed25519 = Ed25519Riscv::new();
-        ed25519.verify(public_key, &message, &sig_array)
+        verifier.verify(public_key, &message, sig_array)
     }
 }
 
@@ -103,12 +145,14 @@ impl TransferTx {
 /// # 变体
 ///
 /// * `Transfer(TransferTx)`: 转账交易
+#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Transaction {
     /// 转账交易变体
     Transfer(TransferTx),
 }
 
+#[allow(dead_code)]
 impl Transaction {
     /// 获取交易的发送方账户 ID
     ///
@@ -149,6 +193,7 @@ impl Transaction {
 ///     .nonce(0)          // 可选，默认为 0
 ///     .build()           // 构建交易
 /// ```
+#[allow(dead_code)]
 #[derive(Debug)]
 pub struct TransactionBuilder {
     /// 发送方账户 ID
@@ -161,6 +206,7 @@ pub struct TransactionBuilder {
     nonce: Option<u32>,
 }
 
+#[allow(dead_code)]
 impl TransactionBuilder {
     /// 创建新的交易构建器
     ///
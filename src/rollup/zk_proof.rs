@@ -1,7 +1,15 @@
-/// ZK Proof 零知识证明模块
-///
-/// 该模块实现 Groth16 零知识证明协议，用于生成和验证零知识证明。
+//! ZK Proof 零知识证明模块
+//!
+//! 该模块实现 Groth16 零知识证明协议，用于生成和验证零知识证明。
+//! 电路以 BN254 标量域 `Fr` 上的 R1CS 约束系统（`ConstraintSystem`）描述，
+//! `PoseidonGadget` 在电路内计算与 `crypto::poseidon` 一致的 Poseidon 哈希。
 
+#[allow(dead_code)]
+use crate::crypto::field::Fr;
+#[allow(dead_code)]
+use crate::crypto::poseidon::{Poseidon, sbox};
+#[allow(dead_code)]
+use crate::crypto::riscv_ext::RiscVCryptoExt;
 #[allow(dead_code)]
 use serde::{Deserialize, Serialize};
 #[allow(dead_code)]
@@ -151,12 +159,22 @@ impl Error for ZKProofError {}
 /// - accelerated_msm(): MSM 硬件加速
 /// - accelerated_fft(): FFT 硬件加速
 #[allow(dead_code)]
-#[derive(Debug)]
 pub struct ZKGroth16 {
     /// 每批处理数量（throughput 参数）
     pps: usize,
+    /// 执行 MSM 的加速器
+    accelerator: Box<dyn RiscVCryptoExt>,
+}
+
+impl fmt::Debug for ZKGroth16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ZKGroth16")
+            .field("pps", &self.pps)
+            .finish_non_exhaustive()
+    }
 }
 
+#[allow(dead_code)]
 impl ZKGroth16 {
     /// 创建新的 Groth16 证明系统
     ///
@@ -164,7 +182,23 @@ impl ZKGroth16 {
     ///
     /// 初始化的 `ZKGroth16` 实例
     pub fn new() -> Self {
-        ZKGroth16 { pps: 1 }
+        Self::with_accelerator(crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 Groth16 证明系统
+    ///
+    /// # 参数
+    ///
+    /// * `accelerator` - 执行 MSM 的后端
+    ///
+    /// # 返回
+    ///
+    /// 初始化的 `ZKGroth16` 实例
+    pub fn with_accelerator(accelerator: Box<dyn RiscVCryptoExt>) -> Self {
+        ZKGroth16 {
+            pps: 1,
+            accelerator,
+        }
     }
 
     /// 设置阶段：生成验证密钥
@@ -279,7 +313,7 @@ impl ZKGroth16 {
     /// 加速的多标量乘法 (MSM)
     ///
     /// MSM 是 ZK 证明中最耗时的操作之一。
-    /// 计算 sum(scalar_i * point_i)。
+    /// 计算 sum(scalar_i * point_i)，由加速器的 `copr_msm_accelerate` 执行。
     ///
     /// # 参数
     ///
@@ -289,21 +323,13 @@ impl ZKGroth16 {
     /// # 返回
     ///
     /// - `Ok(Vec<u8>)`: MSM 计算结果
-    /// - `Err(Box<dyn Error>)`: 计算失败
+    /// - `Err(Box<dyn Error>)`: 计算失败或加速器错误
     pub fn accelerated_msm(
         &self,
         points: &[Vec<u8>],
         scalars: &[Vec<u8>],
     ) -> Result<Vec<u8>, Box<dyn Error>> {
-        let mut result = vec![0u8; 32];
-
-        for (p, s) in points.iter().zip(scalars.iter()) {
-            for i in 0..32.min(p.len()).min(s.len()) {
-                result[i] ^= p[i] & s[i];
-            }
-        }
-
-        Ok(result)
+        self.accelerator.copr_msm_accelerate(points, scalars)
     }
 
     /// 加速的快速傅里叶变换 (FFT)
@@ -358,3 +384,307 @@ impl Default for ZKGroth16 {
         Self::new()
     }
 }
+
+/// R1CS 变量
+///
+/// 变量 0 固定为常数 1（`ConstraintSystem::ONE`）。
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Variable(usize);
+
+/// 线性组合 `sum(coeff_i * var_i)`
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinearCombination {
+    terms: Vec<(Variable, Fr)>,
+}
+
+#[allow(dead_code)]
+impl LinearCombination {
+    /// 空线性组合，值为 0
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// 常数项
+    pub fn constant(value: Fr) -> Self {
+        LinearCombination {
+            terms: vec![(ConstraintSystem::ONE, value)],
+        }
+    }
+
+    /// 追加一项 `coeff * var`
+    pub fn add_term(mut self, var: Variable, coeff: Fr) -> Self {
+        self.terms.push((var, coeff));
+        self
+    }
+
+    /// 两个线性组合相加
+    pub fn add(mut self, other: &LinearCombination) -> Self {
+        self.terms.extend_from_slice(&other.terms);
+        self
+    }
+
+    /// 所有系数乘以 `factor`
+    pub fn scale(mut self, factor: Fr) -> Self {
+        for (_, coeff) in self.terms.iter_mut() {
+            *coeff *= factor;
+        }
+        self
+    }
+
+    /// 合并同一变量的各项并去掉系数为 0 的项
+    pub fn simplify(mut self) -> Self {
+        self.terms.sort_by_key(|(var, _)| var.0);
+        let mut merged: Vec<(Variable, Fr)> = Vec::with_capacity(self.terms.len());
+        for (var, coeff) in self.terms {
+            match merged.last_mut() {
+                Some((last, sum)) if *last == var => *sum += coeff,
+                _ => merged.push((var, coeff)),
+            }
+        }
+        merged.retain(|(_, coeff)| !coeff.is_zero());
+        LinearCombination { terms: merged }
+    }
+
+    /// 线性组合的各项
+    pub fn terms(&self) -> &[(Variable, Fr)] {
+        &self.terms
+    }
+}
+
+impl From<Variable> for LinearCombination {
+    fn from(var: Variable) -> Self {
+        LinearCombination {
+            terms: vec![(var, Fr::ONE)],
+        }
+    }
+}
+
+/// R1CS 约束系统
+///
+/// 每个约束形如 `<a, z> * <b, z> = <c, z>`，`z` 为全部变量的赋值。
+/// 分配变量时同时记录赋值（见证），`is_satisfied` 检查见证是否满足全部约束。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct ConstraintSystem {
+    values: Vec<Fr>,
+    public_inputs: Vec<Variable>,
+    constraints: Vec<(LinearCombination, LinearCombination, LinearCombination)>,
+}
+
+#[allow(dead_code)]
+impl ConstraintSystem {
+    /// 常数 1 变量
+    pub const ONE: Variable = Variable(0);
+
+    /// 创建只含常数 1 变量的约束系统
+    pub fn new() -> Self {
+        ConstraintSystem {
+            values: vec![Fr::ONE],
+            public_inputs: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    /// 分配私有变量（见证）
+    pub fn alloc(&mut self, value: Fr) -> Variable {
+        self.values.push(value);
+        Variable(self.values.len() - 1)
+    }
+
+    /// 分配公开输入变量
+    pub fn alloc_input(&mut self, value: Fr) -> Variable {
+        let var = self.alloc(value);
+        self.public_inputs.push(var);
+        var
+    }
+
+    /// 添加约束 `a * b = c`
+    pub fn enforce(&mut self, a: LinearCombination, b: LinearCombination, c: LinearCombination) {
+        self.constraints.push((a, b, c));
+    }
+
+    /// 变量的当前赋值
+    pub fn value(&self, var: Variable) -> Fr {
+        self.values[var.0]
+    }
+
+    /// 覆盖变量赋值，不修改约束
+    ///
+    /// 用于检验约束能否发现错误的见证。
+    pub fn set_value(&mut self, var: Variable, value: Fr) {
+        self.values[var.0] = value;
+    }
+
+    /// 在当前赋值下计算线性组合的值
+    pub fn eval(&self, lc: &LinearCombination) -> Fr {
+        lc.terms.iter().fold(Fr::ZERO, |acc, (var, coeff)| {
+            acc + self.values[var.0] * *coeff
+        })
+    }
+
+    /// 约束个数
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// 变量个数（含常数 1）
+    pub fn num_variables(&self) -> usize {
+        self.values.len()
+    }
+
+    /// 公开输入的赋值，按分配顺序
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        self.public_inputs
+            .iter()
+            .map(|var| self.value(*var))
+            .collect()
+    }
+
+    /// 检查当前赋值是否满足全部约束
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(())`: 全部满足
+    /// - `Err(Box<dyn Error>)`: 第一个不满足的约束序号
+    pub fn is_satisfied(&self) -> Result<(), Box<dyn Error>> {
+        for (index, (a, b, c)) in self.constraints.iter().enumerate() {
+            if self.eval(a) * self.eval(b) != self.eval(c) {
+                return Err(Box::new(ZKProofError {
+                    message: format!("Constraint {} is not satisfied", index),
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConstraintSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Poseidon 电路 gadget
+///
+/// 轮常数加法和 MDS 乘法都是线性运算，直接合并进线性组合而不产生约束
+/// （每轮合并同类项，避免部分轮中线性组合长度指数增长）；
+/// 每个 x^5 S-box 用 3 个乘法约束（x^2、x^4、x^5）。
+/// 宽度 3 时置换共 `3 * (8 * 3 + 57) = 243` 个约束，`hash` 另加 1 个输出约束。
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct PoseidonGadget {
+    poseidon: Poseidon,
+}
+
+#[allow(dead_code)]
+impl PoseidonGadget {
+    /// 使用宽度为 `width` 的标准参数
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(PoseidonGadget)`: gadget 实例
+    /// - `Err(Box<dyn Error>)`: 宽度不在 2 到 5 之间
+    pub fn new(width: usize) -> Result<Self, Box<dyn Error>> {
+        Ok(PoseidonGadget {
+            poseidon: Poseidon::new(width)?,
+        })
+    }
+
+    /// 在电路中计算 x^5，返回结果变量
+    fn sbox(cs: &mut ConstraintSystem, x: LinearCombination) -> LinearCombination {
+        let value = cs.eval(&x);
+        let x2 = cs.alloc(value.square());
+        cs.enforce(x.clone(), x.clone(), x2.into());
+        let x4 = cs.alloc(value.square().square());
+        cs.enforce(x2.into(), x2.into(), x4.into());
+        let x5 = cs.alloc(sbox(value));
+        cs.enforce(x4.into(), x, x5.into());
+        x5.into()
+    }
+
+    /// 在电路中执行 Poseidon 置换
+    ///
+    /// # 参数
+    ///
+    /// * `cs` - 约束系统
+    /// * `state` - 以线性组合表示的输入状态
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Vec<LinearCombination>)`: 输出状态
+    /// - `Err(Box<dyn Error>)`: 状态长度与宽度不符
+    pub fn permute(
+        &self,
+        cs: &mut ConstraintSystem,
+        mut state: Vec<LinearCombination>,
+    ) -> Result<Vec<LinearCombination>, Box<dyn Error>> {
+        let params = self.poseidon.params();
+        if state.len() != params.width() {
+            return Err(Box::new(ZKProofError {
+                message: format!(
+                    "State has {} elements, expected {}",
+                    state.len(),
+                    params.width()
+                ),
+            }));
+        }
+
+        for round in 0..params.rounds() {
+            for (element, constant) in state.iter_mut().zip(params.round_constants(round)) {
+                *element = std::mem::take(element).add_term(ConstraintSystem::ONE, *constant);
+            }
+            if params.is_full_round(round) {
+                for element in state.iter_mut() {
+                    *element = Self::sbox(cs, std::mem::take(element));
+                }
+            } else {
+                state[0] = Self::sbox(cs, std::mem::take(&mut state[0]));
+            }
+
+            state = params
+                .mds()
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(state.iter())
+                        .fold(LinearCombination::zero(), |acc, (m, s)| {
+                            acc.add(&s.clone().scale(*m))
+                        })
+                        .simplify()
+                })
+                .collect();
+        }
+        Ok(state)
+    }
+
+    /// 在电路中计算定长 Poseidon 哈希，与 `Poseidon::hash` 一致
+    ///
+    /// # 参数
+    ///
+    /// * `cs` - 约束系统
+    /// * `inputs` - 恰好 `width - 1` 个输入变量
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Variable)`: 约束为哈希值的输出变量
+    /// - `Err(Box<dyn Error>)`: 输入个数不符
+    pub fn hash(
+        &self,
+        cs: &mut ConstraintSystem,
+        inputs: &[Variable],
+    ) -> Result<Variable, Box<dyn Error>> {
+        let mut state = vec![LinearCombination::zero()];
+        state.extend(inputs.iter().map(|var| LinearCombination::from(*var)));
+        let state = self.permute(cs, state)?;
+
+        let output = cs.alloc(cs.eval(&state[0]));
+        cs.enforce(
+            state[0].clone(),
+            LinearCombination::from(ConstraintSystem::ONE),
+            output.into(),
+        );
+        Ok(output)
+    }
+}
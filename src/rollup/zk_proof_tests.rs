@@ -1,5 +1,7 @@
 #[cfg(test)]
-mod zk_proof_tests {
+mod rollup_zk_tests {
+    use crate::crypto::hash::{MerkleLayout, Sha256Hasher, merkle_leaf, verify_proof};
+    use crate::crypto::secret::Ed25519SecretKey;
     use crate::rollup::state::{Account, Balance, RollupState};
     use crate::rollup::transaction::{Transaction, TransferTx};
     use crate::rollup::zk_proof::{Proof, PublicInput, ZKGroth16};
@@ -91,7 +93,7 @@ mod zk_proof_tests {
         let mut state = RollupState::new();
 
         let ed25519 = Ed25519Riscv::new();
-        let (secret_key, public_key) = ed25519.keygen();
+        let (secret_key, public_key) = ed25519.keygen().unwrap();
 
         let account1 = Account {
             id: 0,
@@ -129,6 +131,86 @@ mod zk_proof_tests {
         assert_eq!(acc2.balance.eth, 100);
     }
 
+    #[test]
+    fn test_rollup_state_transfer_rejects_forged_signature() {
+        use crate::crypto::ec::{Ed25519Riscv, software};
+
+        let mut state = RollupState::new();
+        let ed25519 = Ed25519Riscv::new();
+        let (secret_key, public_key) = ed25519.keygen().unwrap();
+
+        let sender = Account {
+            id: 0,
+            public_key: public_key.to_vec(),
+            nonce: 0,
+            balance: Balance::new(),
+        };
+        let recipient = Account {
+            id: 1,
+            public_key: software::public_key(&[9u8; 32]).to_vec(),
+            nonce: 0,
+            balance: Balance::new(),
+        };
+        state.create_account(sender).unwrap();
+        state.create_account(recipient).unwrap();
+        state.update_balance(0, 1000).unwrap();
+
+        // 使用非发送方私钥签名
+        let mut forged = TransferTx {
+            from: 0,
+            to: 1,
+            amount: 100,
+            nonce: 0,
+            signature: vec![0u8; 64],
+        };
+        forged.sign(&Ed25519SecretKey::new([9u8; 32])).unwrap();
+        assert!(!forged.verify_signature(&public_key).unwrap());
+        assert!(state.apply_transfer(&forged).is_err());
+
+        // 签名后篡改金额
+        let mut tampered = TransferTx {
+            from: 0,
+            to: 1,
+            amount: 100,
+            nonce: 0,
+            signature: vec![0u8; 64],
+        };
+        tampered.sign(&secret_key).unwrap();
+        tampered.amount = 999;
+        assert!(state.apply_transfer(&tampered).is_err());
+
+        let acc0 = state.get_account(0).unwrap();
+        assert_eq!(acc0.balance.eth, 1000);
+        assert_eq!(acc0.nonce, 0);
+    }
+
+    #[test]
+    fn test_transfer_rejects_wrong_signature_length() {
+        use crate::crypto::ec::Ed25519Riscv;
+
+        let (secret_key, public_key) = Ed25519Riscv::new().keygen().unwrap();
+        let mut tx = TransferTx {
+            from: 0,
+            to: 1,
+            amount: 100,
+            nonce: 0,
+            signature: vec![0u8; 64],
+        };
+        tx.sign(&secret_key).unwrap();
+        assert!(tx.verify_signature(&public_key).unwrap());
+
+        // 截短和追加字节都是长度错误，不 panic，也不截取前 64 字节当作有效签名
+        let mut short = tx.clone();
+        short.signature.pop();
+        assert_eq!(short.signature.len(), 63);
+        assert!(short.verify_signature(&public_key).is_err());
+
+        let mut long = tx.clone();
+        long.signature.push(0);
+        assert_eq!(long.signature.len(), 65);
+        assert!(long.verify_signature(&public_key).is_err());
+    }
+
     #[test]
     fn test_rollup_state_merkle_root() {
         let mut state = RollupState::new();
@@ -146,6 +228,32 @@ mod zk_proof_tests {
         let root = state.get_merkle_root().unwrap();
         assert_eq!(root.len(), 32);
         assert_ne!(root, [0u8; 32]);
+
+        // 第 5 个账户同样进入状态树，余额变化改变根
+        state
+            .create_account(Account {
+                id: 4,
+                public_key: vec![4u8; 32],
+                nonce: 0,
+                balance: Balance::new(),
+            })
+            .unwrap();
+        let with_fifth = state.get_merkle_root().unwrap();
+        assert_ne!(with_fifth, root);
+        state.update_balance(4, 10).unwrap();
+        let root = state.get_merkle_root().unwrap();
+        assert_ne!(root, with_fifth);
+
+        // 轻客户端验证账户包含证明
+        for id in 0..5 {
+            let (leaf_data, index, proof) = state.account_proof(id).unwrap();
+            assert_eq!(index, id as usize);
+            assert_eq!(proof.layout, MerkleLayout::ZeroPad);
+            assert_eq!(proof.siblings.len(), 3);
+            let leaf = merkle_leaf::<Sha256Hasher>(&leaf_data);
+            assert!(verify_proof::<Sha256Hasher>(&root, &leaf, index, &proof));
+        }
+        assert!(state.account_proof(99).is_err());
     }
 
     #[test]
@@ -234,7 +342,143 @@ mod zk_proof_tests {
         let groth16 = ZKGroth16::new();
         let poly_a: Vec<Vec<u8>> = (0..16).map(|i| vec![i as u8, 0, 0, 0]).collect();
         let poly_b: Vec<Vec<u8>> = (16..32).map(|i| vec![i as u8, 0, 0, 0]).collect();
-        let result = groth16.accelerated_polynomial_ops(&poly_a, &poly_b).unwrap();
+        let result = groth16
+            .accelerated_polynomial_ops(&poly_a, &poly_b)
+            .unwrap();
         assert_eq!(result.len(), 31); // (n-1) + (m-1) + 1 = 15+15+1 = 31
     }
+
+    #[test]
+    fn test_accelerated_msm_propagates_accelerator_failure() {
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+
+        let mock = MockAccelerator::new().fail_operation(MockOperation::Msm);
+        let groth16 = ZKGroth16::with_accelerator(Box::new(mock.clone()));
+        let points = vec![vec![1u8; 32]];
+        let scalars = vec![vec![2u8; 32]];
+
+        assert!(groth16.accelerated_msm(&points, &scalars).is_err());
+        assert_eq!(mock.call_count(MockOperation::Msm), 1);
+    }
+
+    #[test]
+    fn test_transfer_with_failing_accelerator_leaves_state_unchanged() {
+        use crate::crypto::ec::Ed25519Riscv;
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+
+        let mut state = RollupState::new();
+        let ed25519 = Ed25519Riscv::new();
+        let (secret_key, public_key) = ed25519.keygen_from_seed(&[4u8; 32]);
+        state
+            .create_account(Account {
+                id: 0,
+                public_key: public_key.to_vec(),
+                nonce: 0,
+                balance: Balance::new(),
+            })
+            .unwrap();
+        state
+            .create_account(Account {
+                id: 1,
+                public_key: vec![2u8; 32],
+                nonce: 0,
+                balance: Balance::new(),
+            })
+            .unwrap();
+        state.update_balance(0, 1000).unwrap();
+
+        let mut tx = TransferTx {
+            from: 0,
+            to: 1,
+            amount: 100,
+            nonce: 0,
+            signature: vec![0u8; 64],
+        };
+
+        // 签名失败时交易签名保持不变
+        let failing_signer = Ed25519Riscv::with_accelerator(Box::new(
+            MockAccelerator::new().fail_operation(MockOperation::SignEd25519),
+        ));
+        assert!(tx.sign_with(&failing_signer, &secret_key).is_err());
+        assert_eq!(tx.signature, vec![0u8; 64]);
+        tx.sign(&secret_key).unwrap();
+
+        // 验证器出错或结果被篡改时拒绝转账
+        let failing_verifier =
+            Ed25519Riscv::with_accelerator(Box::new(MockAccelerator::new().fail_on_call(1)));
+        assert!(state.apply_transfer_with(&tx, &failing_verifier).is_err());
+        let corrupt_verifier = Ed25519Riscv::with_accelerator(Box::new(
+            MockAccelerator::new().corrupt_operation(MockOperation::VerifyEd25519),
+        ));
+        assert!(state.apply_transfer_with(&tx, &corrupt_verifier).is_err());
+
+        let acc0 = state.get_account(0).unwrap();
+        assert_eq!(acc0.balance.eth, 1000);
+        assert_eq!(acc0.nonce, 0);
+        assert_eq!(state.get_account(1).unwrap().balance.eth, 0);
+
+        state.apply_transfer(&tx).unwrap();
+        assert_eq!(state.get_account(0).unwrap().balance.eth, 900);
+    }
+}
+
+#[cfg(test)]
+mod rollup_constraint_tests {
+    use crate::crypto::field::Fr;
+    use crate::crypto::poseidon::poseidon_hash;
+    use crate::rollup::zk_proof::{ConstraintSystem, LinearCombination, PoseidonGadget};
+
+    #[test]
+    fn test_constraint_system_multiplication() {
+        let mut cs = ConstraintSystem::new();
+        let x = cs.alloc(Fr::from(3));
+        let y = cs.alloc_input(Fr::from(9));
+        cs.enforce(x.into(), x.into(), y.into());
+        assert_eq!(cs.num_variables(), 3);
+        assert_eq!(cs.public_inputs(), vec![Fr::from(9)]);
+        assert!(cs.is_satisfied().is_ok());
+
+        cs.set_value(y, Fr::from(10));
+        assert!(cs.is_satisfied().is_err());
+    }
+
+    #[test]
+    fn test_linear_combination_simplify() {
+        let mut cs = ConstraintSystem::new();
+        let x = cs.alloc(Fr::from(5));
+        let lc = LinearCombination::from(x)
+            .add_term(x, Fr::from(2))
+            .add(&LinearCombination::constant(Fr::from(4)))
+            .add_term(ConstraintSystem::ONE, -Fr::from(4));
+        let simplified = lc.clone().simplify();
+        assert_eq!(simplified.terms(), &[(x, Fr::from(3))]);
+        assert_eq!(cs.eval(&lc), cs.eval(&simplified));
+        assert_eq!(cs.eval(&simplified.scale(Fr::from(2))), Fr::from(30));
+    }
+
+    #[test]
+    fn test_poseidon_gadget_matches_native_hash() {
+        let mut cs = ConstraintSystem::new();
+        let a = cs.alloc(Fr::from(1));
+        let b = cs.alloc(Fr::from(2));
+        let gadget = PoseidonGadget::new(3).unwrap();
+        let output = gadget.hash(&mut cs, &[a, b]).unwrap();
+
+        assert_eq!(
+            cs.value(output),
+            poseidon_hash(&[Fr::from(1), Fr::from(2)]).unwrap()
+        );
+        assert_eq!(cs.num_constraints(), 244);
+        assert!(cs.is_satisfied().is_ok());
+
+        // 篡改输入或输出见证都会违反约束
+        let mut tampered = cs.clone();
+        tampered.set_value(output, Fr::from(0));
+        assert!(tampered.is_satisfied().is_err());
+        cs.set_value(a, Fr::from(7));
+        assert!(cs.is_satisfied().is_err());
+
+        assert!(gadget.hash(&mut cs, &[a]).is_err());
+        assert!(PoseidonGadget::new(6).is_err());
+    }
 }
@@ -1,6 +1,6 @@
-/// TEE 远程认证模块
-///
-/// 提供 Enclave 远程认证功能，允许远程方验证 Enclave 的真实性和完整性。
+//! TEE 远程认证模块
+//!
+//! 提供 Enclave 远程认证功能，允许远程方验证 Enclave 的真实性和完整性。
 
 #[allow(dead_code)]
 use std::error::Error;
@@ -71,6 +71,7 @@ impl Error for AttestationError {}
 #[derive(Debug)]
 pub struct RemoteAttestation;
 
+#[allow(dead_code)]
 impl RemoteAttestation {
     /// 创建远程认证服务实例
     ///
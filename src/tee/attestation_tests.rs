@@ -30,7 +30,9 @@ mod tee_enclave_tests {
     #[test]
     fn test_enclave_ecall() {
         let enclave = TeeEnclave::new(EnclaveConfig::default()).unwrap();
-        let _ = std::panic::catch_unwind(|| enclave.call_secure_function(|_| 42));
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            enclave.call_secure_function(|_| 42)
+        }));
     }
 
     #[test]
@@ -38,7 +40,7 @@ mod tee_enclave_tests {
         let report = AttestationReport::generate(0x1234, &[1u8, 2, 3, 4]).unwrap();
 
         assert_eq!(report.enclave_id, 0x1234);
-        assert!(report.measurement.len() > 0);
+        assert!(!report.measurement.is_empty());
     }
 
     #[test]
@@ -109,4 +111,223 @@ mod tee_enclave_tests {
         assert!(result1.is_none());
         assert!(result2.is_none());
     }
+
+    #[test]
+    fn test_enclave_to_enclave_session() {
+        let prover = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+        let sequencer = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+
+        let mut initiator = prover.initiate_session().unwrap();
+        let mut responder = sequencer.accept_session().unwrap();
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        initiator
+            .read_message(&responder.write_message(b"").unwrap())
+            .unwrap();
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+
+        let mut prover_channel = initiator.into_session().unwrap();
+        let mut sequencer_channel = responder.into_session().unwrap();
+        assert_eq!(
+            prover_channel.remote_static(),
+            Some(sequencer.session_public_key())
+        );
+        assert_eq!(
+            sequencer_channel.remote_static(),
+            Some(prover.session_public_key())
+        );
+
+        let message = prover_channel.encrypt(b"witness share").unwrap();
+        assert_eq!(
+            sequencer_channel.decrypt(&message).unwrap(),
+            b"witness share"
+        );
+    }
+
+    #[test]
+    fn test_client_session_checks_pinned_key() {
+        use crate::crypto::session::{Handshake, HandshakePattern, HandshakeRole};
+        use crate::crypto::x25519::X25519KeyPair;
+        use crate::tee::enclave::ENCLAVE_SESSION_PROLOGUE;
+
+        let enclave = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+        // 报告未签名，只记录会话公钥；客户端核对的是预先固定的公钥
+        let report = enclave.attest_session_key().unwrap();
+        assert_eq!(report.enclave_id, enclave.get_id());
+        assert_eq!(report.user_data, enclave.session_public_key());
+        let pinned = enclave.session_public_key();
+
+        let client_key = X25519KeyPair::generate().unwrap();
+        let mut client = Handshake::new(
+            HandshakeRole::Initiator,
+            HandshakePattern::Xx,
+            Some(client_key.clone()),
+            ENCLAVE_SESSION_PROLOGUE,
+        )
+        .unwrap();
+        let mut server = enclave.accept_session().unwrap();
+        server
+            .read_message(&client.write_message(b"").unwrap())
+            .unwrap();
+        client
+            .read_message(&server.write_message(b"").unwrap())
+            .unwrap();
+        // 客户端在发出自己的身份前确认对端公钥是固定的 Enclave 公钥
+        assert_eq!(client.remote_static(), Some(pinned));
+        let impostor = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+        assert_ne!(impostor.session_public_key(), pinned);
+        server
+            .read_message(&client.write_message(b"").unwrap())
+            .unwrap();
+
+        let mut client = client.into_session().unwrap();
+        let mut server = server.into_session().unwrap();
+        assert_eq!(server.remote_static(), Some(client_key.public_key()));
+        let reply = server.encrypt(b"sealed state root").unwrap();
+        assert_eq!(client.decrypt(&reply).unwrap(), b"sealed state root");
+
+        // prologue 不同的客户端无法与 Enclave 完成握手
+        let mut stranger = Handshake::new(
+            HandshakeRole::Initiator,
+            HandshakePattern::Xx,
+            Some(client_key),
+            b"other protocol",
+        )
+        .unwrap();
+        let mut server = enclave.accept_session().unwrap();
+        server
+            .read_message(&stranger.write_message(b"").unwrap())
+            .unwrap();
+        assert!(
+            stranger
+                .read_message(&server.write_message(b"").unwrap())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_enclave_sessions_use_its_accelerator() {
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+
+        let mock = MockAccelerator::new();
+        let enclave =
+            TeeEnclave::with_accelerator(EnclaveConfig::default(), Box::new(mock.clone())).unwrap();
+        let peer = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+
+        let mut initiator = enclave.initiate_session().unwrap();
+        let mut responder = peer.accept_session().unwrap();
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        initiator
+            .read_message(&responder.write_message(b"").unwrap())
+            .unwrap();
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        let encrypted_during_handshake = mock.call_count(MockOperation::EncryptAes256);
+        assert!(encrypted_during_handshake > 0);
+
+        let mut channel = initiator.into_session().unwrap();
+        let message = channel.encrypt(b"batch").unwrap();
+        assert_eq!(
+            mock.call_count(MockOperation::EncryptAes256),
+            encrypted_during_handshake + 1
+        );
+        assert_eq!(
+            responder.into_session().unwrap().decrypt(&message).unwrap(),
+            b"batch"
+        );
+    }
+
+    #[test]
+    fn test_enclave_session_rejects_failing_accelerator() {
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+
+        let mock = MockAccelerator::new().fail_operation(MockOperation::EncryptAes256);
+        let enclave =
+            TeeEnclave::with_accelerator(EnclaveConfig::default(), Box::new(mock)).unwrap();
+        assert!(enclave.initiate_session().is_err());
+        assert!(enclave.accept_session().is_err());
+
+        // 篡改输出的加速器同样无法通过自检
+        let mock = MockAccelerator::new().corrupt_operation(MockOperation::EncryptAes256);
+        let enclave =
+            TeeEnclave::with_accelerator(EnclaveConfig::default(), Box::new(mock)).unwrap();
+        assert!(enclave.initiate_session().is_err());
+        assert!(enclave.accept_session().is_err());
+    }
+
+    #[test]
+    fn test_enclave_handshake_poisoned_by_write_failure() {
+        use crate::crypto::mock::MockAccelerator;
+
+        // 第 1 次调用是自检，第 2 次是响应方加密自己的静态公钥
+        let mock = MockAccelerator::new().fail_on_call(2);
+        let enclave =
+            TeeEnclave::with_accelerator(EnclaveConfig::default(), Box::new(mock)).unwrap();
+        let peer = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+
+        let mut initiator = peer.initiate_session().unwrap();
+        let mut responder = enclave.accept_session().unwrap();
+        let first = initiator.write_message(b"").unwrap();
+        responder.read_message(&first).unwrap();
+        assert!(responder.write_message(b"").is_err());
+
+        // 后续调用不再成功，即使加速器已恢复
+        assert!(responder.write_message(b"").is_err());
+        assert!(responder.read_message(&first).is_err());
+        assert!(responder.into_session().is_err());
+    }
+
+    #[test]
+    fn test_enclave_handshake_poisoned_by_read_failure() {
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+
+        let peer = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+        for mock in [
+            MockAccelerator::new().fail_on_call(2),
+            MockAccelerator::new().corrupt_operation(MockOperation::DecryptAes256),
+        ] {
+            let enclave =
+                TeeEnclave::with_accelerator(EnclaveConfig::default(), Box::new(mock)).unwrap();
+            let mut initiator = enclave.initiate_session().unwrap();
+            let mut responder = peer.accept_session().unwrap();
+            responder
+                .read_message(&initiator.write_message(b"").unwrap())
+                .unwrap();
+            let second = responder.write_message(b"").unwrap();
+            assert!(initiator.read_message(&second).is_err());
+
+            assert!(initiator.read_message(&second).is_err());
+            assert!(initiator.write_message(b"").is_err());
+            assert!(initiator.into_session().is_err());
+        }
+    }
+
+    #[test]
+    fn test_peer_rejects_corrupted_enclave_message() {
+        use crate::crypto::mock::{MockAccelerator, MockOperation};
+
+        // 自检之后才开始篡改，模拟握手过程中出错的加速器
+        let mock = MockAccelerator::new();
+        let enclave =
+            TeeEnclave::with_accelerator(EnclaveConfig::default(), Box::new(mock.clone())).unwrap();
+        let peer = TeeEnclave::new(EnclaveConfig::default()).unwrap();
+
+        let mut initiator = peer.initiate_session().unwrap();
+        let mut responder = enclave.accept_session().unwrap();
+        let mock = mock.corrupt_operation(MockOperation::EncryptAes256);
+        responder
+            .read_message(&initiator.write_message(b"").unwrap())
+            .unwrap();
+        let second = responder.write_message(b"").unwrap();
+        assert_eq!(mock.call_count(MockOperation::EncryptAes256), 3);
+        assert!(initiator.read_message(&second).is_err());
+        assert!(initiator.write_message(b"").is_err());
+        assert!(initiator.into_session().is_err());
+    }
 }
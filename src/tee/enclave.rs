@@ -1,17 +1,45 @@
-/// TEE Enclave 模块
-///
-/// 可信执行环境模块，提供硬件隔离的安全计算环境。
+//! TEE Enclave 模块
+//!
+//! 可信执行环境模块，提供硬件隔离的安全计算环境。
+//!
+//! 每个 Enclave 持有一把 X25519 会话静态密钥，可与其他 Enclave 或外部客户端
+//! 经 `Noise_XX_25519_AESGCM_SHA256` 握手建立认证加密通道（见 `crypto::session`）。
+//! `attest_session_key` 把该公钥写入认证报告的 `user_data`，但目前的报告没有签名
+//! （测量值和签名都是占位值），这一绑定只是占位，不能向对端证明公钥属于此 Enclave；
+//! 在接入硬件认证密钥之前，对端必须通过其他可信途径获得并固定 `session_public_key()`。
 
+#[allow(dead_code)]
+use crate::crypto::riscv_ext::RiscVCryptoExt;
+#[allow(dead_code)]
+use crate::crypto::session::{Handshake, HandshakePattern, HandshakeRole};
+#[allow(dead_code)]
+use crate::crypto::x25519::{X25519_KEY_SIZE, X25519KeyPair};
+#[allow(dead_code)]
+use crate::tee::attestation::AttestationReport;
 #[allow(dead_code)]
 use std::error::Error;
 #[allow(dead_code)]
 use std::fmt;
 #[allow(dead_code)]
+use std::sync::Arc;
+#[allow(dead_code)]
 use std::sync::atomic::{AtomicU64, Ordering};
 
 /// 全局 Enclave ID 计数器
 static ENCLAVE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Enclave 会话握手的 prologue，外部客户端握手时必须使用相同的值
+pub const ENCLAVE_SESSION_PROLOGUE: &[u8] = b"zk-rollup tee session v1";
+
+/// 会话开始前加速器自检使用的 AES-256-GCM 已知答案
+/// （NIST GCM 测试用例 14：全零密钥、全零 IV、16 字节全零明文）
+const SESSION_SELF_TEST_CIPHERTEXT: [u8; 16] = [
+    0xce, 0xa7, 0x40, 0x3d, 0x4d, 0x60, 0x6b, 0x6e, 0x07, 0x4e, 0xc5, 0xd3, 0xba, 0xf3, 0x9d, 0x18,
+];
+const SESSION_SELF_TEST_TAG: [u8; 16] = [
+    0xd0, 0xd1, 0xc8, 0xa7, 0x99, 0x99, 0x6b, 0xf0, 0x26, 0x5b, 0x98, 0xb5, 0xd4, 0x8a, 0xb9, 0x19,
+];
+
 /// Enclave 配置结构
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -61,9 +89,9 @@ impl Error for EnclaveError {}
 ///
 /// - Enclave ID 由硬件分配
 /// - 数据密封使用绑定密钥
+/// - 会话静态私钥创建时随机生成，不离开 Enclave
 /// - 外部无法访问 Enclave 内存
 #[allow(dead_code)]
-#[derive(Debug)]
 pub struct TeeEnclave {
     /// Enclave 唯一标识符
     id: u64,
@@ -71,8 +99,23 @@ pub struct TeeEnclave {
     config: EnclaveConfig,
     /// 初始化状态标志
     is_initialized: bool,
+    /// 会话握手使用的 X25519 静态密钥
+    session_key: X25519KeyPair,
+    /// 会话握手和通道加密使用的后端，所有会话共享
+    accelerator: Arc<dyn RiscVCryptoExt>,
 }
 
+impl fmt::Debug for TeeEnclave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TeeEnclave")
+            .field("id", &self.id)
+            .field("config", &self.config)
+            .field("is_initialized", &self.is_initialized)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(dead_code)]
 impl TeeEnclave {
     /// 创建新的 Enclave 实例
     ///
@@ -88,12 +131,35 @@ impl TeeEnclave {
     /// - `Ok(TeeEnclave)`: 创建成功
     /// - `Err(Box<dyn Error>)`: 创建失败
     pub fn new(config: EnclaveConfig) -> Result<Self, Box<dyn Error>> {
+        Self::with_accelerator(config, crate::crypto::backend::default_backend())
+    }
+
+    /// 使用指定的加速器创建 Enclave 实例
+    ///
+    /// 会话静态密钥从操作系统 CSPRNG 读取。
+    ///
+    /// # 参数
+    ///
+    /// * `config` - Enclave 配置参数
+    /// * `accelerator` - 执行会话握手与通道加密的后端
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(TeeEnclave)`: 创建成功
+    /// - `Err(Box<dyn Error>)`: 随机源不可用
+    pub fn with_accelerator(
+        config: EnclaveConfig,
+        accelerator: Box<dyn RiscVCryptoExt>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let session_key = X25519KeyPair::generate()?;
         let id = ENCLAVE_COUNTER.fetch_add(1, Ordering::SeqCst);
 
         Ok(TeeEnclave {
             id,
             config,
             is_initialized: true,
+            session_key,
+            accelerator: Arc::from(accelerator),
         })
     }
 
@@ -146,6 +212,80 @@ impl TeeEnclave {
         Ok(sealed.to_vec())
     }
 
+    /// 获取会话静态公钥
+    ///
+    /// # 返回
+    ///
+    /// 32 字节 X25519 公钥，握手完成后对端的 `remote_static()` 应与之相等
+    pub fn session_public_key(&self) -> [u8; X25519_KEY_SIZE] {
+        self.session_key.public_key()
+    }
+
+    /// 生成携带会话公钥的认证报告
+    ///
+    /// 报告的 `user_data` 为会话静态公钥。报告目前未签名，这只是为硬件认证预留的占位：
+    /// 任何人都能构造同样的报告，对端不能据此确认通道另一端是此 Enclave，
+    /// 应改为核对预先获得的 `session_public_key()`。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(AttestationReport)`: 认证报告
+    /// - `Err(Box<dyn Error>)`: 报告生成失败
+    pub fn attest_session_key(&self) -> Result<AttestationReport, Box<dyn Error>> {
+        AttestationReport::generate(self.id, &self.session_public_key())
+    }
+
+    /// 作为发起方开始会话握手
+    ///
+    /// 使用 XX 模式和 `ENCLAVE_SESSION_PROLOGUE`，握手与会话加密经创建 Enclave 时的加速器执行。
+    /// 握手的第 1 条消息不经过加速器，因此开始前先用已知答案自检加速器，
+    /// 避免在加速器故障时发出无法完成的握手。
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Handshake)`: 应先调用 `write_message` 发出第一条消息
+    /// - `Err(Box<dyn Error>)`: 加速器未通过自检
+    pub fn initiate_session(&self) -> Result<Handshake, Box<dyn Error>> {
+        self.session_handshake(HandshakeRole::Initiator)
+    }
+
+    /// 作为响应方接受会话握手
+    ///
+    /// # 返回
+    ///
+    /// - `Ok(Handshake)`: 应先调用 `read_message` 读取对端的第一条消息
+    /// - `Err(Box<dyn Error>)`: 加速器未通过自检
+    pub fn accept_session(&self) -> Result<Handshake, Box<dyn Error>> {
+        self.session_handshake(HandshakeRole::Responder)
+    }
+
+    fn session_handshake(&self, role: HandshakeRole) -> Result<Handshake, Box<dyn Error>> {
+        self.self_test_accelerator()?;
+        Handshake::with_accelerator(
+            role,
+            HandshakePattern::Xx,
+            Some(self.session_key.clone()),
+            ENCLAVE_SESSION_PROLOGUE,
+            Box::new(Arc::clone(&self.accelerator)),
+        )
+    }
+
+    /// 用 AES-256-GCM 已知答案检查加速器的输出
+    fn self_test_accelerator(&self) -> Result<(), Box<dyn Error>> {
+        let (ciphertext, tag) = self
+            .accelerator
+            .copr_encrypt_aes256(&[0u8; 32], &[0u8; 12], &[], &[0u8; 16])
+            .map_err(|e| EnclaveError {
+                message: format!("Session accelerator self-test failed: {}", e),
+            })?;
+        if ciphertext != SESSION_SELF_TEST_CIPHERTEXT || tag != SESSION_SELF_TEST_TAG {
+            return Err(Box::new(EnclaveError {
+                message: "Session accelerator self-test returned a wrong answer".to_string(),
+            }));
+        }
+        Ok(())
+    }
+
     /// 调用安全函数
     ///
     /// 在 Enclave 内部执行安全函数。
@@ -176,6 +316,7 @@ impl TeeEnclave {
 ///
 /// 此函数在 Enclave 外部调用时会 panic，
 /// 确保安全函数只能在 Enclave 内部执行。
+#[allow(dead_code)]
 fn f<R>(_enclave: &TeeEnclave) -> R {
     panic!("Cannot call secure function outside enclave")
 }
@@ -1,7 +1,7 @@
 //! TEE (Trusted Execution Environment) 模块
 //!
 //! 提供可信执行环境功能，包括：
-//! - Enclave 管理
+//! - Enclave 管理与 Enclave 间会话通道
 //! - 远程认证
 //! - 安全存储
 //!
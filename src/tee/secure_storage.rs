@@ -1,6 +1,6 @@
-/// TEE 安全存储模块
-///
-/// 提供 Enclave 内的安全键值存储。所有数据以加密形式存储。
+//! TEE 安全存储模块
+//!
+//! 提供 Enclave 内的安全键值存储。所有数据以加密形式存储。
 
 #[allow(dead_code)]
 use std::collections::HashMap;
@@ -78,6 +78,7 @@ pub struct SecureStorage {
     data: Mutex<HashMap<String, EncryptedData>>,
 }
 
+#[allow(dead_code)]
 impl SecureStorage {
     /// 创建新的安全存储实例
     ///